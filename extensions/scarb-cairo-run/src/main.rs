@@ -1,12 +1,19 @@
 use anyhow::{anyhow, bail, ensure, Context, Result};
+use cairo_lang_runner::profiling::{
+    ProfilingInfo, ProfilingInfoProcessor, ProfilingInfoProcessorParams,
+};
 use cairo_lang_runner::short_string::as_cairo_short_string;
-use cairo_lang_runner::{RunResultStarknet, RunResultValue, SierraCasmRunner, StarknetState};
+use cairo_lang_runner::{
+    ProfilingInfoCollectionConfig, RunResultStarknet, RunResultValue, RunnerError,
+    SierraCasmRunner, StarknetState,
+};
 use cairo_lang_sierra::ids::FunctionId;
-use cairo_lang_sierra::program::{Function, ProgramArtifact, VersionedProgram};
+use cairo_lang_sierra::program::{Function, Program, ProgramArtifact, VersionedProgram};
 use camino::Utf8PathBuf;
 use clap::Parser;
 use indoc::formatdoc;
 use serde::Serializer;
+use starknet_types_core::felt::Felt as Felt252;
 use std::env;
 use std::fs;
 use std::process::ExitCode;
@@ -14,9 +21,9 @@ use std::process::ExitCode;
 use scarb_metadata::{
     CompilationUnitMetadata, Metadata, MetadataCommand, PackageId, PackageMetadata, ScarbCommand,
 };
-use scarb_ui::args::{PackagesFilter, VerbositySpec};
-use scarb_ui::components::Status;
-use scarb_ui::{Message, OutputFormat, Ui};
+use scarb_ui::args::{ColorSpec, PackagesFilter, VerbositySpec};
+use scarb_ui::components::{Select, Status};
+use scarb_ui::{Failure, FailureExt, FailureKind, Message, OutputFormat, Ui};
 
 mod deserialization;
 
@@ -55,6 +62,10 @@ struct Args {
     #[command(flatten)]
     pub verbose: VerbositySpec,
 
+    /// Coloring of the output.
+    #[command(flatten)]
+    pub color: ColorSpec,
+
     /// Program arguments.
     ///
     /// This should be a JSON array of numbers, decimal bigints or recursive arrays of those. For example, pass `[1]`
@@ -68,56 +79,52 @@ struct Args {
     /// It specified, `[ARGUMENTS]` CLI parameter will be ignored.
     #[arg(long)]
     arguments_file: Option<Utf8PathBuf>,
+
+    /// Path to a Sierra program file to run, instead of the compiled package.
+    ///
+    /// If specified, the package is neither built nor resolved.
+    #[arg(long, conflicts_with = "no_build")]
+    sierra_file: Option<Utf8PathBuf>,
+
+    /// Profile the run and print gas consumed, and weights, in steps, of user functions, libfuncs
+    /// and stack traces.
+    #[arg(long, default_value_t = false)]
+    profile: bool,
+
+    /// Write the profiling report to a file, instead of printing it.
+    #[arg(long, requires = "profile")]
+    profile_output: Option<Utf8PathBuf>,
 }
 
 fn main() -> ExitCode {
     let args: Args = Args::parse();
     let ui = Ui::new(args.verbose.clone().into(), OutputFormat::Text);
+    args.color.apply(&ui);
     ui.warn("`scarb cairo-run` will be deprecated soon\nhelp: use `scarb execute` instead");
     if let Err(err) = main_inner(&ui, args) {
         ui.anyhow(&err);
-        return ExitCode::FAILURE;
+        return FailureKind::of(&err).exit_code();
     }
     ExitCode::SUCCESS
 }
 
 fn main_inner(ui: &Ui, args: Args) -> Result<()> {
-    let metadata = MetadataCommand::new().inherit_stderr().exec()?;
-
-    let package = args.packages_filter.match_one(&metadata)?;
-
-    let available_gas = GasLimit::parse(args.available_gas).with_metadata(&metadata, &package)?;
-
-    let program_args = match args.arguments_file {
+    let program_args = match &args.arguments_file {
         Some(path) => serde_json::from_str::<deserialization::Args>(
             &fs::read_to_string(path.clone())
                 .with_context(|| format!("failed to read arguments from file: {path}"))?,
         )?,
-        None => args.arguments,
+        None => args.arguments.clone(),
     };
 
-    if !args.no_build {
-        let filter = PackagesFilter::generate_for::<Metadata>(vec![package.clone()].iter());
-        ScarbCommand::new()
-            .arg("build")
-            .env("SCARB_PACKAGES_FILTER", filter.to_env())
-            .run()?;
-    }
-
-    let filename = format!("{}.sierra.json", package.name);
-    let path = Utf8PathBuf::from(env::var("SCARB_TARGET_DIR")?)
-        .join(env::var("SCARB_PROFILE")?)
-        .join(filename.clone());
-
-    ensure!(
-        path.exists(),
-        formatdoc! {r#"
-            package has not been compiled, file does not exist: `{filename}`
-            help: run `scarb build` to compile the package
-        "#}
-    );
-
-    ui.print(Status::new("Running", &package.name));
+    let (path, available_gas) = match &args.sierra_file {
+        Some(path) => {
+            ensure!(path.exists(), "Sierra file does not exist: `{path}`");
+            ui.print(Status::new("Running", path.as_str()));
+            (path.clone(), GasLimit::parse(args.available_gas))
+        }
+        None => package_sierra_file(ui, &args)?,
+    };
 
     let sierra_program = serde_json::from_str::<VersionedProgram>(
         &fs::read_to_string(path.clone())
@@ -139,17 +146,31 @@ fn main_inner(ui: &Ui, args: Args) -> Result<()> {
             Some(Default::default())
         },
         Default::default(),
-        None,
+        args.profile.then(ProfilingInfoCollectionConfig::default),
     )?;
 
-    let result = runner
+    let mut result = runner
         .run_function_with_starknet_context(
-            main_function(&runner, &sierra_program, args.function.as_deref())?,
+            main_function(ui, &runner, &sierra_program, args.function.as_deref())?,
             program_args.into(),
             available_gas.value(),
             StarknetState::default(),
         )
-        .with_context(|| "failed to run the function")?;
+        .map_err(|err| {
+            let kind = match err {
+                RunnerError::ArgumentUnaligned { .. }
+                | RunnerError::ArgumentsSizeMismatch { .. } => FailureKind::Usage,
+                _ => FailureKind::Runtime,
+            };
+            Failure::new(kind, anyhow!(err).context("failed to run the function"))
+        })?;
+
+    let profiling_info = result.profiling_info.take();
+    let gas_consumed = available_gas
+        .value()
+        .zip(result.gas_counter)
+        .map(|(available, remaining)| Felt252::from(available) - remaining);
+    let panicked = matches!(result.value, RunResultValue::Panic(_));
 
     ui.print(Summary {
         result,
@@ -158,10 +179,87 @@ fn main_inner(ui: &Ui, args: Args) -> Result<()> {
         detailed_resources: args.print_resource_usage,
     });
 
+    if let Some(profiling_info) = profiling_info {
+        let report = profiling_report(&sierra_program.program, &profiling_info, gas_consumed);
+        match &args.profile_output {
+            Some(path) => fs::write(path, report)
+                .with_context(|| format!("failed to write profiling report: {path}"))?,
+            None => ui.print(report),
+        }
+    }
+
+    if panicked {
+        return Err(anyhow!("program panicked")).failure_kind(FailureKind::Runtime);
+    }
+
     Ok(())
 }
 
+/// Format profiling info collected during the run, the same way the Cairo profiler does.
+///
+/// Weights which require the compiler database, like these of Cairo functions, are not reported.
+/// The profiler only measures steps, so gas is reported for the whole run, not per function.
+fn profiling_report(
+    program: &Program,
+    profiling_info: &ProfilingInfo,
+    gas_consumed: Option<Felt252>,
+) -> String {
+    let params = ProfilingInfoProcessorParams {
+        process_by_statement: false,
+        process_by_generic_libfunc: false,
+        process_by_original_user_function: false,
+        process_by_cairo_function: false,
+        process_by_cairo_stack_trace: false,
+        ..Default::default()
+    };
+    let processor = ProfilingInfoProcessor::new(None, program.clone(), Default::default(), params);
+    let weights = processor.process(profiling_info);
+    match gas_consumed {
+        Some(gas) => format!("Gas consumed: {gas}\n{weights}"),
+        None => weights.to_string(),
+    }
+}
+
+/// Build the selected package, unless `--no-build` is passed, and find its Sierra program file.
+fn package_sierra_file(ui: &Ui, args: &Args) -> Result<(Utf8PathBuf, GasLimit)> {
+    let metadata = MetadataCommand::new().inherit_stderr().exec()?;
+
+    let package = args
+        .packages_filter
+        .match_one(&metadata)
+        .failure_kind(FailureKind::Usage)?;
+
+    let available_gas = GasLimit::parse(args.available_gas).with_metadata(&metadata, &package)?;
+
+    if !args.no_build {
+        let filter = PackagesFilter::generate_for::<Metadata>(vec![package.clone()].iter());
+        ScarbCommand::new()
+            .arg("build")
+            .env("SCARB_PACKAGES_FILTER", filter.to_env())
+            .env("SCARB_UI_VERBOSITY", ui.verbosity().to_string())
+            .run()?;
+    }
+
+    let filename = format!("{}.sierra.json", package.name);
+    let path = Utf8PathBuf::from(env::var("SCARB_TARGET_DIR")?)
+        .join(env::var("SCARB_PROFILE")?)
+        .join(filename.clone());
+
+    ensure!(
+        path.exists(),
+        formatdoc! {r#"
+            package has not been compiled, file does not exist: `{filename}`
+            help: run `scarb build` to compile the package
+        "#}
+    );
+
+    ui.print(Status::new("Running", &package.name));
+
+    Ok((path, available_gas))
+}
+
 fn main_function<'a>(
+    ui: &Ui,
     runner: &'a SierraCasmRunner,
     sierra_program: &'a ProgramArtifact,
     name: Option<&str>,
@@ -196,22 +294,22 @@ fn main_function<'a>(
         );
     }
 
-    // If executables are ambiguous, bail with error.
+    // If executables are ambiguous, ask the user to choose one.
     if executables.len() > 1 {
-        let names = executables
+        let Some(names) = executables
             .iter()
-            .flat_map(|fid| fid.debug_name.clone())
-            .map(|name| name.to_string())
-            .collect::<Vec<_>>();
-        let msg = if names.is_empty() {
-            "please only mark a single function as executable or enable debug ids and choose function by name".to_string()
-        } else {
-            format!(
-                "please choose a function to run from the list:\n`{}`",
-                names.join("`, `")
-            )
+            .map(|fid| fid.debug_name.as_ref().map(|name| name.to_string()))
+            .collect::<Option<Vec<_>>>()
+        else {
+            bail!("multiple executable functions found\nplease only mark a single function as executable or enable debug ids and choose function by name");
         };
-        bail!("multiple executable functions found\n{msg}");
+        let index = ui
+            .select(
+                Select::new("please choose a function to run from the list", names)
+                    .with_help("use the `--function` flag to choose a function by name"),
+            )
+            .map_err(|err| anyhow!("multiple executable functions found\n{err}"))?;
+        return find_function(sierra_program, &executables[index]);
     }
 
     // Finally check default function.
@@ -89,7 +89,7 @@ fn invalid_number_of_args() {
         .assert()
         .failure();
 
-    output_assert(
+    output_assert_with_code(
         snapbox,
         indoc! {r#"
         warn: `scarb cairo-run` will be deprecated soon
@@ -102,6 +102,7 @@ fn invalid_number_of_args() {
         Caused by:
             Function expects arguments of size 3 and received 4 instead.
     "#},
+        2,
     );
 }
 
@@ -118,7 +119,7 @@ fn array_instead_of_felt() {
         .assert()
         .failure();
 
-    output_assert(
+    output_assert_with_code(
         snapbox,
         indoc! {r#"
             warn: `scarb cairo-run` will be deprecated soon
@@ -131,6 +132,7 @@ fn array_instead_of_felt() {
             Caused by:
                 Function param 2 only partially contains argument 2.
         "#},
+        2,
     );
 }
 
@@ -257,7 +259,7 @@ fn invalid_struct_deserialization() {
         .failure();
 
     // Received 2, because arrays in Cairo are represented as [begin_addr, end_addr]
-    output_assert(
+    output_assert_with_code(
         snapbox,
         indoc! {r#"
         warn: `scarb cairo-run` will be deprecated soon
@@ -270,6 +272,7 @@ fn invalid_struct_deserialization() {
         Caused by:
             Function expects arguments of size 3 and received 2 instead.
     "#},
+        2,
     );
 }
 
@@ -377,10 +380,14 @@ fn can_control_verbosity() {
 }
 
 fn output_assert(output: OutputAssert, expected: &str) {
+    output_assert_with_code(output, expected, 1)
+}
+
+fn output_assert_with_code(output: OutputAssert, expected: &str, code: i32) {
     #[cfg(windows)]
     output.stdout_matches(format!(
-        "{expected}error: process did not exit successfully: exit code: 1\n"
+        "{expected}error: process did not exit successfully: exit code: {code}\n"
     ));
     #[cfg(not(windows))]
-    output.stdout_matches(expected);
+    output.code(code).stdout_matches(expected);
 }
@@ -1,3 +1,4 @@
+use assert_fs::prelude::*;
 use assert_fs::TempDir;
 use indoc::indoc;
 use snapbox::cmd::OutputAssert;
@@ -32,6 +33,27 @@ fn can_run_default_main_function() {
         "#});
 }
 
+#[test]
+fn quiet_mode_suppresses_output() {
+    let t = TempDir::new().unwrap();
+    ProjectBuilder::start()
+        .name("hello")
+        .version("0.1.0")
+        .lib_cairo(indoc! {r#"
+            fn main() -> felt252 {
+                42
+            }
+        "#})
+        .build(&t);
+    Scarb::quick_snapbox()
+        .arg("cairo-run")
+        .arg("--quiet")
+        .current_dir(&t)
+        .assert()
+        .success()
+        .stdout_eq("");
+}
+
 #[test]
 fn can_run_default_main_function_with_plugin() {
     let t = TempDir::new().unwrap();
@@ -191,6 +213,7 @@ fn ambiguous_executables_will_fail() {
         error: multiple executable functions found
         please choose a function to run from the list:
         `hello::hello`, `hello::world`
+        help: use the `--function` flag to choose a function by name
         "#},
     )
 }
@@ -398,3 +421,209 @@ fn output_assert(output: OutputAssert, expected: &str) {
     #[cfg(not(windows))]
     output.stdout_matches(expected);
 }
+
+#[test]
+fn can_run_sierra_file() {
+    let t = TempDir::new().unwrap();
+    ProjectBuilder::start()
+        .name("hello")
+        .version("0.1.0")
+        .lib_cairo(indoc! {r#"
+            fn main() -> felt252 {
+                42
+            }
+        "#})
+        .build(&t);
+    Scarb::quick_snapbox()
+        .arg("build")
+        .current_dir(&t)
+        .assert()
+        .success();
+
+    let program = TempDir::new().unwrap();
+    let sierra_file = program.child("program.sierra.json");
+    sierra_file
+        .write_binary(&std::fs::read(t.child("target/dev/hello.sierra.json")).unwrap())
+        .unwrap();
+
+    Scarb::quick_snapbox()
+        .arg("cairo-run")
+        .arg("--sierra-file")
+        .arg(sierra_file.path())
+        .current_dir(&t)
+        .assert()
+        .success()
+        .stdout_matches(indoc! {r#"
+        warn: `scarb cairo-run` will be deprecated soon
+        help: use `scarb execute` instead
+        [..]Running [..]program.sierra.json
+        Run completed successfully, returning [42]
+        "#});
+}
+
+#[test]
+fn invalid_sierra_file() {
+    let t = TempDir::new().unwrap();
+    ProjectBuilder::start().name("hello").build(&t);
+    t.child("program.sierra.json").write_str("{}").unwrap();
+
+    Scarb::quick_snapbox()
+        .args(["cairo-run", "--sierra-file", "program.sierra.json"])
+        .current_dir(&t)
+        .assert()
+        .failure()
+        .stdout_matches(indoc! {r#"
+        warn: `scarb cairo-run` will be deprecated soon
+        help: use `scarb execute` instead
+        [..]Running program.sierra.json
+        error: failed to deserialize Sierra program: program.sierra.json
+
+        Caused by:
+            [..]
+        "#});
+}
+
+#[test]
+fn can_profile_run() {
+    let t = TempDir::new().unwrap();
+    ProjectBuilder::start()
+        .name("hello")
+        .version("0.1.0")
+        .lib_cairo(indoc! {r#"
+            fn main() -> felt252 {
+                double(21)
+            }
+
+            fn double(a: felt252) -> felt252 {
+                a * 2
+            }
+        "#})
+        .build(&t);
+
+    Scarb::quick_snapbox()
+        .args(["cairo-run", "--profile"])
+        .current_dir(&t)
+        .assert()
+        .success()
+        .stdout_matches(indoc! {r#"
+        warn: `scarb cairo-run` will be deprecated soon
+        help: use `scarb execute` instead
+        [..]Compiling hello v0.1.0 ([..]Scarb.toml)
+        [..]Finished `dev` profile target(s) in [..]
+        [..]Running hello
+        Run completed successfully, returning [42]
+        Gas consumed: [..]
+        ...
+        Weight by user function (inc. generated):
+        ...
+          function hello::main: [..]
+        ...
+        "#});
+}
+
+#[test]
+fn can_write_profile_to_file() {
+    let t = TempDir::new().unwrap();
+    ProjectBuilder::start()
+        .name("hello")
+        .version("0.1.0")
+        .lib_cairo(indoc! {r#"
+            fn main() -> felt252 {
+                42
+            }
+        "#})
+        .build(&t);
+
+    Scarb::quick_snapbox()
+        .args(["cairo-run", "--profile", "--profile-output", "profile.txt"])
+        .current_dir(&t)
+        .assert()
+        .success()
+        .stdout_matches(indoc! {r#"
+        warn: `scarb cairo-run` will be deprecated soon
+        help: use `scarb execute` instead
+        [..]Compiling hello v0.1.0 ([..]Scarb.toml)
+        [..]Finished `dev` profile target(s) in [..]
+        [..]Running hello
+        Run completed successfully, returning [42]
+        "#});
+
+    let report = std::fs::read_to_string(t.child("profile.txt")).unwrap();
+    assert!(report.starts_with("Gas consumed: "));
+    assert!(report.contains("function hello::main: "));
+}
+
+// Scarb does not forward exit codes of extensions on Windows.
+#[cfg(not(windows))]
+#[test]
+fn panicking_run_exits_with_runtime_code() {
+    let t = TempDir::new().unwrap();
+    ProjectBuilder::start()
+        .name("hello")
+        .version("0.1.0")
+        .lib_cairo(indoc! {r#"
+            fn main() -> felt252 {
+                panic!("oops")
+            }
+        "#})
+        .build(&t);
+
+    Scarb::quick_snapbox()
+        .arg("cairo-run")
+        .current_dir(&t)
+        .assert()
+        .code(3)
+        .stdout_matches(indoc! {r#"
+        warn: `scarb cairo-run` will be deprecated soon
+        help: use `scarb execute` instead
+        [..]Compiling hello v0.1.0 ([..]Scarb.toml)
+        [..]Finished `dev` profile target(s) in [..]
+        [..]Running hello
+        Run panicked with [[..]].
+        error: program panicked
+        "#});
+}
+
+// Scarb does not forward exit codes of extensions on Windows.
+#[cfg(not(windows))]
+#[test]
+fn compilation_error_exits_with_build_code() {
+    let t = TempDir::new().unwrap();
+    ProjectBuilder::start()
+        .name("hello")
+        .version("0.1.0")
+        .lib_cairo(indoc! {r#"
+            fn main() -> felt252 {
+                x
+            }
+        "#})
+        .build(&t);
+
+    Scarb::quick_snapbox()
+        .arg("cairo-run")
+        .current_dir(&t)
+        .assert()
+        .code(1);
+}
+
+// Scarb does not forward exit codes of extensions on Windows.
+#[cfg(not(windows))]
+#[test]
+fn unknown_package_exits_with_usage_code() {
+    let t = TempDir::new().unwrap();
+    ProjectBuilder::start()
+        .name("hello")
+        .version("0.1.0")
+        .lib_cairo(indoc! {r#"
+            fn main() -> felt252 {
+                42
+            }
+        "#})
+        .build(&t);
+
+    Scarb::quick_snapbox()
+        .args(["cairo-run", "--package", "world"])
+        .current_dir(&t)
+        .assert()
+        .code(2);
+}
@@ -1,18 +1,21 @@
 use std::collections::HashSet;
+use std::process::ExitCode;
+use std::time::{SystemTime, UNIX_EPOCH};
 use std::{env, fs};
 
 use anyhow::{Context, Result};
 use cairo_lang_sierra::program::VersionedProgram;
 use cairo_lang_test_plugin::{TestCompilation, TestCompilationMetadata};
 use cairo_lang_test_runner::{CompiledTestRunner, RunProfilerConfig, TestRunConfig};
-use camino::Utf8PathBuf;
+use camino::Utf8Path;
 use clap::{Parser, ValueEnum};
 use indoc::formatdoc;
 
 use scarb_metadata::{
     Metadata, MetadataCommand, PackageId, PackageMetadata, ScarbCommand, TargetMetadata,
 };
-use scarb_ui::args::PackagesFilter;
+use scarb_ui::args::{ColorSpec, PackagesFilter, VerbositySpec};
+use scarb_ui::{FailureExt, FailureKind, OutputFormat, Ui};
 
 /// Execute all unit tests of a local package.
 #[derive(Parser, Clone, Debug)]
@@ -40,6 +43,22 @@ struct Args {
     /// Whether to print resource usage after each test.
     #[arg(long, default_value_t = false)]
     print_resource_usage: bool,
+
+    /// Run tests in random order within each test target.
+    #[arg(long, default_value_t = false)]
+    shuffle: bool,
+
+    /// Seed used to shuffle tests, implies `--shuffle`. A random seed is used if not specified.
+    #[arg(long)]
+    seed: Option<u64>,
+
+    /// Logging verbosity.
+    #[command(flatten)]
+    verbose: VerbositySpec,
+
+    /// Coloring of the output.
+    #[command(flatten)]
+    color: ColorSpec,
 }
 
 #[derive(ValueEnum, Clone, Debug, Default)]
@@ -60,15 +79,28 @@ impl TestKind {
     }
 }
 
-fn main() -> Result<()> {
+fn main() -> ExitCode {
+    if let Err(err) = main_inner() {
+        eprintln!("Error: {err:?}");
+        return FailureKind::of(&err).exit_code();
+    }
+    ExitCode::SUCCESS
+}
+
+fn main_inner() -> Result<()> {
     let args: Args = Args::parse();
+    let ui = Ui::new(args.verbose.clone().into(), OutputFormat::Text);
+    args.color.apply(&ui);
 
     let metadata = MetadataCommand::new().inherit_stderr().exec()?;
 
     check_scarb_version(&metadata);
     check_cairo_test_plugin(&metadata);
 
-    let matched = args.packages_filter.match_many(&metadata)?;
+    let matched = args
+        .packages_filter
+        .match_many(&metadata)
+        .failure_kind(FailureKind::Usage)?;
     let filter = PackagesFilter::generate_for::<Metadata>(matched.iter());
     let test_kind = args.test_kind.unwrap_or_default();
     let target_names = matched
@@ -77,13 +109,7 @@ fn main() -> Result<()> {
             find_testable_targets(package)
                 .iter()
                 .filter(|target| {
-                    test_kind.matches(
-                        target
-                            .params
-                            .get("test-type")
-                            .and_then(|v| v.as_str())
-                            .unwrap_or_default(),
-                    )
+                    test_kind.matches(target.param_str("test-type").unwrap_or_default())
                 })
                 .map(|t| t.name.clone())
                 .collect::<Vec<_>>()
@@ -95,34 +121,35 @@ fn main() -> Result<()> {
         .arg("--test")
         .env("SCARB_TARGET_NAMES", target_names.clone().join(","))
         .env("SCARB_PACKAGES_FILTER", filter.to_env())
+        .env("SCARB_UI_VERBOSITY", ui.verbosity().to_string())
         .run()?;
 
     let profile = env::var("SCARB_PROFILE").unwrap_or("dev".into());
     let default_target_dir = metadata.runtime_manifest.join("target");
-    let target_dir = metadata
-        .target_dir
-        .clone()
-        .unwrap_or(default_target_dir)
-        .join(profile);
+    let target_dir = metadata.target_dir.clone().unwrap_or(default_target_dir);
+
+    let shuffle_seed = (args.shuffle || args.seed.is_some()).then(|| {
+        let seed = args.seed.unwrap_or_else(random_seed);
+        ui.print(format!("shuffling tests with seed {seed}"));
+        seed
+    });
 
     let mut deduplicator = TargetGroupDeduplicator::default();
     for package in matched {
-        println!("testing {} ...", package.name);
+        ui.print(format!("testing {} ...", package.name));
         for target in find_testable_targets(&package) {
             if !target_names.contains(&target.name) {
                 continue;
             }
-            let name = target
-                .params
-                .get("group-id")
-                .and_then(|v| v.as_str())
-                .map(ToString::to_string)
-                .unwrap_or(target.name.clone());
-            let already_seen = deduplicator.visit(package.name.clone(), name.clone());
+            let name = target.output_name().to_string();
+            let already_seen = deduplicator.visit(package.name.clone(), name);
             if already_seen {
                 continue;
             }
-            let test_compilation = deserialize_test_compilation(&target_dir, name.clone())?;
+            let mut test_compilation = deserialize_test_compilation(target, &target_dir, &profile)?;
+            if let Some(seed) = shuffle_seed {
+                shuffle(&mut test_compilation.metadata.named_tests, seed);
+            }
             let config = TestRunConfig {
                 filter: args.filter.clone(),
                 include_ignored: args.include_ignored,
@@ -132,7 +159,7 @@ fn main() -> Result<()> {
                 print_resource_usage: args.print_resource_usage,
             };
             let runner = CompiledTestRunner::new(test_compilation, config);
-            runner.run(None)?;
+            runner.run(None).failure_kind(FailureKind::Runtime)?;
             println!();
         }
     }
@@ -140,15 +167,19 @@ fn main() -> Result<()> {
     Ok(())
 }
 
-fn deserialize_test_compilation(target_dir: &Utf8PathBuf, name: String) -> Result<TestCompilation> {
-    let file_path = target_dir.join(format!("{}.test.json", name));
+fn deserialize_test_compilation(
+    target: &TargetMetadata,
+    target_dir: &Utf8Path,
+    profile: &str,
+) -> Result<TestCompilation> {
+    let file_path = target.test_json_path(target_dir, profile);
     let test_comp_metadata = serde_json::from_str::<TestCompilationMetadata>(
         &fs::read_to_string(file_path.clone())
             .with_context(|| format!("failed to read file: {file_path}"))?,
     )
     .with_context(|| format!("failed to deserialize compiled tests metadata file: {file_path}"))?;
 
-    let file_path = target_dir.join(format!("{}.test.sierra.json", name));
+    let file_path = target.test_sierra_json_path(target_dir, profile);
     let sierra_program = serde_json::from_str::<VersionedProgram>(
         &fs::read_to_string(file_path.clone())
             .with_context(|| format!("failed to read file: {file_path}"))?,
@@ -161,6 +192,32 @@ fn deserialize_test_compilation(target_dir: &Utf8PathBuf, name: String) -> Resul
     })
 }
 
+fn random_seed() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or_default()
+}
+
+/// Shuffles items in place with the Fisher-Yates algorithm.
+///
+/// Uses the SplitMix64 generator, so that the same seed always yields the same order,
+/// regardless of the platform.
+fn shuffle<T>(items: &mut [T], seed: u64) {
+    let mut state = seed;
+    let mut next = || {
+        state = state.wrapping_add(0x9E3779B97F4A7C15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        z ^ (z >> 31)
+    };
+    for i in (1..items.len()).rev() {
+        let j = (next() % (i as u64 + 1)) as usize;
+        items.swap(i, j);
+    }
+}
+
 #[derive(Default)]
 struct TargetGroupDeduplicator {
     seen: HashSet<(String, String)>,
@@ -230,17 +287,11 @@ fn check_cairo_test_plugin(metadata: &Metadata) {
         );
     };
 
-    let Some(plugin_pkg) = metadata.packages.iter().find(|pkg| {
-        pkg.name == "cairo_test"
-            && pkg.targets.iter().any(|t| {
-                t.kind == "cairo-plugin"
-                    && t.name == "cairo_test"
-                    && t.params
-                        .get("builtin")
-                        .and_then(|v| v.as_bool())
-                        .unwrap_or(false)
-            })
-    }) else {
+    let Some(plugin_pkg) = metadata
+        .packages
+        .iter()
+        .find(|pkg| pkg.name == "cairo_test")
+    else {
         warn();
         return;
     };
@@ -252,10 +303,39 @@ fn check_cairo_test_plugin(metadata: &Metadata) {
         if !cu
             .cairo_plugins
             .iter()
-            .any(|plugin| plugin.package == plugin_pkg.id)
+            .any(|plugin| plugin.builtin && plugin.package == plugin_pkg.id)
         {
             warn();
             return;
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::shuffle;
+
+    fn shuffled(seed: u64) -> Vec<u32> {
+        let mut items = (0..32).collect::<Vec<_>>();
+        shuffle(&mut items, seed);
+        items
+    }
+
+    #[test]
+    fn same_seed_yields_same_order() {
+        assert_eq!(shuffled(42), shuffled(42));
+    }
+
+    #[test]
+    fn different_seeds_yield_different_order() {
+        assert_ne!(shuffled(42), shuffled(43));
+    }
+
+    #[test]
+    fn shuffle_is_permutation() {
+        let mut items = shuffled(42);
+        assert_ne!(items, (0..32).collect::<Vec<_>>());
+        items.sort();
+        assert_eq!(items, (0..32).collect::<Vec<_>>());
+    }
+}
@@ -109,6 +109,46 @@ fn can_print_test_resources() {
         "#});
 }
 
+#[test]
+fn can_shuffle_tests_with_seed() {
+    let t = TempDir::new().unwrap();
+    ProjectBuilder::start()
+        .name("hello")
+        .lib_cairo(indoc! {r#"
+            fn main() -> felt252 { 42 }
+
+            #[cfg(test)]
+            mod tests {
+                use super::main;
+
+                #[test]
+                fn it_works() {
+                    assert(main() == 42, 'it works!');
+                }
+            }
+        "#})
+        .dep_cairo_test()
+        .build(&t);
+    Scarb::quick_snapbox()
+        .arg("cairo-test")
+        .arg("--shuffle")
+        .arg("--seed")
+        .arg("42")
+        .current_dir(&t)
+        .assert()
+        .success()
+        .stdout_matches(indoc! {r#"
+            [..]Compiling test(hello_unittest) hello v1.0.0 ([..]Scarb.toml)
+            [..]Finished `dev` profile target(s) in [..]
+            shuffling tests with seed 42
+            testing hello ...
+            running 1 test
+            test hello::tests::it_works ... ok (gas usage est.: [..])
+            test result: ok. 1 passed; 0 failed; 0 ignored; 0 filtered out;
+
+        "#});
+}
+
 fn get_features_test_build(t: &TempDir) {
     ProjectBuilder::start()
         .name("hello")
@@ -358,3 +398,71 @@ fn can_choose_test_kind_to_run() {
             
         "#});
 }
+
+#[test]
+fn quiet_mode_prints_only_test_results() {
+    let t = TempDir::new().unwrap();
+    ProjectBuilder::start()
+        .name("hello")
+        .lib_cairo(indoc! {r#"
+            #[cfg(test)]
+            mod tests {
+                #[test]
+                fn it_works() {
+                    assert(1 == 1, 'it works');
+                }
+            }
+        "#})
+        .dep_cairo_test()
+        .build(&t);
+    Scarb::quick_snapbox()
+        .arg("cairo-test")
+        .arg("--quiet")
+        .current_dir(&t)
+        .assert()
+        .success()
+        .stdout_matches(indoc! {r#"
+            running 1 test
+            test hello::tests::it_works ... ok (gas usage est.: [..])
+            test result: ok. 1 passed; 0 failed; 0 ignored; 0 filtered out;
+
+        "#});
+}
+
+// Scarb does not forward exit codes of extensions on Windows.
+#[cfg(not(windows))]
+#[test]
+fn failing_tests_exit_with_runtime_code() {
+    let t = TempDir::new().unwrap();
+    ProjectBuilder::start()
+        .name("hello")
+        .lib_cairo(indoc! {r#"
+            #[cfg(test)]
+            mod tests {
+                #[test]
+                fn it_fails() {
+                    assert(1 == 2, 'it fails!');
+                }
+            }
+        "#})
+        .dep_cairo_test()
+        .build(&t);
+    Scarb::quick_snapbox()
+        .arg("cairo-test")
+        .current_dir(&t)
+        .assert()
+        .code(3);
+}
+
+// Scarb does not forward exit codes of extensions on Windows.
+#[cfg(not(windows))]
+#[test]
+fn features_test_build_failure_exits_with_build_code() {
+    let t = TempDir::new().unwrap();
+    get_features_test_build(&t);
+    Scarb::quick_snapbox()
+        .arg("cairo-test")
+        .current_dir(&t)
+        .assert()
+        .code(1);
+}
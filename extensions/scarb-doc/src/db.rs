@@ -14,6 +14,7 @@ use cairo_lang_semantic::plugin::PluginSuite;
 use cairo_lang_starknet::starknet_plugin_suite;
 use cairo_lang_syntax::node::db::{SyntaxDatabase, SyntaxGroup};
 use cairo_lang_utils::Upcast;
+use camino::{Utf8Path, Utf8PathBuf};
 
 use salsa;
 
@@ -29,10 +30,18 @@ use salsa;
 )]
 pub struct ScarbDocDatabase {
     storage: salsa::Storage<Self>,
+    source_links_root: Option<Utf8PathBuf>,
 }
 
 impl ScarbDocDatabase {
-    pub fn new(project_config: Option<ProjectConfig>) -> Self {
+    /// Creates a new database.
+    ///
+    /// If `source_links_root` is set, documented items carry their source locations,
+    /// with paths relative to this directory.
+    pub fn new(
+        project_config: Option<ProjectConfig>,
+        source_links_root: Option<Utf8PathBuf>,
+    ) -> Self {
         let plugin_suite = [get_default_plugin_suite(), starknet_plugin_suite()]
             .into_iter()
             .fold(PluginSuite::default(), |mut acc, suite| {
@@ -41,6 +50,7 @@ impl ScarbDocDatabase {
             });
         let mut db = Self {
             storage: Default::default(),
+            source_links_root,
         };
 
         init_files_group(&mut db);
@@ -56,6 +66,10 @@ impl ScarbDocDatabase {
         db
     }
 
+    pub fn source_links_root(&self) -> Option<&Utf8Path> {
+        self.source_links_root.as_deref()
+    }
+
     fn initial_cfg_set() -> CfgSet {
         CfgSet::from_iter([Cfg::name("doc")])
     }
@@ -6,6 +6,7 @@ use std::fs;
 
 use crate::docs_generation::markdown::book_toml::generate_book_toml_content;
 use crate::docs_generation::markdown::summary::generate_summary_file_content;
+pub use crate::docs_generation::markdown::theme::Theme;
 use crate::docs_generation::markdown::traits::{
     generate_markdown_list_for_top_level_subitems, TopLevelMarkdownDocItem,
 };
@@ -20,6 +21,7 @@ use crate::PackageInformation;
 mod book_toml;
 mod context;
 mod summary;
+mod theme;
 mod traits;
 
 const BASE_HEADER_LEVEL: usize = 1;
@@ -34,10 +36,61 @@ pub struct MarkdownContent {
     book_toml: String,
     summary: String,
     doc_files: Vec<GeneratedFile>,
+    theme: Theme,
+}
+
+/// Generates Markdown documentation of multiple workspace packages at once.
+///
+/// Links to items of other packages in the set are resolved to relative links between
+/// documentation trees of these packages, which are expected to be saved in sibling directories
+/// named after the packages.
+/// References to items outside the set are left as plain text.
+pub struct WorkspaceMarkdownBuilder<'a> {
+    packages_information: &'a [PackageInformation],
+    theme: Theme,
+}
+
+impl<'a> WorkspaceMarkdownBuilder<'a> {
+    pub fn new(packages_information: &'a [PackageInformation], theme: Theme) -> Self {
+        Self {
+            packages_information,
+            theme,
+        }
+    }
+
+    pub fn build(self) -> Result<Vec<(&'a PackageInformation, MarkdownContent)>> {
+        self.packages_information
+            .iter()
+            .map(|package_information| {
+                let other_crates = self
+                    .packages_information
+                    .iter()
+                    .filter(|other| other.metadata.name != package_information.metadata.name)
+                    .map(|other| (other.metadata.name.as_str(), &other.crate_));
+                let context = MarkdownGenerationContext::from_crate(&package_information.crate_)
+                    .with_workspace_crates(other_crates);
+                let content = MarkdownContent::from_crate_with_context(
+                    package_information,
+                    &context,
+                    self.theme,
+                )?;
+                Ok((package_information, content))
+            })
+            .collect()
+    }
 }
 
 impl MarkdownContent {
-    pub fn from_crate(package_information: &PackageInformation) -> Result<Self> {
+    pub fn from_crate(package_information: &PackageInformation, theme: Theme) -> Result<Self> {
+        let context = MarkdownGenerationContext::from_crate(&package_information.crate_);
+        Self::from_crate_with_context(package_information, &context, theme)
+    }
+
+    fn from_crate_with_context(
+        package_information: &PackageInformation,
+        context: &MarkdownGenerationContext,
+        theme: Theme,
+    ) -> Result<Self> {
         let top_level_items = collect_all_top_level_items(&package_information.crate_);
 
         let summary_file_content = generate_summary_file_content(&top_level_items)?;
@@ -55,19 +108,18 @@ impl MarkdownContent {
             extern_functions,
         } = top_level_items;
 
-        let context = MarkdownGenerationContext::from_crate(&package_information.crate_);
         let docs_for_top_level_items = chain!(
-            generate_top_level_docs_contents(&modules, &context)?,
-            generate_top_level_docs_contents(&constants, &context)?,
-            generate_top_level_docs_contents(&free_functions, &context)?,
-            generate_top_level_docs_contents(&structs, &context)?,
-            generate_top_level_docs_contents(&enums, &context)?,
-            generate_top_level_docs_contents(&type_aliases, &context)?,
-            generate_top_level_docs_contents(&impl_aliases, &context)?,
-            generate_top_level_docs_contents(&traits, &context)?,
-            generate_top_level_docs_contents(&impls, &context)?,
-            generate_top_level_docs_contents(&extern_types, &context)?,
-            generate_top_level_docs_contents(&extern_functions, &context)?,
+            generate_top_level_docs_contents(&modules, context)?,
+            generate_top_level_docs_contents(&constants, context)?,
+            generate_top_level_docs_contents(&free_functions, context)?,
+            generate_top_level_docs_contents(&structs, context)?,
+            generate_top_level_docs_contents(&enums, context)?,
+            generate_top_level_docs_contents(&type_aliases, context)?,
+            generate_top_level_docs_contents(&impl_aliases, context)?,
+            generate_top_level_docs_contents(&traits, context)?,
+            generate_top_level_docs_contents(&impls, context)?,
+            generate_top_level_docs_contents(&extern_types, context)?,
+            generate_top_level_docs_contents(&extern_functions, context)?,
         )
         .collect::<Vec<(String, String)>>();
 
@@ -125,9 +177,10 @@ impl MarkdownContent {
         .collect::<Vec<_>>();
 
         Ok(Self {
-            book_toml: generate_book_toml_content(&package_information.metadata),
+            book_toml: generate_book_toml_content(&package_information.metadata, theme),
             summary: summary_file_content,
             doc_files: chain!(docs_for_top_level_items, summaries_for_top_level_items).collect(),
+            theme,
         })
     }
 
@@ -139,6 +192,8 @@ impl MarkdownContent {
         fs::write(output_dir.join(BOOK_TOML_FILENAME), self.book_toml)
             .map_err(|e| IOWriteError::new(e, "book.toml"))?;
 
+        self.theme.save_assets(output_dir)?;
+
         fs::write(source_directory_path.join(SUMMARY_FILENAME), self.summary)
             .map_err(|e| IOWriteError::new(e, "summary"))?;
 
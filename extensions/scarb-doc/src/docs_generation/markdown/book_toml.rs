@@ -1,8 +1,13 @@
 use indoc::formatdoc;
 
+use crate::docs_generation::markdown::theme::Theme;
 use crate::AdditionalMetadata;
 
-pub fn generate_book_toml_content(package_metadata: &AdditionalMetadata) -> String {
+pub fn generate_book_toml_content(package_metadata: &AdditionalMetadata, theme: Theme) -> String {
+    let theme_settings = theme
+        .mdbook_theme()
+        .map(|theme| format!("\ndefault-theme = \"{theme}\"\npreferred-dark-theme = \"{theme}\""))
+        .unwrap_or_default();
     formatdoc! {
         r##"
             [book]
@@ -13,7 +18,7 @@ pub fn generate_book_toml_content(package_metadata: &AdditionalMetadata) -> Stri
             title = "{} - Cairo"
 
             [output.html]
-            no-section-label = true
+            no-section-label = true{}
 
             [output.html.playground]
             runnable = false
@@ -26,6 +31,7 @@ pub fn generate_book_toml_content(package_metadata: &AdditionalMetadata) -> Stri
             cairo = "#"
         "##,
         package_metadata.authors.clone().unwrap_or_else(|| vec!["<unknown>".to_string()]),
-        package_metadata.name
+        package_metadata.name,
+        theme_settings
     }
 }
@@ -1,5 +1,5 @@
 use crate::docs_generation::markdown::traits::WithPath;
-use crate::docs_generation::markdown::SUMMARY_FILENAME;
+use crate::docs_generation::markdown::{SOURCE_DIRECTORY, SUMMARY_FILENAME};
 use crate::types::Crate;
 use cairo_lang_defs::ids::{ImplItemId, LookupItemId, TraitItemId};
 use cairo_lang_doc::documentable_item::DocumentableItemId;
@@ -10,62 +10,102 @@ pub type IncludedItems<'a> = HashMap<DocumentableItemId, &'a dyn WithPath>;
 
 pub struct MarkdownGenerationContext<'a> {
     included_items: IncludedItems<'a>,
+    /// Links to items of other crates of the workspace, relative to this crate's source directory,
+    /// keyed by full paths of these items.
+    workspace_items: HashMap<String, String>,
+    /// Full paths of items that documentation links of this crate resolved to.
+    link_targets: HashMap<DocumentableItemId, String>,
 }
 
 impl<'a> MarkdownGenerationContext<'a> {
     pub fn from_crate(crate_: &'a Crate) -> Self {
-        let included_items = crate_.root_module.get_all_item_ids();
         Self {
-            included_items: included_items
-                .into_iter()
-                .map(|(id, item)| {
-                    let item: &dyn WithPath = item;
-                    (id, item)
-                })
+            included_items: collect_included_items(crate_),
+            workspace_items: HashMap::new(),
+            link_targets: crate_
+                .root_module
+                .get_all_item_ids()
+                .into_values()
+                .flat_map(|item| item.link_targets.clone())
                 .collect(),
         }
     }
 
+    /// Resolve links to items of given crates, documented in sibling directories named after
+    /// their packages.
+    pub fn with_workspace_crates<'b>(
+        mut self,
+        crates: impl IntoIterator<Item = (&'b str, &'b Crate)>,
+    ) -> Self {
+        for (package_name, crate_) in crates {
+            for (id, item) in collect_included_items(crate_) {
+                let file_link = item_file_link(id, item);
+                let file_link = file_link.strip_prefix("./").unwrap_or(&file_link);
+                self.workspace_items.insert(
+                    item.full_path(),
+                    format!("../../{package_name}/{SOURCE_DIRECTORY}/{file_link}"),
+                );
+            }
+        }
+        self
+    }
+
     pub fn resolve_markdown_file_path_from_link(&self, link: &CommentLinkToken) -> String {
-        match link.resolved_item {
-            Some(resolved_item_id) => match self.included_items.get(&resolved_item_id) {
-                Some(resolved_item) => match resolved_item_id {
-                    DocumentableItemId::Member(_)
-                    | DocumentableItemId::Variant(_)
-                    | DocumentableItemId::LookupItem(LookupItemId::TraitItem(TraitItemId::Type(
-                        _,
-                    )))
-                    | DocumentableItemId::LookupItem(LookupItemId::TraitItem(
-                        TraitItemId::Function(_),
-                    ))
-                    | DocumentableItemId::LookupItem(LookupItemId::TraitItem(
-                        TraitItemId::Constant(_),
-                    ))
-                    | DocumentableItemId::LookupItem(LookupItemId::ImplItem(ImplItemId::Type(_)))
-                    | DocumentableItemId::LookupItem(LookupItemId::ImplItem(
-                        ImplItemId::Function(_),
-                    ))
-                    | DocumentableItemId::LookupItem(LookupItemId::ImplItem(
-                        ImplItemId::Constant(_),
-                    )) => {
-                        match resolved_item.parent_full_path() {
-                            Some(parent_path) => {
-                                format!(
-                                    "{}#{}",
-                                    path_to_file_link(&parent_path),
-                                    resolved_item.name().to_lowercase()
-                                )
-                            }
-                            // Only root_module / crate doesn't have the parent.
-                            _ => SUMMARY_FILENAME.to_string(),
-                        }
-                    }
-                    _ => path_to_file_link(&resolved_item.full_path()),
-                },
-                None => link.path.clone().unwrap_or(link.label.clone()),
-            },
-            None => link.path.clone().unwrap_or(link.label.clone()),
+        let resolved_item = link
+            .resolved_item
+            .and_then(|id| self.included_items.get(&id).map(|item| (id, *item)));
+        match resolved_item {
+            Some((id, item)) => item_file_link(id, item),
+            None => {
+                let path = link.path.clone().unwrap_or(link.label.clone());
+                // Links resolved in the item's scope (e.g. through imports) are looked up by the
+                // full path of the target, the literal link text is a fallback.
+                link.resolved_item
+                    .and_then(|id| self.link_targets.get(&id))
+                    .and_then(|full_path| self.workspace_items.get(full_path))
+                    .or_else(|| self.workspace_items.get(&path))
+                    .cloned()
+                    .unwrap_or(path)
+            }
+        }
+    }
+}
+
+fn collect_included_items(crate_: &Crate) -> IncludedItems<'_> {
+    crate_
+        .root_module
+        .get_all_item_ids()
+        .into_iter()
+        .map(|(id, item)| {
+            let item: &dyn WithPath = item;
+            (id, item)
+        })
+        .collect()
+}
+
+fn item_file_link(id: DocumentableItemId, item: &dyn WithPath) -> String {
+    match id {
+        DocumentableItemId::Member(_)
+        | DocumentableItemId::Variant(_)
+        | DocumentableItemId::LookupItem(LookupItemId::TraitItem(TraitItemId::Type(_)))
+        | DocumentableItemId::LookupItem(LookupItemId::TraitItem(TraitItemId::Function(_)))
+        | DocumentableItemId::LookupItem(LookupItemId::TraitItem(TraitItemId::Constant(_)))
+        | DocumentableItemId::LookupItem(LookupItemId::ImplItem(ImplItemId::Type(_)))
+        | DocumentableItemId::LookupItem(LookupItemId::ImplItem(ImplItemId::Function(_)))
+        | DocumentableItemId::LookupItem(LookupItemId::ImplItem(ImplItemId::Constant(_))) => {
+            match item.parent_full_path() {
+                Some(parent_path) => {
+                    format!(
+                        "{}#{}",
+                        path_to_file_link(&parent_path),
+                        item.name().to_lowercase()
+                    )
+                }
+                // Only root_module / crate doesn't have the parent.
+                _ => SUMMARY_FILENAME.to_string(),
+            }
         }
+        _ => path_to_file_link(&item.full_path()),
     }
 }
 
@@ -0,0 +1,69 @@
+use anyhow::Result;
+use camino::Utf8Path;
+use std::fs;
+
+use crate::errors::{IODirectoryCreationError, IOWriteError};
+
+const THEME_DIRECTORY: &str = "theme";
+
+const HIGHLIGHT_JS: &str = include_str!("../../../theme/highlight.js");
+const HIGHLIGHT_LIGHT_CSS: &str = include_str!("../../../theme/highlight-light.css");
+const HIGHLIGHT_DARK_CSS: &str = include_str!("../../../theme/highlight-dark.css");
+
+/// Color theme of the generated documentation book.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Theme {
+    /// Leaves theme selection to mdBook, which follows the system color preference.
+    #[default]
+    Auto,
+    /// Always uses the light theme, with Cairo code highlighting.
+    Light,
+    /// Always uses the dark theme, with Cairo code highlighting.
+    Dark,
+}
+
+impl Theme {
+    /// mdBook theme to use both as the default theme and the preferred dark theme.
+    pub(crate) fn mdbook_theme(self) -> Option<&'static str> {
+        match self {
+            Theme::Auto => None,
+            Theme::Light => Some("light"),
+            Theme::Dark => Some("navy"),
+        }
+    }
+
+    /// Theme files to save in the book, as pairs of a file name and its content.
+    ///
+    /// mdBook uses `highlight.css` for light themes and `tomorrow-night.css` for dark ones.
+    fn assets(self) -> Vec<(&'static str, &'static str)> {
+        match self {
+            Theme::Auto => Vec::new(),
+            Theme::Light => vec![
+                ("highlight.js", HIGHLIGHT_JS),
+                ("highlight.css", HIGHLIGHT_LIGHT_CSS),
+            ],
+            Theme::Dark => vec![
+                ("highlight.js", HIGHLIGHT_JS),
+                ("tomorrow-night.css", HIGHLIGHT_DARK_CSS),
+            ],
+        }
+    }
+
+    pub(crate) fn save_assets(self, output_dir: &Utf8Path) -> Result<()> {
+        let assets = self.assets();
+        if assets.is_empty() {
+            return Ok(());
+        }
+
+        let theme_directory_path = output_dir.join(THEME_DIRECTORY);
+        fs::create_dir_all(&theme_directory_path)
+            .map_err(|e| IODirectoryCreationError::new(e, "theme"))?;
+
+        for (filename, content) in assets {
+            fs::write(theme_directory_path.join(filename), content)
+                .map_err(|e| IOWriteError::new(e, filename))?;
+        }
+
+        Ok(())
+    }
+}
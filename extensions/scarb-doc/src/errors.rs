@@ -1,5 +1,7 @@
 use core::fmt;
 use scarb_metadata::MetadataCommandError as ScarbMetadataCommandFail;
+use serde::ser::SerializeStruct;
+use serde::{Serialize, Serializer};
 use serde_json::Error as SerdeError;
 use std::error::Error as StdError;
 use std::io::Error as IOError;
@@ -9,6 +11,12 @@ use thiserror::Error;
 #[error("failed to serialize information about crates")]
 pub struct PackagesSerializationError(#[from] SerdeError);
 
+impl Serialize for PackagesSerializationError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_error_record(serializer, "serialization", None, self)
+    }
+}
+
 #[derive(Debug, Error)]
 #[error("failed to find {0} package")]
 pub struct MissingPackageError(pub String);
@@ -29,10 +37,51 @@ pub struct MissingCompilationUnitForPackage(pub String);
 #[error("metadata command failed")]
 pub struct MetadataCommandError(#[from] ScarbMetadataCommandFail);
 
+impl Serialize for MetadataCommandError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_error_record(serializer, "metadata", None, self)
+    }
+}
+
 #[derive(Debug, Error)]
 #[error("could not compile {0} due to previous error")]
 pub struct DiagnosticError(pub String);
 
+impl Serialize for DiagnosticError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_error_record(serializer, "diagnostics", Some(&self.0), self)
+    }
+}
+
+/// Serialize an error as a structured record, with a category that allows classifying failures.
+fn serialize_error_record<S: Serializer>(
+    serializer: S,
+    category: &str,
+    package: Option<&str>,
+    error: &dyn StdError,
+) -> Result<S::Ok, S::Error> {
+    let mut record = serializer.serialize_struct("ErrorRecord", 3)?;
+    record.serialize_field("category", category)?;
+    record.serialize_field("package", &package)?;
+    record.serialize_field("message", &error.to_string())?;
+    record.end()
+}
+
+/// Convert the first error in the chain which has a structured form to JSON.
+pub fn error_to_json(error: &anyhow::Error) -> Option<serde_json::Value> {
+    error.chain().find_map(|cause| {
+        if let Some(err) = cause.downcast_ref::<DiagnosticError>() {
+            serde_json::to_value(err).ok()
+        } else if let Some(err) = cause.downcast_ref::<MetadataCommandError>() {
+            serde_json::to_value(err).ok()
+        } else if let Some(err) = cause.downcast_ref::<PackagesSerializationError>() {
+            serde_json::to_value(err).ok()
+        } else {
+            None
+        }
+    })
+}
+
 pub struct IODirectoryCreationError {
     inner_error: IOError,
     directory_purpose: String,
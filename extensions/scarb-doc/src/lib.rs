@@ -22,6 +22,18 @@ pub mod metadata;
 pub mod types;
 pub mod versioned_json_output;
 
+/// Controls whether diagnostic warnings make documentation generation fail.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum WarningsPolicy {
+    /// Follow the `allow-warnings` compiler setting of each documented package.
+    #[default]
+    Manifest,
+    /// Never fail because of warnings.
+    Allow,
+    /// Fail if any warnings are emitted.
+    Deny,
+}
+
 #[derive(Serialize, Clone)]
 pub struct PackageInformation {
     pub crate_: Crate,
@@ -38,6 +50,8 @@ pub fn generate_packages_information(
     metadata: &Metadata,
     metadata_for_packages: &[PackageMetadata],
     document_private_items: bool,
+    warnings_policy: WarningsPolicy,
+    emit_source_links: bool,
     ui: Ui,
 ) -> Result<Vec<PackageInformation>> {
     let mut packages_information = vec![];
@@ -61,7 +75,8 @@ pub fn generate_packages_information(
         let project_config =
             get_project_config(metadata, package_metadata, compilation_unit_metadata)?;
 
-        let db = ScarbDocDatabase::new(Some(project_config));
+        let source_links_root = emit_source_links.then(|| metadata.workspace.root.clone());
+        let db = ScarbDocDatabase::new(Some(project_config), source_links_root);
 
         let main_component = compilation_unit_metadata
             .components
@@ -82,14 +97,23 @@ pub fn generate_packages_information(
             .iter()
             .find(|unit| unit.package == package_metadata.id);
 
+        let warnings_allowed = match warnings_policy {
+            WarningsPolicy::Manifest => package_compilation_unit
+                .map(allows_warnings)
+                .unwrap_or(true),
+            WarningsPolicy::Allow => true,
+            WarningsPolicy::Deny => false,
+        };
+
         let mut diagnostics_reporter =
-            setup_diagnostics_reporter(&db, main_crate_id, package_compilation_unit, &ui)
+            setup_diagnostics_reporter(&db, main_crate_id, warnings_allowed, &ui)
                 .skip_lowering_diagnostics();
 
         let crate_ = Crate::new(&db, main_crate_id, should_document_private_items)
             .map_err(|_| DiagnosticError(package_metadata.name.clone()));
 
-        if crate_.is_err() {
+        // Diagnostics are only reported if they can make documentation generation fail.
+        if crate_.is_err() || !warnings_allowed {
             diagnostics_reporter.ensure(&db)?;
         }
 
@@ -107,7 +131,7 @@ pub fn generate_packages_information(
 fn setup_diagnostics_reporter<'a>(
     db: &ScarbDocDatabase,
     main_crate_id: CrateId,
-    package_compilation_unit: Option<&CompilationUnitMetadata>,
+    warnings_allowed: bool,
     ui: &'a Ui,
 ) -> DiagnosticsReporter<'a> {
     let ignore_warnings_crates = db
@@ -142,15 +166,10 @@ fn setup_diagnostics_reporter<'a>(
     })
     .with_ignore_warnings_crates(&ignore_warnings_crates);
 
-    // We check whether the warnings are allowed during compilation.
-    match package_compilation_unit {
-        Some(package_compilation_unit) => {
-            if allows_warnings(package_compilation_unit) {
-                return diagnostics_reporter.allow_warnings();
-            }
-            diagnostics_reporter
-        }
-        None => diagnostics_reporter,
+    if warnings_allowed {
+        diagnostics_reporter.allow_warnings()
+    } else {
+        diagnostics_reporter
     }
 }
 
@@ -1,17 +1,18 @@
 use anyhow::{Context, Result};
 use clap::Parser;
-use scarb_doc::docs_generation::markdown::MarkdownContent;
-use scarb_doc::errors::MetadataCommandError;
+use scarb_doc::docs_generation::markdown::{Theme, WorkspaceMarkdownBuilder};
+use scarb_doc::errors::{error_to_json, MetadataCommandError};
 use scarb_doc::metadata::get_target_dir;
 use std::process::ExitCode;
 
 use scarb_metadata::MetadataCommand;
-use scarb_ui::args::{PackagesFilter, ToEnvVars, VerbositySpec};
+use scarb_ui::args::{ColorSpec, PackagesFilter, ToEnvVars, VerbositySpec};
 
-use scarb_doc::generate_packages_information;
 use scarb_doc::versioned_json_output::VersionedJsonOutput;
+use scarb_doc::{generate_packages_information, WarningsPolicy};
 
 use scarb_ui::args::FeaturesSpec;
+use scarb_ui::components::MachineMessage;
 use scarb_ui::Ui;
 
 const OUTPUT_DIR: &str = "doc";
@@ -39,10 +40,26 @@ struct Args {
     #[arg(long, value_enum, default_value_t)]
     output_format: OutputFormat,
 
+    /// Color theme of the generated documentation book.
+    #[arg(long, value_enum, default_value_t)]
+    theme: Theme,
+
     /// Generates documentation also for private items.
     #[arg(long, default_value_t = false)]
     document_private_items: bool,
 
+    /// Fail if any warnings are emitted, regardless of the `allow-warnings` setting of packages.
+    #[arg(long, conflicts_with = "allow_warnings")]
+    deny_warnings: bool,
+
+    /// Do not fail on warnings, regardless of the `allow-warnings` setting of packages.
+    #[arg(long)]
+    allow_warnings: bool,
+
+    /// Include source locations of documented items in the JSON output.
+    #[arg(long, default_value_t = false)]
+    emit_source_links: bool,
+
     /// Specifies features to enable.
     #[command(flatten)]
     pub features: FeaturesSpec,
@@ -50,6 +67,10 @@ struct Args {
     /// Logging verbosity.
     #[command(flatten)]
     pub verbose: VerbositySpec,
+
+    /// Coloring of the output.
+    #[command(flatten)]
+    pub color: ColorSpec,
 }
 
 fn main_inner(args: Args, ui: Ui) -> Result<()> {
@@ -61,10 +82,20 @@ fn main_inner(args: Args, ui: Ui) -> Result<()> {
     let metadata_for_packages = args.packages_filter.match_many(&metadata)?;
     let output_dir = get_target_dir(&metadata).join(OUTPUT_DIR);
 
+    let warnings_policy = if args.deny_warnings {
+        WarningsPolicy::Deny
+    } else if args.allow_warnings {
+        WarningsPolicy::Allow
+    } else {
+        WarningsPolicy::Manifest
+    };
+
     let packages_information = generate_packages_information(
         &metadata,
         &metadata_for_packages,
         args.document_private_items,
+        warnings_policy,
+        args.emit_source_links,
         ui,
     )?;
 
@@ -73,17 +104,17 @@ fn main_inner(args: Args, ui: Ui) -> Result<()> {
             VersionedJsonOutput::new(packages_information).save_to_file(&output_dir)?
         }
         OutputFormat::Markdown => {
-            for pkg_information in packages_information {
+            let contents =
+                WorkspaceMarkdownBuilder::new(&packages_information, args.theme).build()?;
+            for (pkg_information, content) in contents {
                 let pkg_output_dir = output_dir.join(&pkg_information.metadata.name);
 
-                MarkdownContent::from_crate(&pkg_information)?
-                    .save(&pkg_output_dir)
-                    .with_context(|| {
-                        format!(
-                            "failed to save docs for package {}",
-                            pkg_information.metadata.name
-                        )
-                    })?;
+                content.save(&pkg_output_dir).with_context(|| {
+                    format!(
+                        "failed to save docs for package {}",
+                        pkg_information.metadata.name
+                    )
+                })?;
             }
         }
     }
@@ -93,11 +124,19 @@ fn main_inner(args: Args, ui: Ui) -> Result<()> {
 
 fn main() -> ExitCode {
     let args = Args::parse();
-    let ui = Ui::new(args.verbose.clone().into(), scarb_ui::OutputFormat::Text);
+    let output_format =
+        scarb_ui::OutputFormat::from_env_var("SCARB_UI_OUTPUT_FORMAT").unwrap_or_default();
+    let ui = Ui::new(args.verbose.clone().into(), output_format);
+    args.color.apply(&ui);
     match main_inner(args, ui.clone()) {
         Ok(()) => ExitCode::SUCCESS,
         Err(error) => {
             ui.error(format!("{error:#}"));
+            // In JSON mode, also emit a structured record, so that CI can classify failures.
+            let json_output = ui.output_format() == scarb_ui::OutputFormat::Json;
+            if let Some(record) = error_to_json(&error).filter(|_| json_output) {
+                ui.force_print(MachineMessage(record));
+            }
             ExitCode::FAILURE
         }
     }
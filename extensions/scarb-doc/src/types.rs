@@ -9,6 +9,7 @@ use cairo_lang_semantic::items::visibility::Visibility;
 use cairo_lang_semantic::resolve::ResolvedGenericItem;
 use cairo_lang_syntax::node::helpers::QueryAttrs;
 use cairo_lang_utils::{LookupIntern, Upcast};
+use camino::{Utf8Path, Utf8PathBuf};
 use itertools::chain;
 use serde::Serialize;
 
@@ -22,7 +23,8 @@ use cairo_lang_defs::ids::{
 };
 use cairo_lang_doc::db::DocGroup;
 use cairo_lang_doc::documentable_item::DocumentableItemId;
-use cairo_lang_filesystem::ids::CrateId;
+use cairo_lang_filesystem::ids::{CrateId, FileLongId};
+use cairo_lang_filesystem::span::TextPosition;
 use cairo_lang_semantic::db::SemanticGroup;
 use cairo_lang_semantic::items::attribute::SemanticQueryAttrs;
 use cairo_lang_semantic::{ConcreteTypeId, GenericArgumentId, TypeLongId};
@@ -463,6 +465,11 @@ pub struct ItemData {
     pub doc: Option<Vec<DocumentationCommentToken>>,
     pub signature: Option<String>,
     pub full_path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<SourceLocation>,
+    /// Full paths of items that links in the documentation resolved to, in the item's scope.
+    #[serde(skip_serializing)]
+    pub link_targets: HashMap<DocumentableItemId, String>,
 }
 
 impl ItemData {
@@ -471,13 +478,16 @@ impl ItemData {
         id: impl TopLevelLanguageElementId,
         documentable_item_id: DocumentableItemId,
     ) -> Self {
+        let doc = db.get_item_documentation_as_tokens(documentable_item_id);
         Self {
             id: documentable_item_id,
             name: id.name(db).into(),
-            doc: db.get_item_documentation_as_tokens(documentable_item_id),
+            link_targets: collect_link_targets(db, &doc),
+            doc,
             signature: Some(db.get_item_signature(documentable_item_id)),
             full_path: id.full_path(db),
             parent_full_path: Some(id.parent_module(db).full_path(db)),
+            location: SourceLocation::new(db, documentable_item_id),
         }
     }
 
@@ -486,29 +496,106 @@ impl ItemData {
         id: impl TopLevelLanguageElementId,
         documentable_item_id: DocumentableItemId,
     ) -> Self {
+        let doc = db.get_item_documentation_as_tokens(documentable_item_id);
         Self {
             id: documentable_item_id,
             name: id.name(db).into(),
-            doc: db.get_item_documentation_as_tokens(documentable_item_id),
+            link_targets: collect_link_targets(db, &doc),
+            doc,
             signature: None,
             full_path: id.full_path(db),
             parent_full_path: Some(id.parent_module(db).full_path(db)),
+            location: SourceLocation::new(db, documentable_item_id),
         }
     }
 
     pub fn new_crate(db: &ScarbDocDatabase, id: CrateId) -> Self {
         let documentable_id = DocumentableItemId::Crate(id);
+        let doc = db.get_item_documentation_as_tokens(documentable_id);
         Self {
             id: documentable_id,
             name: id.name(db).into(),
-            doc: db.get_item_documentation_as_tokens(documentable_id),
+            link_targets: collect_link_targets(db, &doc),
+            doc,
             signature: None,
             full_path: ModuleId::CrateRoot(id).full_path(db),
             parent_full_path: None,
+            location: None,
         }
     }
 }
 
+fn collect_link_targets(
+    db: &ScarbDocDatabase,
+    doc: &Option<Vec<DocumentationCommentToken>>,
+) -> HashMap<DocumentableItemId, String> {
+    doc.iter()
+        .flatten()
+        .filter_map(|token| match token {
+            DocumentationCommentToken::Link(link) => link.resolved_item,
+            DocumentationCommentToken::Content(_) => None,
+        })
+        .map(|id| (id, documentable_item_full_path(db, id)))
+        .collect()
+}
+
+fn documentable_item_full_path(db: &ScarbDocDatabase, id: DocumentableItemId) -> String {
+    match id {
+        DocumentableItemId::Crate(id) => ModuleId::CrateRoot(id).full_path(db),
+        DocumentableItemId::LookupItem(LookupItemId::ModuleItem(id)) => id.full_path(db),
+        DocumentableItemId::LookupItem(LookupItemId::TraitItem(id)) => id.full_path(db),
+        DocumentableItemId::LookupItem(LookupItemId::ImplItem(id)) => id.full_path(db),
+        DocumentableItemId::Member(id) => id.full_path(db),
+        DocumentableItemId::Variant(id) => id.full_path(db),
+    }
+}
+
+/// Location of an item in the source code, emitted with `--emit-source-links`.
+#[derive(Debug, Serialize, Clone)]
+pub struct SourceLocation {
+    /// Path to the source file, relative to the workspace root.
+    pub file: Utf8PathBuf,
+    pub start: SourcePosition,
+    pub end: SourcePosition,
+}
+
+/// Position in a source file, with 1-based line and column numbers.
+#[derive(Debug, Serialize, Clone)]
+pub struct SourcePosition {
+    pub line: usize,
+    pub col: usize,
+}
+
+impl SourceLocation {
+    /// Returns `None` if source links are disabled, or the item is not backed by a file on disk.
+    fn new(db: &ScarbDocDatabase, id: DocumentableItemId) -> Option<Self> {
+        let workspace_root = db.source_links_root()?;
+        let location = id
+            .stable_location(db.upcast())?
+            .diagnostic_location(db.upcast());
+        let FileLongId::OnDisk(path) = location.file_id.lookup_intern(db) else {
+            return None;
+        };
+        let path = Utf8PathBuf::from_path_buf(path).ok()?;
+        let file = path
+            .strip_prefix(workspace_root)
+            .map(Utf8Path::to_path_buf)
+            .unwrap_or(path);
+        let span = location
+            .span
+            .position_in_file(db.upcast(), location.file_id)?;
+        let position = |position: TextPosition| SourcePosition {
+            line: position.line + 1,
+            col: position.col + 1,
+        };
+        Some(Self {
+            file,
+            start: position(span.start),
+            end: position(span.end),
+        })
+    }
+}
+
 fn documentation_serializer<S>(
     docs: &Option<Vec<DocumentationCommentToken>>,
     serializer: S,
@@ -1,6 +1,7 @@
 use assert_fs::TempDir;
 use indoc::indoc;
 use scarb_test_support::{command::Scarb, project_builder::ProjectBuilder};
+use test_case::test_case;
 
 #[test]
 fn test_diagnostics_success() {
@@ -90,7 +91,68 @@ fn test_diagnostics_not_allowed_warnings() {
         .arg("doc")
         .current_dir(&t)
         .assert()
-        .success();
+        .failure()
+        .stdout_matches(indoc! {r#"
+        warn[..]: Unused variable. Consider ignoring by prefixing with `_`.
+        ...
+        error: Compilation failed.
+        ...
+        "#});
+}
+
+#[test_case(None, None, true)]
+#[test_case(None, Some("--allow-warnings"), true)]
+#[test_case(None, Some("--deny-warnings"), false)]
+#[test_case(Some(true), None, true)]
+#[test_case(Some(true), Some("--allow-warnings"), true)]
+#[test_case(Some(true), Some("--deny-warnings"), false)]
+#[test_case(Some(false), None, false)]
+#[test_case(Some(false), Some("--allow-warnings"), true)]
+#[test_case(Some(false), Some("--deny-warnings"), false)]
+fn test_diagnostics_warnings_policy(
+    manifest_allow_warnings: Option<bool>,
+    flag: Option<&str>,
+    success: bool,
+) {
+    let t = TempDir::new().unwrap();
+
+    let builder = ProjectBuilder::start()
+        .name("hello_world")
+        .lib_cairo(indoc! {r#"
+          fn main() {
+            let a = 5;
+          }
+        "#});
+    let builder = match manifest_allow_warnings {
+        Some(allow) => builder.manifest_extra(format!("[cairo]\nallow-warnings = {allow}\n")),
+        None => builder,
+    };
+    builder.build(&t);
+
+    let assert = Scarb::quick_snapbox()
+        .arg("doc")
+        .args(flag)
+        .current_dir(&t)
+        .assert();
+    if success {
+        assert.success();
+    } else {
+        assert.failure();
+    }
+}
+
+#[test]
+fn test_diagnostics_allow_and_deny_warnings_conflict() {
+    let t = TempDir::new().unwrap();
+    ProjectBuilder::start().name("hello_world").build(&t);
+
+    Scarb::quick_snapbox()
+        .arg("doc")
+        .arg("--allow-warnings")
+        .arg("--deny-warnings")
+        .current_dir(&t)
+        .assert()
+        .failure();
 }
 
 #[test]
@@ -0,0 +1,109 @@
+use assert_fs::TempDir;
+use indoc::indoc;
+use scarb_doc::errors::{
+    error_to_json, DiagnosticError, MetadataCommandError, PackagesSerializationError,
+};
+use scarb_metadata::MetadataCommandError as ScarbMetadataCommandFail;
+use scarb_test_support::command::Scarb;
+use scarb_test_support::project_builder::ProjectBuilder;
+use serde_json::json;
+
+#[test]
+fn diagnostic_error_to_json() {
+    let error = DiagnosticError("hello_world".to_string());
+    assert_eq!(
+        serde_json::to_value(&error).unwrap(),
+        json!({
+            "category": "diagnostics",
+            "package": "hello_world",
+            "message": "could not compile hello_world due to previous error",
+        })
+    );
+}
+
+#[test]
+fn metadata_command_error_to_json() {
+    let error = MetadataCommandError::from(ScarbMetadataCommandFail::NotFound {
+        stdout: String::new(),
+    });
+    assert_eq!(
+        serde_json::to_value(&error).unwrap(),
+        json!({
+            "category": "metadata",
+            "package": null,
+            "message": "metadata command failed",
+        })
+    );
+}
+
+#[test]
+fn packages_serialization_error_to_json() {
+    let error = PackagesSerializationError::from(serde_json::from_str::<u32>("x").unwrap_err());
+    assert_eq!(
+        serde_json::to_value(&error).unwrap(),
+        json!({
+            "category": "serialization",
+            "package": null,
+            "message": "failed to serialize information about crates",
+        })
+    );
+}
+
+#[test]
+fn error_to_json_finds_cause_in_chain() {
+    let error = anyhow::Error::from(DiagnosticError("hello_world".to_string()))
+        .context("failed to generate documentation");
+    assert_eq!(
+        error_to_json(&error).unwrap()["category"],
+        json!("diagnostics")
+    );
+    assert!(error_to_json(&anyhow::anyhow!("unknown error")).is_none());
+}
+
+fn error_records(output: &[u8]) -> Vec<serde_json::Value> {
+    String::from_utf8_lossy(output)
+        .lines()
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+        .filter(|value| value.get("category").is_some())
+        .collect()
+}
+
+#[test]
+fn error_record_is_printed_in_json_mode() {
+    let t = TempDir::new().unwrap();
+    ProjectBuilder::start()
+        .name("hello_world")
+        .lib_cairo(indoc! {r#"
+            fn main() -> felt252 {
+                undefined_function()
+            }
+        "#})
+        .build(&t);
+
+    let output = Scarb::quick_snapbox()
+        .args(["--json", "doc"])
+        .current_dir(&t)
+        .assert()
+        .failure()
+        .get_output()
+        .stdout
+        .clone();
+    assert_eq!(
+        error_records(&output),
+        vec![json!({
+            "category": "diagnostics",
+            "package": "hello_world",
+            "message": "could not compile hello_world due to previous error",
+        })]
+    );
+
+    let output = Scarb::quick_snapbox()
+        .args(["doc", "--output-format", "json"])
+        .current_dir(&t)
+        .assert()
+        .failure()
+        .get_output()
+        .stdout
+        .clone();
+    assert!(error_records(&output).is_empty());
+}
@@ -1,6 +1,9 @@
 //! Run `UPDATE_EXPECT=1 cargo test` to fix the tests.
 
+use std::fs;
+
 use assert_fs::TempDir;
+use indoc::indoc;
 use scarb_test_support::{command::Scarb, project_builder::ProjectBuilder};
 
 mod markdown_target;
@@ -34,6 +37,60 @@ fn json_output() {
         .assert_files_match();
 }
 
+#[test]
+fn json_output_with_source_links() {
+    let t = TempDir::new().unwrap();
+    ProjectBuilder::start()
+        .name("hello_world")
+        .lib_cairo(indoc! {r#"
+            /// Returns the answer.
+            pub fn answer() -> felt252 {
+                42
+            }
+        "#})
+        .build(&t);
+
+    Scarb::quick_snapbox()
+        .arg("doc")
+        .args(["--output-format", "json", "--emit-source-links"])
+        .current_dir(&t)
+        .assert()
+        .success();
+
+    let output: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(t.path().join("target/doc/output.json")).unwrap())
+            .unwrap();
+    let function = &output["packages_information"][0]["crate_"]["root_module"]["free_functions"][0];
+    assert_eq!(function["item_data"]["name"], "answer");
+    assert_eq!(
+        function["item_data"]["location"],
+        serde_json::json!({
+            "file": "src/lib.cairo",
+            "start": { "line": 2, "col": 1 },
+            "end": { "line": 4, "col": 2 },
+        })
+    );
+}
+
+#[test]
+fn json_output_without_source_links() {
+    let t = TempDir::new().unwrap();
+    ProjectBuilder::start()
+        .name("hello_world")
+        .lib_cairo(FIBONACCI_CODE_WITHOUT_FEATURE)
+        .build(&t);
+
+    Scarb::quick_snapbox()
+        .arg("doc")
+        .args(["--output-format", "json"])
+        .current_dir(&t)
+        .assert()
+        .success();
+
+    let output = fs::read_to_string(t.path().join("target/doc/output.json")).unwrap();
+    assert!(!output.contains("\"location\""));
+}
+
 #[test]
 fn markdown_output() {
     let t = TempDir::new().unwrap();
@@ -54,3 +111,32 @@ fn markdown_output() {
         .expected(EXPECTED_ROOT_PACKAGE_NO_FEATURES_PATH)
         .assert_all_files_match();
 }
+
+#[test]
+fn markdown_output_with_theme() {
+    let t = TempDir::new().unwrap();
+    ProjectBuilder::start()
+        .name("hello_world")
+        .lib_cairo(FIBONACCI_CODE_WITHOUT_FEATURE)
+        .build(&t);
+
+    Scarb::quick_snapbox()
+        .arg("doc")
+        .args(["--theme", "dark"])
+        .current_dir(&t)
+        .assert()
+        .success();
+
+    let book_dir = t.path().join("target/doc/hello_world");
+    let book_toml = fs::read_to_string(book_dir.join("book.toml")).unwrap();
+    assert!(book_toml.contains("default-theme = \"navy\"\npreferred-dark-theme = \"navy\"\n"));
+    assert_eq!(
+        fs::read_to_string(book_dir.join("theme/highlight.js")).unwrap(),
+        include_str!("../theme/highlight.js")
+    );
+    assert_eq!(
+        fs::read_to_string(book_dir.join("theme/tomorrow-night.css")).unwrap(),
+        include_str!("../theme/highlight-dark.css")
+    );
+    assert!(!book_dir.join("theme/highlight.css").exists());
+}
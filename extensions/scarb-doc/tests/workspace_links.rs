@@ -0,0 +1,90 @@
+use assert_fs::prelude::PathChild;
+use assert_fs::TempDir;
+use indoc::indoc;
+use std::fs;
+
+use scarb_test_support::command::Scarb;
+use scarb_test_support::project_builder::ProjectBuilder;
+use scarb_test_support::workspace_builder::WorkspaceBuilder;
+
+#[test]
+fn links_between_workspace_packages() {
+    let t = TempDir::new().unwrap();
+    ProjectBuilder::start()
+        .name("first")
+        .lib_cairo(indoc! {r#"
+            /// A point in space.
+            pub struct Point {
+                pub x: u32,
+            }
+        "#})
+        .build(&t.child("first"));
+    ProjectBuilder::start()
+        .name("second")
+        .dep("first", &t.child("first"))
+        .lib_cairo(indoc! {r#"
+            /// Moves a [first::Point] into an [core::array::Array].
+            pub fn wrap(point: first::Point) -> Array<first::Point> {
+                array![point]
+            }
+        "#})
+        .build(&t.child("second"));
+    WorkspaceBuilder::start()
+        .add_member("first")
+        .add_member("second")
+        .build(&t);
+
+    Scarb::quick_snapbox()
+        .args(["doc", "--workspace"])
+        .current_dir(&t)
+        .assert()
+        .success();
+
+    let wrap = fs::read_to_string(t.child("target/doc/second/src/second-wrap.md")).unwrap();
+    assert!(wrap.contains("[first::Point](../../first/src/first-Point.md)"));
+    // References outside of the workspace are not turned into links between doc trees.
+    assert!(!wrap.contains("../../core"));
+    assert!(t
+        .child("target/doc/first/src/first-Point.md")
+        .path()
+        .is_file());
+}
+
+#[test]
+fn links_to_imported_items_of_workspace_packages() {
+    let t = TempDir::new().unwrap();
+    ProjectBuilder::start()
+        .name("first")
+        .lib_cairo(indoc! {r#"
+            /// A point in space.
+            pub struct Point {
+                pub x: u32,
+            }
+        "#})
+        .build(&t.child("first"));
+    ProjectBuilder::start()
+        .name("second")
+        .dep("first", &t.child("first"))
+        .lib_cairo(indoc! {r#"
+            use first::Point;
+
+            /// Moves a [Point] into an array.
+            pub fn wrap(point: Point) -> Array<Point> {
+                array![point]
+            }
+        "#})
+        .build(&t.child("second"));
+    WorkspaceBuilder::start()
+        .add_member("first")
+        .add_member("second")
+        .build(&t);
+
+    Scarb::quick_snapbox()
+        .args(["doc", "--workspace"])
+        .current_dir(&t)
+        .assert()
+        .success();
+
+    let wrap = fs::read_to_string(t.child("target/doc/second/src/second-wrap.md")).unwrap();
+    assert!(wrap.contains("[Point](../../first/src/first-Point.md)"));
+}
@@ -1,11 +1,13 @@
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use cairo_lang_runner::Arg;
 use cairo_lang_utils::bigint::BigUintAsHex;
+use cairo_vm::types::layout_name::LayoutName;
 use camino::Utf8PathBuf;
 use clap::{arg, Parser, ValueEnum};
 use num_bigint::BigInt;
-use scarb_ui::args::{PackagesFilter, VerbositySpec};
+use scarb_ui::args::{ColorSpec, PackagesFilter, VerbositySpec};
 use std::fs;
+use std::str::FromStr;
 
 /// Compiles a Cairo project and runs a function marked `#[executable]`.
 /// Exits with 1 if the compilation or run fails, otherwise 0.
@@ -22,6 +24,10 @@ pub struct Args {
     /// Logging verbosity.
     #[command(flatten)]
     pub verbose: VerbositySpec,
+
+    /// Coloring of the output.
+    #[command(flatten)]
+    pub color: ColorSpec,
 }
 
 #[derive(Parser, Clone, Debug)]
@@ -39,10 +45,16 @@ pub struct RunArgs {
     #[command(flatten)]
     pub arguments: ProgramArguments,
 
-    /// Desired execution output, either default Standard or CairoPie
+    /// Desired execution output, either default Standard, CairoPie or BootloaderTask
     #[arg(long, default_value = "standard")]
     pub output: OutputFormat,
 
+    /// Hash function the bootloader uses to compute the program hash of the task.
+    ///
+    /// Only used with the `bootloader-task` output format.
+    #[arg(long, value_enum, default_value_t)]
+    pub task_program_hash: ProgramHashFunction,
+
     /// Execution target.
     #[arg(long, default_value = "standalone")]
     pub target: ExecutionTarget,
@@ -50,6 +62,41 @@ pub struct RunArgs {
     /// Whether to print the program outputs.
     #[arg(long, default_value_t = false)]
     pub print_program_output: bool,
+
+    /// How to render the printed program outputs.
+    ///
+    /// With `felts` and `json`, the rendered outputs are also saved in the execution directory.
+    #[arg(long, value_enum, default_value_t, requires = "print_program_output")]
+    pub output_decoding: OutputDecoding,
+
+    /// Whether to print a SHA-256 hash of the program output values.
+    ///
+    /// The hash is stable across runs, so it can be used to verify that a run reproduces
+    /// a known output.
+    #[arg(long, default_value_t = false)]
+    pub output_hash: bool,
+
+    /// Cairo layout to run the program with.
+    #[arg(long, value_enum, default_value_t = LayoutName::all_cairo)]
+    pub layout: LayoutName,
+
+    /// Fail if the program uses builtins which are not available in the selected layout.
+    #[arg(long, default_value_t = false)]
+    pub strict_builtins: bool,
+
+    /// Allocate all memory segments as regular segments, so that emitted memory and trace
+    /// files are identical across runs.
+    ///
+    /// Segments of dictionaries are normally temporary, and are relocated at the end of
+    /// the run. Allocating them up front may make the run use more memory.
+    #[arg(long, default_value_t = false)]
+    pub deterministic_ids: bool,
+
+    /// Abort the execution once the program takes more than this many Cairo VM steps.
+    ///
+    /// The number of steps is unbounded by default.
+    #[arg(long)]
+    pub max_steps: Option<usize>,
 }
 
 #[derive(Parser, Debug, Clone)]
@@ -61,18 +108,43 @@ pub struct ProgramArguments {
     /// Serialized arguments to the executable function from a file.
     #[arg(long, conflicts_with = "arguments")]
     pub arguments_file: Option<Utf8PathBuf>,
+
+    /// Format of values in the arguments file.
+    #[arg(long, value_enum, default_value_t, requires = "arguments_file")]
+    pub arguments_format: ArgumentsFormat,
 }
 
+/// Format of values in the arguments file.
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ArgumentsFormat {
+    /// JSON array of hex strings, like `["0x1", "0x2"]`.
+    #[default]
+    Hex,
+    /// JSON array of decimal numbers or strings of decimal digits, like `[1, "2"]`.
+    ///
+    /// Values exceeding 64 bits must be passed as strings.
+    Dec,
+}
+
+/// Maximum nesting depth of arrays in the arguments file.
+pub const MAX_ARGUMENTS_DEPTH: usize = 8;
+
 impl ProgramArguments {
+    /// Read arguments to the executable function.
+    ///
+    /// Arrays nested in the arguments file are read as [`Arg::Array`]s.
     pub fn read_arguments(self) -> Result<Vec<Arg>> {
         if let Some(path) = self.arguments_file {
             let file = fs::File::open(&path).with_context(|| "reading arguments file failed")?;
-            let as_vec: Vec<BigUintAsHex> = serde_json::from_reader(file)
+            let value: serde_json::Value = serde_json::from_reader(file)
                 .with_context(|| "deserializing arguments file failed")?;
-            Ok(as_vec
-                .into_iter()
-                .map(|v| Arg::Value(v.value.into()))
-                .collect())
+            let Some(values) = value.as_array() else {
+                bail!(
+                    "arguments file must contain a JSON array of {} values",
+                    self.arguments_format.describe()
+                );
+            };
+            parse_arguments(values, self.arguments_format, &[])
         } else {
             Ok(self
                 .arguments
@@ -83,10 +155,97 @@ impl ProgramArguments {
     }
 }
 
+impl ArgumentsFormat {
+    fn describe(self) -> &'static str {
+        match self {
+            Self::Hex => "hex",
+            Self::Dec => "decimal",
+        }
+    }
+}
+
+/// Parse elements of a (possibly nested) JSON array into arguments, preserving their order.
+///
+/// The `path` holds indices of arrays enclosing the array being parsed, and is used in errors.
+fn parse_arguments(
+    values: &[serde_json::Value],
+    format: ArgumentsFormat,
+    path: &[usize],
+) -> Result<Vec<Arg>> {
+    values
+        .iter()
+        .enumerate()
+        .map(|(index, value)| {
+            let path = [path, &[index]].concat();
+            let index = path
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(".");
+            if let serde_json::Value::Array(values) = value {
+                if path.len() > MAX_ARGUMENTS_DEPTH {
+                    bail!(
+                        "argument at index {index} exceeds maximum nesting depth of \
+                        {MAX_ARGUMENTS_DEPTH} arrays"
+                    );
+                }
+                return Ok(Arg::Array(parse_arguments(values, format, &path)?));
+            }
+            let parsed = match format {
+                ArgumentsFormat::Hex => parse_hex_argument(value, &index)?,
+                ArgumentsFormat::Dec => parse_decimal_argument(value, &index)?,
+            };
+            Ok(Arg::Value((&parsed).into()))
+        })
+        .collect()
+}
+
+fn parse_hex_argument(value: &serde_json::Value, index: &str) -> Result<BigInt> {
+    let parsed: BigUintAsHex = serde_json::from_value(value.clone())
+        .with_context(|| format!("argument at index {index} is not a hex value: {value}"))?;
+    Ok(parsed.value.into())
+}
+
+fn parse_decimal_argument(value: &serde_json::Value, index: &str) -> Result<BigInt> {
+    match value {
+        serde_json::Value::Number(number) => number.as_u64().map(BigInt::from).with_context(|| {
+            format!(
+                "argument at index {index} is not an unsigned integer fitting in 64 bits: \
+                {number}, pass large values as strings of decimal digits"
+            )
+        }),
+        serde_json::Value::String(string) if string.starts_with("0x") => bail!(
+            "argument at index {index} is a hex value: {string}, \
+            use `--arguments-format hex` for hex arguments"
+        ),
+        serde_json::Value::String(string) => BigInt::from_str(string)
+            .ok()
+            .filter(|_| string.bytes().all(|b| b.is_ascii_digit()))
+            .with_context(|| {
+                format!("argument at index {index} is not a decimal number: {string}")
+            }),
+        _ => bail!("argument at index {index} is not a decimal number: {value}"),
+    }
+}
+
+/// Rendering of program outputs.
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OutputDecoding {
+    /// Each value on a separate line, followed by the panic reason if the program panicked.
+    #[default]
+    Debug,
+    /// Array of decimal values, like `[1, 2]`, saved to `program_output.txt`.
+    Felts,
+    /// JSON array of hex strings, like `["0x1","0x2"]`, saved to `program_output.json`.
+    Json,
+}
+
 #[derive(ValueEnum, Clone, Debug)]
 pub enum OutputFormat {
     CairoPie,
     Standard,
+    /// Cairo PIE, with a `task.json` file describing a bootloader task running it.
+    BootloaderTask,
 }
 impl OutputFormat {
     pub fn is_standard(&self) -> bool {
@@ -95,6 +254,17 @@ impl OutputFormat {
     pub fn is_cairo_pie(&self) -> bool {
         matches!(self, OutputFormat::CairoPie)
     }
+    pub fn is_bootloader_task(&self) -> bool {
+        matches!(self, OutputFormat::BootloaderTask)
+    }
+}
+
+/// Hash function used to compute the hash of a program run by the bootloader.
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ProgramHashFunction {
+    #[default]
+    Poseidon,
+    Pedersen,
 }
 
 #[derive(ValueEnum, Clone, Debug)]
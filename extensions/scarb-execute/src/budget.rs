@@ -0,0 +1,56 @@
+use anyhow::{ensure, Context, Result};
+use cairo_vm::vm::runners::cairo_runner::ExecutionResources;
+use scarb_metadata::PackageMetadata;
+use serde::Deserialize;
+
+/// Resource limits of an executable, declared in the `[tool.budget]` manifest table.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct Budget {
+    /// Maximum number of Cairo VM steps a single execution may take.
+    pub steps: Option<usize>,
+    /// Maximum number of memory holes a single execution may leave.
+    pub memory_holes: Option<usize>,
+}
+
+impl Budget {
+    /// Read the budget of the given package, if its manifest declares one.
+    pub fn of_package(package: &PackageMetadata) -> Result<Option<Self>> {
+        package
+            .tool_metadata("budget")
+            .map(|value| {
+                serde_json::from_value(value.clone())
+                    .context("failed to parse `[tool.budget]` table")
+            })
+            .transpose()
+    }
+
+    /// Fail if the execution of the given package used more resources than allowed.
+    pub fn check(&self, package: &PackageMetadata, resources: &ExecutionResources) -> Result<()> {
+        check_limit(package, "steps", resources.n_steps, self.steps)?;
+        check_limit(
+            package,
+            "memory holes",
+            resources.n_memory_holes,
+            self.memory_holes,
+        )
+    }
+}
+
+fn check_limit(
+    package: &PackageMetadata,
+    resource: &str,
+    used: usize,
+    limit: Option<usize>,
+) -> Result<()> {
+    let Some(limit) = limit else {
+        return Ok(());
+    };
+    ensure!(
+        used <= limit,
+        "package `{}` exceeds its budget of {resource}: used {used}, limit is {limit} (over by {})",
+        package.name,
+        used - limit
+    );
+    Ok(())
+}
@@ -1,32 +1,87 @@
-use anyhow::{bail, ensure, Context, Result};
+use crate::args::{OutputDecoding, ProgramHashFunction};
+use crate::budget::Budget;
+use anyhow::{anyhow, bail, ensure, Context, Result};
 use bincode::enc::write::Writer;
 use cairo_lang_executable::executable::{EntryPointKind, Executable};
 use cairo_lang_runner::casm_run::format_for_panic;
 use cairo_lang_runner::{build_hints_dict, Arg, CairoHintProcessor};
 use cairo_vm::cairo_run::cairo_run_program;
 use cairo_vm::cairo_run::CairoRunConfig;
-use cairo_vm::types::layout_name::LayoutName;
 use cairo_vm::types::program::Program;
-use cairo_vm::types::relocatable::MaybeRelocatable;
+use cairo_vm::types::relocatable::{MaybeRelocatable, Relocatable};
+use cairo_vm::vm::runners::cairo_runner::{ResourceTracker, RunResources};
+use cairo_vm::vm::vm_core::VirtualMachine;
 use cairo_vm::{cairo_run, Felt252};
 use camino::{Utf8Path, Utf8PathBuf};
 use create_output_dir::create_output_dir;
+use data_encoding::HEXLOWER;
 use indoc::formatdoc;
 use scarb_metadata::{Metadata, MetadataCommand, PackageMetadata, ScarbCommand};
 use scarb_ui::args::PackagesFilter;
 use scarb_ui::components::Status;
-use scarb_ui::Ui;
+use scarb_ui::{FailureExt, FailureKind, Message, Ui};
+use serde::{Serialize, Serializer};
+use sha2::{Digest, Sha256};
 use std::env;
 use std::fs;
 use std::io::{self, Write};
 
 pub mod args;
+pub mod budget;
 const MAX_ITERATION_COUNT: usize = 10000;
 
-pub fn main_inner(args: args::Args, ui: Ui) -> Result<usize, anyhow::Error> {
+pub fn main_inner(args: args::Args, ui: Ui) -> Result<(), anyhow::Error> {
     let metadata = MetadataCommand::new().inherit_stderr().exec()?;
-    let package = args.packages_filter.match_one(&metadata)?;
-    execute(&package, &args.execution, &ui)
+    if args.packages_filter.is_workspace() {
+        let packages = args
+            .packages_filter
+            .match_many(&metadata)
+            .failure_kind(FailureKind::Usage)?;
+        return execute_all(&packages, &args.execution, &ui);
+    }
+    let package = args
+        .packages_filter
+        .match_one(&metadata)
+        .failure_kind(FailureKind::Usage)?;
+    execute(&package, &args.execution, &ui)?;
+    Ok(())
+}
+
+/// Execute each of the given packages, and fail if any of them failed.
+///
+/// A failing package does not stop execution of the remaining ones, so that budgets of all
+/// workspace members are checked in a single run.
+fn execute_all(
+    packages: &[PackageMetadata],
+    args: &args::ExecutionArgs,
+    ui: &Ui,
+) -> Result<(), anyhow::Error> {
+    let mut failures = Vec::new();
+    for package in packages {
+        if let Err(error) = execute(package, args, ui) {
+            ui.error(format!("{error:#}"));
+            failures.push((package.name.as_str(), FailureKind::of(&error)));
+        }
+    }
+
+    let Some((_, kind)) = failures.first() else {
+        return Ok(());
+    };
+    // Report the common failure kind if there is one, to keep the exit code specific.
+    let kind = if failures.iter().all(|(_, k)| k == kind) {
+        *kind
+    } else {
+        FailureKind::default()
+    };
+    Err(anyhow!(
+        "failed to execute packages: {}",
+        failures
+            .iter()
+            .map(|(name, _)| format!("`{name}`"))
+            .collect::<Vec<_>>()
+            .join(", ")
+    ))
+    .failure_kind(kind)
 }
 
 pub fn execute(
@@ -38,12 +93,21 @@ pub fn execute(
         !(args.run.output.is_cairo_pie() && args.run.target.is_standalone()),
         "Cairo pie output format is not supported for standalone execution target"
     );
+    ensure!(
+        !(args.run.output.is_bootloader_task() && args.run.target.is_standalone()),
+        "bootloader task output format is not supported for standalone execution target"
+    );
+    ensure!(
+        !(args.run.deterministic_ids && !args.run.output.is_standard()),
+        "deterministic ids are only supported for standard output format"
+    );
 
     if !args.no_build {
         let filter = PackagesFilter::generate_for::<Metadata>(vec![package.clone()].iter());
         ScarbCommand::new()
             .arg("build")
             .env("SCARB_PACKAGES_FILTER", filter.to_env())
+            .env("SCARB_UI_VERBOSITY", ui.verbosity().to_string())
             .run()?;
     }
 
@@ -104,20 +168,28 @@ pub fn execute(
 
     let mut hint_processor = CairoHintProcessor {
         runner: None,
-        user_args: vec![vec![Arg::Array(
-            args.run.arguments.clone().read_arguments()?,
-        )]],
+        user_args: vec![vec![Arg::Array(serialize_arguments(
+            args.run
+                .arguments
+                .clone()
+                .read_arguments()
+                .failure_kind(FailureKind::Usage)?,
+        ))]],
         string_to_hint,
         starknet_state: Default::default(),
-        run_resources: Default::default(),
+        run_resources: args
+            .run
+            .max_steps
+            .map(RunResources::new)
+            .unwrap_or_default(),
         syscalls_used_resources: Default::default(),
-        no_temporary_segments: false,
+        no_temporary_segments: args.run.deterministic_ids,
         markers: Default::default(),
     };
 
     let cairo_run_config = CairoRunConfig {
-        allow_missing_builtins: Some(true),
-        layout: LayoutName::all_cairo,
+        allow_missing_builtins: Some(!args.run.strict_builtins),
+        layout: args.run.layout,
         proof_mode: args.run.target.is_standalone(),
         secure_run: None,
         relocate_mem: args.run.output.is_standard(),
@@ -126,23 +198,60 @@ pub fn execute(
     };
 
     let mut runner = cairo_run_program(&program, &cairo_run_config, &mut hint_processor)
-        .with_context(|| "Cairo program run failed")?;
-
-    if args.run.print_program_output {
-        let mut output_buffer = "Program output:\n".to_string();
-        runner.vm.write_output(&mut output_buffer)?;
-        ui.print(output_buffer.trim_end());
-        // Print panic reason.
-        if let [.., start_marker, end_marker] = &hint_processor.markers[..] {
-            let size = (*end_marker - *start_marker).with_context(|| {
-                format!("panic data markers mismatch: start={start_marker}, end={end_marker}")
-            })?;
-            let panic_data = runner
-                .vm
-                .get_integer_range(*start_marker, size)
-                .with_context(|| "failed reading panic data")?;
-            ui.print(format_for_panic(panic_data.into_iter().map(|value| *value)));
+        .map_err(|err| match args.run.max_steps {
+            // The VM stops once all step resources are consumed, and reports an unfinished run.
+            Some(max_steps) if hint_processor.run_resources.consumed() => {
+                anyhow!("step limit exceeded: program did not finish within {max_steps} steps")
+            }
+            _ => anyhow::Error::from(err).context("Cairo program run failed"),
+        })
+        .failure_kind(FailureKind::Runtime)?;
+
+    let panic_reason = read_panic_reason(&hint_processor.markers, &runner.vm)?;
+    let panicked = panic_reason.is_some();
+
+    let decoded_output = if args.run.print_program_output {
+        match args.run.output_decoding {
+            OutputDecoding::Debug => {
+                let mut program_output = String::new();
+                runner.vm.write_output(&mut program_output)?;
+                ui.print(format!("Program output:\n{}", program_output.trim_end()));
+                if let Some(panic_reason) = panic_reason {
+                    ui.print(panic_reason);
+                }
+                None
+            }
+            decoding => {
+                let output = DecodedProgramOutput::new(
+                    read_program_output(&mut runner.vm)?,
+                    decoding,
+                    panic_reason,
+                );
+                let text = output.render();
+                ui.print(output);
+                Some(text)
+            }
         }
+    } else {
+        None
+    };
+
+    if args.run.output_hash {
+        let mut hasher = Sha256::new();
+        for value in read_program_output(&mut runner.vm)? {
+            hasher.update(value.to_bytes_be());
+        }
+        let hash = HEXLOWER.encode(&hasher.finalize());
+        ui.print(Status::new("Output hash", &hash));
+    }
+
+    if let Some(budget) = Budget::of_package(package)? {
+        let resources = runner
+            .get_execution_resources()
+            .with_context(|| "failed to read execution resources")?;
+        budget
+            .check(package, &resources)
+            .failure_kind(FailureKind::Runtime)?;
     }
 
     let output_dir = scarb_target_dir.join("execute").join(&package.name);
@@ -150,6 +259,14 @@ pub fn execute(
 
     let (execution_output_dir, execution_id) = incremental_create_output_dir(&output_dir)?;
 
+    if let Some(decoded_output) = decoded_output {
+        let file_name = match args.run.output_decoding {
+            OutputDecoding::Json => "program_output.json",
+            _ => "program_output.txt",
+        };
+        fs::write(execution_output_dir.join(file_name), decoded_output)?;
+    }
+
     if args.run.output.is_cairo_pie() {
         let output_value = runner.get_cairo_pie()?;
         let output_file_path = execution_output_dir.join("cairo_pie.zip");
@@ -158,6 +275,27 @@ pub fn execute(
             &display_path(&scarb_target_dir, &output_file_path),
         ));
         output_value.write_zip_file(output_file_path.as_std_path())?;
+    } else if args.run.output.is_bootloader_task() {
+        ui.print(Status::new(
+            "Saving output to:",
+            &display_path(&scarb_target_dir, &execution_output_dir),
+        ));
+        // The task refers to the PIE saved next to it, so the directory can be moved as a whole.
+        let cairo_pie_path = Utf8PathBuf::from("cairo_pie.zip");
+        runner
+            .get_cairo_pie()?
+            .write_zip_file(execution_output_dir.join(&cairo_pie_path).as_std_path())?;
+
+        // Write a task description, in the format of bootloader task inputs.
+        let task = BootloaderTask {
+            r#type: "CairoPiePath",
+            path: cairo_pie_path,
+            use_poseidon: args.run.task_program_hash == ProgramHashFunction::Poseidon,
+        };
+        fs::write(
+            execution_output_dir.join("task.json"),
+            serde_json::to_string_pretty(&task)?,
+        )?;
     } else {
         ui.print(Status::new(
             "Saving output to:",
@@ -195,6 +333,11 @@ pub fn execute(
         fs::write(air_private_input_path, output_value)?;
     }
 
+    // Outputs of a panicked run are saved anyway, so that the run can be inspected.
+    if panicked {
+        return Err(anyhow!("program panicked")).failure_kind(FailureKind::Runtime);
+    }
+
     Ok(execution_id)
 }
 
@@ -240,6 +383,16 @@ fn incremental_create_output_dir(path: &Utf8Path) -> Result<(Utf8PathBuf, usize)
     bail!("failed to create output directory")
 }
 
+/// A bootloader task running a Cairo PIE, saved with `--output bootloader-task`.
+///
+/// The path of the PIE is relative to the directory of the task file.
+#[derive(Serialize)]
+struct BootloaderTask {
+    r#type: &'static str,
+    path: Utf8PathBuf,
+    use_poseidon: bool,
+}
+
 /// Writer implementation for a file.
 struct FileWriter {
     buf_writer: io::BufWriter<fs::File>,
@@ -278,3 +431,105 @@ impl FileWriter {
         self.buf_writer.flush()
     }
 }
+
+/// Read program outputs from the output builtin segment.
+fn read_program_output(vm: &mut VirtualMachine) -> Result<Vec<Felt252>> {
+    let Ok(output_builtin) = vm.get_output_builtin_mut() else {
+        return Ok(Vec::new());
+    };
+    let base = output_builtin.base();
+    let size = vm.segments.compute_effective_sizes()[base];
+    let values = vm
+        .get_integer_range(Relocatable::from((base as isize, 0)), size)
+        .with_context(|| "failed reading program output")?;
+    Ok(values.into_iter().map(|value| *value).collect())
+}
+
+/// Read the panic reason of the program, if it panicked.
+fn read_panic_reason(markers: &[Relocatable], vm: &VirtualMachine) -> Result<Option<String>> {
+    let [.., start_marker, end_marker] = markers else {
+        return Ok(None);
+    };
+    let size = (*end_marker - *start_marker).with_context(|| {
+        format!("panic data markers mismatch: start={start_marker}, end={end_marker}")
+    })?;
+    let panic_data = vm
+        .get_integer_range(*start_marker, size)
+        .with_context(|| "failed reading panic data")?;
+    Ok(Some(format_for_panic(
+        panic_data.into_iter().map(|value| *value),
+    )))
+}
+
+/// Program output values, rendered with the `felts` or `json` [`OutputDecoding`].
+#[derive(Serialize)]
+struct DecodedProgramOutput {
+    #[serde(skip)]
+    decoding: OutputDecoding,
+    output: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    panic_reason: Option<String>,
+}
+
+impl DecodedProgramOutput {
+    fn new(values: Vec<Felt252>, decoding: OutputDecoding, panic_reason: Option<String>) -> Self {
+        let output = values
+            .iter()
+            .map(|value| match decoding {
+                OutputDecoding::Json => value.to_hex_string(),
+                _ => value.to_string(),
+            })
+            .collect();
+        Self {
+            decoding,
+            output,
+            panic_reason,
+        }
+    }
+
+    /// Render the output values alone, like `[0, 42]` or `["0x0","0x2a"]`.
+    fn render(&self) -> String {
+        match self.decoding {
+            OutputDecoding::Json => format!(
+                "[{}]",
+                self.output
+                    .iter()
+                    .map(|value| format!("\"{value}\""))
+                    .collect::<Vec<_>>()
+                    .join(",")
+            ),
+            _ => format!("[{}]", self.output.join(", ")),
+        }
+    }
+}
+
+impl Message for DecodedProgramOutput {
+    fn text(self) -> String {
+        match &self.panic_reason {
+            Some(panic_reason) => format!("{}\n{panic_reason}", self.render()),
+            None => self.render(),
+        }
+    }
+
+    fn structured<S: Serializer>(self, ser: S) -> Result<S::Ok, S::Error>
+    where
+        Self: Sized,
+    {
+        self.serialize(ser)
+    }
+}
+
+/// Serialize arguments the same way Cairo `Serde` serializes arrays, so that nested arrays are
+/// passed to the executable function as their length followed by their elements.
+fn serialize_arguments(args: Vec<Arg>) -> Vec<Arg> {
+    args.into_iter()
+        .flat_map(|arg| match arg {
+            Arg::Value(value) => vec![Arg::Value(value)],
+            Arg::Array(items) => {
+                let mut serialized = vec![Arg::Value(Felt252::from(items.len()))];
+                serialized.extend(serialize_arguments(items));
+                serialized
+            }
+        })
+        .collect()
+}
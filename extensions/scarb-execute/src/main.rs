@@ -1,18 +1,21 @@
 use clap::Parser;
 use scarb_execute::args::Args;
 use scarb_execute::main_inner;
-use scarb_ui::Ui;
+use scarb_ui::{FailureKind, Ui};
 use std::process::ExitCode;
 
 fn main() -> ExitCode {
     let args = Args::parse();
-    let ui = Ui::new(args.verbose.clone().into(), scarb_ui::OutputFormat::Text);
+    let output_format =
+        scarb_ui::OutputFormat::from_env_var("SCARB_UI_OUTPUT_FORMAT").unwrap_or_default();
+    let ui = Ui::new(args.verbose.clone().into(), output_format);
+    args.color.apply(&ui);
 
     match main_inner(args, ui.clone()) {
-        Ok(_execution_id) => ExitCode::SUCCESS,
+        Ok(()) => ExitCode::SUCCESS,
         Err(error) => {
             ui.error(format!("{error:#}"));
-            ExitCode::FAILURE
+            FailureKind::of(&error).exit_code()
         }
     }
 }
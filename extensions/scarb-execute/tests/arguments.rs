@@ -1,6 +1,9 @@
 use assert_fs::fixture::{FileWriteStr, PathChild};
 use assert_fs::TempDir;
+use cairo_lang_runner::Arg;
+use cairo_vm::Felt252;
 use indoc::indoc;
+use scarb_execute::args::{ArgumentsFormat, ProgramArguments};
 use scarb_test_support::command::Scarb;
 use scarb_test_support::project_builder::ProjectBuilder;
 
@@ -88,3 +91,242 @@ fn can_read_arguments_from_file() {
             Saving output to: target/execute/hello/execution1
         "#});
 }
+
+#[test]
+fn output_hash_is_stable() {
+    let t = TempDir::new().unwrap();
+
+    ProjectBuilder::start()
+        .name("hello")
+        .version("0.1.0")
+        .manifest_extra(indoc! {r#"
+            [executable]
+
+            [cairo]
+            enable-gas = false
+        "#})
+        .dep_cairo_execute()
+        .lib_cairo(indoc! {r#"
+        #[executable]
+        fn main(a: felt252) -> felt252 {
+            a * 2
+        }
+        "#})
+        .build(&t);
+
+    let output_hash = |arguments: &str| {
+        let output = Scarb::quick_snapbox()
+            .arg("execute")
+            .arg("--output-hash")
+            .arg("--arguments")
+            .arg(arguments)
+            .current_dir(&t)
+            .assert()
+            .success()
+            .get_output()
+            .stdout
+            .clone();
+        String::from_utf8(output)
+            .unwrap()
+            .lines()
+            .find_map(|line| line.trim().strip_prefix("Output hash ").map(str::to_string))
+            .expect("output hash should be printed")
+    };
+
+    let hash = output_hash("1");
+    assert_eq!(hash.len(), 64);
+    assert_eq!(output_hash("1"), hash);
+    assert_ne!(output_hash("2"), hash);
+}
+
+fn read_arguments_file(content: &str, format: ArgumentsFormat) -> anyhow::Result<String> {
+    let t = TempDir::new().unwrap();
+    let path = t.child("args.json");
+    path.write_str(content).unwrap();
+    let args = ProgramArguments {
+        arguments: Vec::new(),
+        arguments_file: Some(path.to_path_buf().try_into().unwrap()),
+        arguments_format: format,
+    }
+    .read_arguments()?;
+    // `Arg` does not implement `PartialEq`, so compare debug representations.
+    Ok(format!("{args:?}"))
+}
+
+#[test]
+fn decimal_and_hex_arguments_files_are_equivalent() {
+    let hex = read_arguments_file(
+        r#"["0x1","0x27F73E6C94FA8249EC9F2F4EEC607ACC97FA632C9E8FB6C49437E62390D9860"]"#,
+        ArgumentsFormat::Hex,
+    )
+    .unwrap();
+    let dec = read_arguments_file(
+        r#"[1, "1129815197211541481934112806673325772687763881719835256646064516195041515616"]"#,
+        ArgumentsFormat::Dec,
+    )
+    .unwrap();
+    assert_eq!(hex, dec);
+}
+
+#[test]
+fn malformed_decimal_arguments_file() {
+    let err = |content: &str| {
+        read_arguments_file(content, ArgumentsFormat::Dec)
+            .unwrap_err()
+            .to_string()
+    };
+    assert_eq!(
+        err(r#"["0x1"]"#),
+        "argument at index 0 is a hex value: 0x1, use `--arguments-format hex` for hex arguments"
+    );
+    assert_eq!(
+        err(r#"[1, "-2"]"#),
+        "argument at index 1 is not a decimal number: -2"
+    );
+    assert_eq!(
+        err("[1.5]"),
+        "argument at index 0 is not an unsigned integer fitting in 64 bits: 1.5, \
+        pass large values as strings of decimal digits"
+    );
+    assert_eq!(
+        err(r#"{"a": 1}"#),
+        "arguments file must contain a JSON array of decimal values"
+    );
+}
+
+#[test]
+fn can_read_decimal_arguments_from_file() {
+    let t = TempDir::new().unwrap();
+
+    ProjectBuilder::start()
+        .name("hello")
+        .version("0.1.0")
+        .manifest_extra(indoc! {r#"
+            [executable]
+
+            [cairo]
+            enable-gas = false
+        "#})
+        .dep_cairo_execute()
+        .lib_cairo(indoc! {r#"
+        #[executable]
+        fn main(a: felt252, b: felt252) -> felt252 {
+            b
+        }
+        "#})
+        .build(&t);
+
+    t.child("args.json").write_str("[1, 42]").unwrap();
+
+    Scarb::quick_snapbox()
+        .arg("execute")
+        .arg("--print-program-output")
+        .args(["--arguments-file", "args.json", "--arguments-format", "dec"])
+        .current_dir(&t)
+        .assert()
+        .success()
+        .stdout_matches(indoc! {r#"
+            [..]Compiling hello v0.1.0 ([..]/Scarb.toml)
+            [..]Finished `dev` profile target(s) in [..]
+            [..]Executing hello
+            Program output:
+            0
+            42
+            Saving output to: target/execute/hello/execution1
+        "#});
+}
+
+#[test]
+fn nested_arguments_file() {
+    let value = |v: u64| Arg::Value(Felt252::from(v));
+    let expected = vec![
+        value(1),
+        Arg::Array(vec![
+            value(2),
+            Arg::Array(vec![value(3), value(4)]),
+            value(5),
+        ]),
+        Arg::Array(vec![]),
+    ];
+    assert_eq!(
+        read_arguments_file("[1, [2, [3, 4], 5], []]", ArgumentsFormat::Dec).unwrap(),
+        format!("{expected:?}")
+    );
+    assert_eq!(
+        read_arguments_file(
+            r#"["0x1", ["0x2", ["0x3", "0x4"], "0x5"], []]"#,
+            ArgumentsFormat::Hex
+        )
+        .unwrap(),
+        format!("{expected:?}")
+    );
+}
+
+#[test]
+fn malformed_nested_arguments_file() {
+    let err = |content: &str, format| {
+        read_arguments_file(content, format)
+            .unwrap_err()
+            .to_string()
+    };
+    assert_eq!(
+        err(r#"[1, [2, {"a": 3}]]"#, ArgumentsFormat::Dec),
+        r#"argument at index 1.1 is not a decimal number: {"a":3}"#
+    );
+    assert_eq!(
+        err(r#"["0x1", [7]]"#, ArgumentsFormat::Hex),
+        "argument at index 1.0 is not a hex value: 7"
+    );
+    assert_eq!(
+        err("[[[[[[[[[[1]]]]]]]]]]", ArgumentsFormat::Dec),
+        "argument at index 0.0.0.0.0.0.0.0.0 exceeds maximum nesting depth of 8 arrays"
+    );
+}
+
+#[test]
+fn can_pass_nested_arrays() {
+    let t = TempDir::new().unwrap();
+
+    ProjectBuilder::start()
+        .name("hello")
+        .version("0.1.0")
+        .manifest_extra(indoc! {r#"
+            [executable]
+
+            [cairo]
+            enable-gas = false
+        "#})
+        .dep_cairo_execute()
+        .lib_cairo(indoc! {r#"
+        #[executable]
+        fn main(rows: Array<Array<felt252>>) -> felt252 {
+            let mut sum = 0;
+            for row in rows {
+                for value in row {
+                    sum += value;
+                }
+            };
+            sum
+        }
+        "#})
+        .build(&t);
+
+    t.child("args.json").write_str("[[[1, 2], [3]]]").unwrap();
+
+    Scarb::quick_snapbox()
+        .arg("execute")
+        .arg("--print-program-output")
+        .args(["--arguments-file", "args.json", "--arguments-format", "dec"])
+        .current_dir(&t)
+        .assert()
+        .success()
+        .stdout_matches(indoc! {r#"
+            [..]Compiling hello v0.1.0 ([..]/Scarb.toml)
+            [..]Finished `dev` profile target(s) in [..]
+            [..]Executing hello
+            Program output:
+            0
+            6
+            Saving output to: target/execute/hello/execution1
+        "#});
+}
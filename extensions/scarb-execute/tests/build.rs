@@ -1,12 +1,13 @@
 use assert_fs::assert::PathAssert;
 use assert_fs::fixture::PathChild;
 use assert_fs::TempDir;
-use indoc::indoc;
+use indoc::{formatdoc, indoc};
 use predicates::prelude::*;
 use scarb_test_support::command::Scarb;
 use scarb_test_support::fsx::ChildPathEx;
 use scarb_test_support::predicates::is_file_empty;
 use scarb_test_support::project_builder::ProjectBuilder;
+use scarb_test_support::workspace_builder::WorkspaceBuilder;
 use snapbox::cmd::OutputAssert;
 
 fn executable_project_builder() -> ProjectBuilder {
@@ -131,6 +132,63 @@ fn can_produce_cairo_pie_output() {
         .assert(predicates::path::exists());
 }
 
+#[test]
+fn can_produce_bootloader_task_output() {
+    let t = build_executable_project();
+    Scarb::quick_snapbox()
+        .arg("execute")
+        .arg("--target=bootloader")
+        .arg("--output=bootloader-task")
+        .current_dir(&t)
+        .assert()
+        .success()
+        .stdout_matches(indoc! {r#"
+        [..]Compiling hello v0.1.0 ([..]Scarb.toml)
+        [..]Finished `dev` profile target(s) in [..]
+        [..]Executing hello
+        Saving output to: target/execute/hello/execution1
+        "#});
+
+    let cairo_pie = t.child("target/execute/hello/execution1/cairo_pie.zip");
+    cairo_pie.assert(predicates::path::exists().and(is_file_empty().not()));
+    let task = t
+        .child("target/execute/hello/execution1/task.json")
+        .assert_is_json::<serde_json::Value>();
+    assert_eq!(task["type"], "CairoPiePath");
+    assert_eq!(task["path"], "cairo_pie.zip");
+    assert_eq!(task["use_poseidon"], true);
+
+    Scarb::quick_snapbox()
+        .arg("execute")
+        .arg("--no-build")
+        .arg("--target=bootloader")
+        .arg("--output=bootloader-task")
+        .arg("--task-program-hash=pedersen")
+        .current_dir(&t)
+        .assert()
+        .success();
+    let task = t
+        .child("target/execute/hello/execution2/task.json")
+        .assert_is_json::<serde_json::Value>();
+    assert_eq!(task["use_poseidon"], false);
+}
+
+#[test]
+fn bootloader_task_output_requires_bootloader_target() {
+    let t = build_executable_project();
+    output_assert(
+        Scarb::quick_snapbox()
+            .arg("execute")
+            .arg("--output=bootloader-task")
+            .current_dir(&t)
+            .assert()
+            .failure(),
+        indoc! {r#"
+        error: bootloader task output format is not supported for standalone execution target
+        "#},
+    );
+}
+
 #[test]
 fn fails_when_attr_missing() {
     let t = TempDir::new().unwrap();
@@ -193,13 +251,14 @@ fn can_print_panic_reason() {
             }
         "#})
         .build(&t);
-    Scarb::quick_snapbox()
-        .arg("execute")
-        .arg("--print-program-output")
-        .current_dir(&t)
-        .assert()
-        .success()
-        .stdout_matches(indoc! {r#"
+    output_assert_with_code(
+        Scarb::quick_snapbox()
+            .arg("execute")
+            .arg("--print-program-output")
+            .current_dir(&t)
+            .assert()
+            .failure(),
+        indoc! {r#"
         [..]Compiling hello v0.1.0 ([..]Scarb.toml)
         [..]Finished `dev` profile target(s) in [..]
         [..]Executing hello
@@ -207,7 +266,10 @@ fn can_print_panic_reason() {
         1
         Panicked with "abcd".
         Saving output to: target/execute/hello/execution1
-        "#});
+        error: program panicked
+        "#},
+        3,
+    );
     t.child("target/execute/hello/execution1/air_private_input.json")
         .assert_is_json::<serde_json::Value>();
     t.child("target/execute/hello/execution1/air_public_input.json")
@@ -218,11 +280,323 @@ fn can_print_panic_reason() {
         .assert(predicates::path::exists().and(is_file_empty().not()));
 }
 
+#[test]
+fn strict_builtins_fail_on_missing_builtin() {
+    let t = TempDir::new().unwrap();
+    executable_project_builder()
+        .lib_cairo(indoc! {r#"
+            use core::pedersen::pedersen;
+
+            #[executable]
+            fn main() -> felt252 {
+                pedersen(1, 2)
+            }
+        "#})
+        .build(&t);
+
+    output_assert_with_code(
+        Scarb::quick_snapbox()
+            .arg("execute")
+            .args(["--layout", "plain", "--strict-builtins"])
+            .current_dir(&t)
+            .assert()
+            .failure(),
+        indoc! {r#"
+        [..]Compiling hello v0.1.0 ([..]Scarb.toml)
+        [..]Finished `dev` profile target(s) in [..]
+        [..]Executing hello
+        error: Cairo program run failed: [..]not present in layout plain[..]
+        "#},
+        3,
+    );
+}
+
+fn budgeted_project(budget: &str) -> TempDir {
+    let t = TempDir::new().unwrap();
+    executable_project_builder()
+        .manifest_extra(formatdoc! {r#"
+            [executable]
+
+            [cairo]
+            enable-gas = false
+
+            [tool.budget]
+            {budget}
+        "#})
+        .build(&t);
+    t
+}
+
+#[test]
+fn execution_within_budget_passes() {
+    let t = budgeted_project("steps = 1000000");
+    Scarb::quick_snapbox()
+        .arg("execute")
+        .current_dir(&t)
+        .assert()
+        .success()
+        .stdout_matches(indoc! {r#"
+        [..]Compiling hello v0.1.0 ([..]Scarb.toml)
+        [..]Finished `dev` profile target(s) in [..]
+        [..]Executing hello
+        Saving output to: target/execute/hello/execution1
+        "#});
+}
+
+#[test]
+fn execution_over_budget_fails() {
+    let t = budgeted_project("steps = 1");
+    output_assert_with_code(
+        Scarb::quick_snapbox()
+            .arg("execute")
+            .current_dir(&t)
+            .assert()
+            .failure(),
+        indoc! {r#"
+        [..]Compiling hello v0.1.0 ([..]Scarb.toml)
+        [..]Finished `dev` profile target(s) in [..]
+        [..]Executing hello
+        error: package `hello` exceeds its budget of steps: used [..], limit is 1 (over by [..])
+        "#},
+        3,
+    );
+    t.child("target/execute/hello/execution1")
+        .assert(predicates::path::missing());
+}
+
+#[test]
+fn workspace_execution_checks_budgets_of_all_members() {
+    let t = TempDir::new().unwrap();
+    for (name, steps) in [("first", 1000000), ("second", 1)] {
+        executable_project_builder()
+            .name(name)
+            .manifest_extra(formatdoc! {r#"
+                [executable]
+
+                [cairo]
+                enable-gas = false
+
+                [tool.budget]
+                steps = {steps}
+            "#})
+            .build(&t.child(name));
+    }
+    WorkspaceBuilder::start()
+        .add_member("first")
+        .add_member("second")
+        .build(&t);
+
+    output_assert_with_code(
+        Scarb::quick_snapbox()
+            .args(["execute", "--workspace"])
+            .current_dir(&t)
+            .assert()
+            .failure(),
+        indoc! {r#"
+        [..]Compiling first v0.1.0 ([..]Scarb.toml)
+        [..]Finished `dev` profile target(s) in [..]
+        [..]Executing first
+        Saving output to: target/execute/first/execution1
+        [..]Compiling second v0.1.0 ([..]Scarb.toml)
+        [..]Finished `dev` profile target(s) in [..]
+        [..]Executing second
+        error: package `second` exceeds its budget of steps: used [..], limit is 1 (over by [..])
+        error: failed to execute packages: `second`
+        "#},
+        3,
+    );
+    t.child("target/execute/second/execution1")
+        .assert(predicates::path::missing());
+}
+
+#[test]
+fn can_decode_program_output() {
+    let t = build_executable_project();
+    for (decoding, expected) in [
+        ("debug", "Program output:\n0\n42"),
+        ("felts", "[0, 42]"),
+        ("json", r#"["0x0","0x2a"]"#),
+    ] {
+        Scarb::quick_snapbox()
+            .arg("execute")
+            .args(["--print-program-output", "--output-decoding", decoding])
+            .current_dir(&t)
+            .assert()
+            .success()
+            .stdout_matches(formatdoc! {r#"
+            ...
+            [..]Executing hello
+            {expected}
+            Saving output to: target/execute/hello/execution[..]
+            "#});
+    }
+    t.child("target/execute/hello/execution2/program_output.txt")
+        .assert("[0, 42]");
+    t.child("target/execute/hello/execution3/program_output.json")
+        .assert(r#"["0x0","0x2a"]"#);
+}
+
+#[test]
+fn decoded_program_output_includes_panic_reason() {
+    let t = TempDir::new().unwrap();
+    executable_project_builder()
+        .lib_cairo(indoc! {r#"
+            #[executable]
+            fn main() -> felt252 {
+                panic!("abcd");
+                42
+            }
+        "#})
+        .build(&t);
+    output_assert_with_code(
+        Scarb::quick_snapbox()
+            .arg("execute")
+            .args(["--print-program-output", "--output-decoding", "felts"])
+            .current_dir(&t)
+            .assert()
+            .failure(),
+        indoc! {r#"
+        [..]Compiling hello v0.1.0 ([..]Scarb.toml)
+        [..]Finished `dev` profile target(s) in [..]
+        [..]Executing hello
+        [1]
+        Panicked with "abcd".
+        Saving output to: target/execute/hello/execution1
+        error: program panicked
+        "#},
+        3,
+    );
+    Scarb::quick_snapbox()
+        .args(["--json", "execute", "--no-build"])
+        .args(["--print-program-output", "--output-decoding", "json"])
+        .current_dir(&t)
+        .assert()
+        .failure()
+        .stdout_matches(indoc! {r#"
+        ...
+        {"output":["0x1"],"panic_reason":"Panicked with \"abcd\"."}
+        ...
+        "#});
+}
+
+#[test]
+fn deterministic_ids_allocate_dict_segments_up_front() {
+    let t = TempDir::new().unwrap();
+    executable_project_builder()
+        .lib_cairo(indoc! {r#"
+            #[executable]
+            fn main() -> felt252 {
+                let mut dict: Felt252Dict<felt252> = Default::default();
+                dict.insert(1, 42);
+                dict.get(1)
+            }
+        "#})
+        .build(&t);
+    let execute = |deterministic_ids: bool| {
+        Scarb::quick_snapbox()
+            .arg("execute")
+            .args(deterministic_ids.then_some("--deterministic-ids"))
+            .current_dir(&t)
+            .assert()
+            .success();
+    };
+    execute(false);
+    execute(true);
+    execute(true);
+    let memory = |execution: &str| {
+        let path = t.child(format!("target/execute/hello/{execution}/memory.bin"));
+        path.assert(predicates::path::exists().and(is_file_empty().not()));
+        std::fs::read(path.path()).unwrap()
+    };
+
+    // Without the flag, the dictionary is moved to a segment allocated at the end of the run,
+    // so its relocated addresses differ from the ones of a segment allocated up front.
+    assert_ne!(memory("execution1"), memory("execution2"));
+    assert_eq!(memory("execution2"), memory("execution3"));
+}
+
+#[test]
+fn deterministic_ids_fail_for_cairo_pie_output() {
+    let t = build_executable_project();
+    output_assert(
+        Scarb::quick_snapbox()
+            .arg("execute")
+            .args(["--deterministic-ids", "--output", "cairo-pie"])
+            .args(["--target", "bootloader"])
+            .current_dir(&t)
+            .assert()
+            .failure(),
+        indoc! {r#"
+        error: deterministic ids are only supported for standard output format
+        "#},
+    );
+}
+
+fn looping_project() -> TempDir {
+    let t = TempDir::new().unwrap();
+    executable_project_builder()
+        .lib_cairo(indoc! {r#"
+            #[executable]
+            fn main() -> felt252 {
+                let mut i: felt252 = 0;
+                while i != 1000000 {
+                    i += 1;
+                };
+                i
+            }
+        "#})
+        .build(&t);
+    t
+}
+
+#[test]
+fn max_steps_aborts_long_running_program() {
+    let t = looping_project();
+    output_assert_with_code(
+        Scarb::quick_snapbox()
+            .arg("execute")
+            .args(["--max-steps", "1000"])
+            .current_dir(&t)
+            .assert()
+            .failure(),
+        indoc! {r#"
+        [..]Compiling hello v0.1.0 ([..]Scarb.toml)
+        [..]Finished `dev` profile target(s) in [..]
+        [..]Executing hello
+        error: step limit exceeded: program did not finish within 1000 steps
+        "#},
+        3,
+    );
+    t.child("target/execute/hello/execution1")
+        .assert(predicates::path::missing());
+}
+
+#[test]
+fn max_steps_does_not_affect_program_within_limit() {
+    let t = build_executable_project();
+    Scarb::quick_snapbox()
+        .arg("execute")
+        .args(["--max-steps", "1000"])
+        .current_dir(&t)
+        .assert()
+        .success()
+        .stdout_matches(indoc! {r#"
+        [..]Compiling hello v0.1.0 ([..]Scarb.toml)
+        [..]Finished `dev` profile target(s) in [..]
+        [..]Executing hello
+        Saving output to: target/execute/hello/execution1
+        "#});
+}
+
 fn output_assert(output: OutputAssert, expected: &str) {
+    output_assert_with_code(output, expected, 1)
+}
+
+fn output_assert_with_code(output: OutputAssert, expected: &str, code: i32) {
     #[cfg(windows)]
     output.stdout_matches(format!(
-        "{expected}error: process did not exit successfully: exit code: 1\n"
+        "{expected}error: process did not exit successfully: exit code: {code}\n"
     ));
     #[cfg(not(windows))]
-    output.stdout_matches(expected);
+    output.code(code).stdout_matches(expected);
 }
@@ -5,9 +5,10 @@ use create_output_dir::create_output_dir;
 use indoc::{formatdoc, indoc};
 use scarb_execute::args::ExecutionArgs;
 use scarb_metadata::MetadataCommand;
-use scarb_ui::args::{PackagesFilter, VerbositySpec};
+use scarb_ui::args::{ColorSpec, PackagesFilter, VerbositySpec};
 use scarb_ui::components::Status;
-use scarb_ui::{OutputFormat, Ui};
+use scarb_ui::{FailureExt, FailureKind, OutputFormat, Ui};
+use serde_json::Value;
 use std::env;
 use std::fs;
 use std::process::ExitCode;
@@ -52,9 +53,17 @@ struct Args {
     #[command(flatten)]
     prover: ProverArgs,
 
+    /// Directory to save the proof to, instead of the execution output directory.
+    #[arg(long)]
+    output_dir: Option<Utf8PathBuf>,
+
     /// Logging verbosity.
     #[command(flatten)]
     pub verbose: VerbositySpec,
+
+    /// Coloring of the output.
+    #[command(flatten)]
+    pub color: ColorSpec,
 }
 
 #[derive(Parser, Clone, Debug)]
@@ -71,12 +80,13 @@ struct ProverArgs {
 fn main() -> ExitCode {
     let args = Args::parse();
     let ui = Ui::new(args.verbose.clone().into(), OutputFormat::Text);
+    args.color.apply(&ui);
 
     match main_inner(args, ui.clone()) {
         Ok(()) => ExitCode::SUCCESS,
         Err(error) => {
             ui.error(format!("{error:#}"));
-            ExitCode::FAILURE
+            FailureKind::of(&error).exit_code()
         }
     }
 }
@@ -94,7 +104,10 @@ fn main_inner(args: Args, ui: Ui) -> Result<()> {
     let scarb_target_dir = Utf8PathBuf::from(env::var("SCARB_TARGET_DIR")?);
 
     let metadata = MetadataCommand::new().inherit_stderr().exec()?;
-    let package = args.packages_filter.match_one(&metadata)?;
+    let package = args
+        .packages_filter
+        .match_one(&metadata)
+        .failure_kind(FailureKind::Usage)?;
 
     let execution_id = match args.execution_id {
         Some(id) => id,
@@ -106,9 +119,26 @@ fn main_inner(args: Args, ui: Ui) -> Result<()> {
     ui.print(Status::new("Proving", &package.name));
     ui.warn("soundness of proof is not yet guaranteed by Stwo, use at your own risk");
 
-    let (pub_input_path, priv_input_path, proof_path) =
+    let (pub_input_path, priv_input_path, execution_dir) =
         resolve_paths_from_package(&scarb_target_dir, &package.name, execution_id)?;
 
+    let proof_path = match args.output_dir {
+        Some(output_dir) => {
+            fs::create_dir_all(&output_dir)
+                .with_context(|| format!("failed to create output directory: {output_dir}"))?;
+            output_dir.join("proof.json")
+        }
+        None => {
+            // Create proof directory under this execution folder
+            let proof_dir = execution_dir.join("proof");
+            create_output_dir(proof_dir.as_std_path())
+                .context("failed to create proof directory")?;
+            proof_dir.join("proof.json")
+        }
+    };
+
+    validate_vm_output(&pub_input_path, &priv_input_path)?;
+
     let prover_input = adapt_vm_output(
         pub_input_path.as_std_path(),
         priv_input_path.as_std_path(),
@@ -175,12 +205,42 @@ fn resolve_paths_from_package(
         format!("private input file does not exist at path: {priv_input_path}")
     );
 
-    // Create proof directory under this execution folder
-    let proof_dir = execution_dir.join("proof");
-    create_output_dir(proof_dir.as_std_path()).context("failed to create proof directory")?;
-    let proof_path = proof_dir.join("proof.json");
+    Ok((pub_input_path, priv_input_path, execution_dir))
+}
+
+/// Check that AIR inputs of the execution can be read by the VM output adapter.
+///
+/// The adapter panics or fails with opaque errors on malformed inputs, for example when
+/// the execution directory has been written only partially.
+fn validate_vm_output(pub_input_path: &Utf8Path, priv_input_path: &Utf8Path) -> Result<()> {
+    read_json_input(pub_input_path, "public")?;
+    let priv_input = read_json_input(priv_input_path, "private")?;
+
+    for (key, file) in [("trace_path", "trace"), ("memory_path", "memory")] {
+        let path = priv_input
+            .get(key)
+            .and_then(Value::as_str)
+            .map(Utf8Path::new)
+            .with_context(|| {
+                format!("private input does not reference {file} file: {priv_input_path}")
+            })?;
+        ensure!(
+            path.exists(),
+            formatdoc! {r#"
+                private input references missing {file} file: {path}
+                help: run `scarb execute` again to regenerate the execution output
+                "#}
+        );
+    }
+
+    Ok(())
+}
 
-    Ok((pub_input_path, priv_input_path, proof_path))
+fn read_json_input(path: &Utf8Path, kind: &str) -> Result<Value> {
+    let contents = fs::read_to_string(path)
+        .with_context(|| format!("failed to read {kind} input file: {path}"))?;
+    serde_json::from_str(&contents)
+        .with_context(|| format!("failed to parse {kind} input file: {path}"))
 }
 
 fn display_path(scarb_target_dir: &Utf8Path, output_path: &Utf8Path) -> String {
@@ -199,6 +199,47 @@ fn prove_with_execute() {
         .assert(predicates::path::exists());
 }
 
+#[test]
+#[cfg(not(windows))]
+fn prove_to_output_dir() {
+    let t = build_executable_project();
+
+    Scarb::quick_snapbox()
+        .arg("execute")
+        .current_dir(&t)
+        .assert()
+        .success();
+
+    Scarb::quick_snapbox()
+        .arg("prove")
+        .arg("--execution-id=1")
+        .arg("--output-dir=proofs/hello")
+        .current_dir(&t)
+        .assert()
+        .success()
+        .stdout_matches(indoc! {r#"
+        [..]Proving hello
+        warn: soundness of proof is not yet guaranteed by Stwo, use at your own risk
+        Saving proof to: proofs/hello/proof.json
+        "#});
+
+    t.child("proofs/hello/proof.json")
+        .assert(predicates::path::exists());
+    t.child("target/execute/hello/execution1/proof")
+        .assert(predicates::path::missing());
+
+    Scarb::quick_snapbox()
+        .arg("verify")
+        .arg("--proof-file=proofs/hello/proof.json")
+        .current_dir(&t)
+        .assert()
+        .success()
+        .stdout_matches(indoc! {r#"
+        [..]Verifying proof
+        [..]Verified proof successfully
+        "#});
+}
+
 #[test]
 #[cfg(windows)]
 fn prove_fails_on_windows() {
@@ -219,6 +260,66 @@ fn prove_fails_on_windows() {
     )
 }
 
+#[test]
+#[cfg(not(windows))]
+fn prove_fails_when_trace_file_missing() {
+    let t = build_executable_project();
+
+    Scarb::quick_snapbox()
+        .arg("execute")
+        .current_dir(&t)
+        .assert()
+        .success();
+
+    std::fs::remove_file(t.child("target/execute/hello/execution1/trace.bin")).unwrap();
+
+    output_assert(
+        Scarb::quick_snapbox()
+            .arg("prove")
+            .arg("--execution-id=1")
+            .current_dir(&t)
+            .assert()
+            .failure(),
+        indoc! {r#"
+        [..]Proving hello
+        warn: soundness of proof is not yet guaranteed by Stwo, use at your own risk
+        error: private input references missing trace file: [..]/target/execute/hello/execution1/trace.bin
+        help: run `scarb execute` again to regenerate the execution output
+
+        "#},
+    )
+}
+
+#[test]
+#[cfg(not(windows))]
+fn prove_fails_when_public_input_truncated() {
+    let t = build_executable_project();
+
+    Scarb::quick_snapbox()
+        .arg("execute")
+        .current_dir(&t)
+        .assert()
+        .success();
+
+    let pub_input = t.child("target/execute/hello/execution1/air_public_input.json");
+    let contents = std::fs::read_to_string(&pub_input).unwrap();
+    std::fs::write(&pub_input, &contents[..contents.len() / 2]).unwrap();
+
+    output_assert(
+        Scarb::quick_snapbox()
+            .arg("prove")
+            .arg("--execution-id=1")
+            .current_dir(&t)
+            .assert()
+            .failure(),
+        indoc! {r#"
+        [..]Proving hello
+        warn: soundness of proof is not yet guaranteed by Stwo, use at your own risk
+        error: failed to parse public input file: [..]/target/execute/hello/execution1/air_public_input.json: EOF while parsing [..]
+        "#},
+    )
+}
+
 fn output_assert(output: OutputAssert, expected: &str) {
     #[cfg(windows)]
     output.stdout_matches(format!(
@@ -3,9 +3,11 @@ use camino::{Utf8Path, Utf8PathBuf};
 use clap::Parser;
 use indoc::formatdoc;
 use scarb_metadata::{MetadataCommand, PackageMetadata};
-use scarb_ui::args::{PackagesFilter, VerbositySpec};
-use scarb_ui::components::Status;
-use scarb_ui::{OutputFormat, Ui};
+use scarb_ui::args::{ColorSpec, PackagesFilter, VerbositySpec};
+use scarb_ui::components::{JsonValue, Status};
+use scarb_ui::{FailureExt, FailureKind, Message, Ui};
+use serde::{Serialize, Serializer};
+use serde_json::Value;
 use std::env;
 use std::fs;
 use std::process::ExitCode;
@@ -33,48 +35,99 @@ struct Args {
     )]
     proof_file: Option<Utf8PathBuf>,
 
+    /// Print public input claimed by the proof before verifying it.
+    #[arg(long)]
+    print_public_input: bool,
+
     /// Logging verbosity.
     #[command(flatten)]
     pub verbose: VerbositySpec,
+
+    /// Coloring of the output.
+    #[command(flatten)]
+    pub color: ColorSpec,
 }
 
 fn main() -> ExitCode {
     let args = Args::parse();
-    let ui = Ui::new(args.verbose.clone().into(), OutputFormat::Text);
-
-    match main_inner(args, ui.clone()) {
+    let output_format =
+        scarb_ui::OutputFormat::from_env_var("SCARB_UI_OUTPUT_FORMAT").unwrap_or_default();
+    let ui = Ui::new(args.verbose.clone().into(), output_format);
+    args.color.apply(&ui);
+
+    let mut proof_path = None;
+    let result = main_inner(args, ui.clone(), &mut proof_path);
+    let exit_code = match &result {
         Ok(()) => ExitCode::SUCCESS,
         Err(error) => {
             ui.error(format!("{error:#}"));
-            ExitCode::FAILURE
+            FailureKind::of(error).exit_code()
         }
-    }
+    };
+    ui.print(VerificationResult {
+        verified: result.is_ok(),
+        proof_path,
+        error: result.err().map(|error| format!("{error:#}")),
+    });
+    exit_code
 }
 
-fn main_inner(args: Args, ui: Ui) -> Result<()> {
+/// Verify the proof, storing its path in `proof_path` as soon as it is known, so that it can be
+/// reported even if verification fails.
+fn main_inner(args: Args, ui: Ui, proof_path: &mut Option<Utf8PathBuf>) -> Result<()> {
     let scarb_target_dir = Utf8PathBuf::from(env::var("SCARB_TARGET_DIR")?);
 
     let metadata = MetadataCommand::new().inherit_stderr().exec()?;
-    let package = args.packages_filter.match_one(&metadata)?;
+    let package = args
+        .packages_filter
+        .match_one(&metadata)
+        .failure_kind(FailureKind::Usage)?;
 
-    let proof_path = if let Some(execution_id) = args.execution_id {
+    let proof_path = proof_path.insert(if let Some(execution_id) = args.execution_id {
         ui.print(Status::new("Verifying", &package.name));
         resolve_proof_path_from_package(&scarb_target_dir, &package, execution_id)?
     } else {
         ui.print(Status::new("Verifying", "proof"));
         args.proof_file.unwrap()
-    };
+    });
 
-    let proof = load_proof(&proof_path)?;
+    let proof = load_proof(proof_path)?;
 
-    verify_cairo::<Blake2sMerkleChannel>(proof).with_context(|| "failed to verify proof")?;
+    if args.print_public_input {
+        let public_input = proof
+            .pointer("/claim/public_data")
+            .cloned()
+            .with_context(|| format!("proof does not contain public input: {proof_path}"))?;
+        ui.print(JsonValue::new(public_input));
+    }
+
+    let proof: CairoProof<Blake2sMerkleHasher> = serde_json::from_value(proof)
+        .with_context(|| format!("failed to deserialize proof file: {proof_path}"))?;
+
+    verify_cairo::<Blake2sMerkleChannel>(proof)
+        .with_context(|| "failed to verify proof")
+        .failure_kind(FailureKind::Verification)?;
 
     ui.print(Status::new("Verified", "proof successfully"));
 
     Ok(())
 }
 
-fn load_proof(path: &Utf8Path) -> Result<CairoProof<Blake2sMerkleHasher>> {
+/// Outcome of the verification, printed in JSON output mode only.
+#[derive(Serialize)]
+struct VerificationResult {
+    verified: bool,
+    proof_path: Option<Utf8PathBuf>,
+    error: Option<String>,
+}
+
+impl Message for VerificationResult {
+    fn structured<S: Serializer>(self, ser: S) -> Result<S::Ok, S::Error> {
+        self.serialize(ser)
+    }
+}
+
+fn load_proof(path: &Utf8Path) -> Result<Value> {
     ensure!(
         path.exists(),
         format!("proof file does not exist at path: {path}")
@@ -83,7 +136,7 @@ fn load_proof(path: &Utf8Path) -> Result<CairoProof<Blake2sMerkleHasher>> {
     let proof_contents =
         fs::read_to_string(path).with_context(|| format!("failed to read proof file: {path}"))?;
     let proof = serde_json::from_str(&proof_contents)
-        .with_context(|| format!("failed to deserialize proof file: {path}"))?;
+        .with_context(|| format!("failed to parse proof file: {path}"))?;
     Ok(proof)
 }
 
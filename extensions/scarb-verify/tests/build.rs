@@ -1,7 +1,11 @@
+use std::fs;
+
+use assert_fs::prelude::*;
 use assert_fs::TempDir;
 use indoc::indoc;
 use scarb_test_support::command::Scarb;
 use scarb_test_support::project_builder::ProjectBuilder;
+use serde_json::Value;
 use snapbox::cmd::OutputAssert;
 
 fn build_executable_project() -> TempDir {
@@ -88,6 +92,58 @@ fn verify_from_path() {
         "#});
 }
 
+// Disabled due to `scarb prove` not being supported on Windows
+#[cfg(not(windows))]
+#[test]
+fn verify_prints_public_input() {
+    let t = build_executable_project();
+
+    Scarb::quick_snapbox()
+        .arg("execute")
+        .current_dir(&t)
+        .assert()
+        .success();
+
+    Scarb::quick_snapbox()
+        .arg("prove")
+        .arg("--execution-id=1")
+        .current_dir(&t)
+        .assert()
+        .success();
+
+    let output = Scarb::quick_snapbox()
+        .arg("verify")
+        .arg("--execution-id=1")
+        .arg("--print-public-input")
+        .current_dir(&t)
+        .assert()
+        .success();
+    let stdout = String::from_utf8(output.get_output().stdout.clone()).unwrap();
+    let start = stdout.find('{').unwrap();
+    let end = stdout.rfind('}').unwrap();
+    let public_input: Value = serde_json::from_str(&stdout[start..=end]).unwrap();
+
+    let execution_dir = t.child("target/execute/hello/execution1");
+    let proof: Value =
+        serde_json::from_str(&fs::read_to_string(execution_dir.child("proof/proof.json")).unwrap())
+            .unwrap();
+    assert_eq!(public_input, proof["claim"]["public_data"]);
+
+    let air_public_input: Value = serde_json::from_str(
+        &fs::read_to_string(execution_dir.child("air_public_input.json")).unwrap(),
+    )
+    .unwrap();
+    let segments = &air_public_input["memory_segments"];
+    assert_eq!(
+        public_input["initial_state"]["pc"],
+        segments["program"]["begin_addr"]
+    );
+    assert_eq!(
+        public_input["final_state"]["ap"],
+        segments["execution"]["stop_ptr"]
+    );
+}
+
 #[test]
 fn verify_fails_when_execution_output_not_found() {
     let t = build_executable_project();
@@ -127,6 +183,173 @@ fn verify_fails_when_proof_file_not_found() {
     )
 }
 
+// Disabled due to `scarb prove` not being supported on Windows
+#[cfg(not(windows))]
+#[test]
+fn verify_fails_with_verification_code_for_tampered_proof() {
+    let t = build_executable_project();
+
+    Scarb::quick_snapbox()
+        .arg("execute")
+        .current_dir(&t)
+        .assert()
+        .success();
+
+    Scarb::quick_snapbox()
+        .arg("prove")
+        .arg("--execution-id=1")
+        .current_dir(&t)
+        .assert()
+        .success();
+
+    tamper_proof(&t);
+
+    Scarb::quick_snapbox()
+        .arg("verify")
+        .arg("--execution-id=1")
+        .current_dir(&t)
+        .assert()
+        .code(4)
+        .stdout_matches(indoc! {r#"
+        [..]Verifying hello
+        error: failed to verify proof: [..]
+        "#});
+}
+
+// Scarb does not forward exit codes of extensions on Windows.
+#[cfg(not(windows))]
+#[test]
+fn verify_fails_with_usage_code_for_unknown_package() {
+    let t = build_executable_project();
+
+    Scarb::quick_snapbox()
+        .arg("verify")
+        .arg("--package=world")
+        .arg("--execution-id=1")
+        .current_dir(&t)
+        .assert()
+        .code(2);
+}
+
+// Disabled due to `scarb prove` not being supported on Windows
+#[cfg(not(windows))]
+#[test]
+fn verify_prints_json_result() {
+    let t = build_executable_project();
+
+    Scarb::quick_snapbox()
+        .arg("execute")
+        .current_dir(&t)
+        .assert()
+        .success();
+
+    Scarb::quick_snapbox()
+        .arg("prove")
+        .arg("--execution-id=1")
+        .current_dir(&t)
+        .assert()
+        .success();
+
+    let output = Scarb::quick_snapbox()
+        .arg("--json")
+        .arg("verify")
+        .arg("--proof-file=target/execute/hello/execution1/proof/proof.json")
+        .current_dir(&t)
+        .assert()
+        .success();
+    assert_eq!(
+        last_json_line(&output),
+        serde_json::json!({
+            "verified": true,
+            "proof_path": "target/execute/hello/execution1/proof/proof.json",
+            "error": null,
+        })
+    );
+}
+
+// Disabled due to `scarb prove` not being supported on Windows
+#[cfg(not(windows))]
+#[test]
+fn verify_prints_json_result_on_failure() {
+    let t = build_executable_project();
+
+    Scarb::quick_snapbox()
+        .arg("execute")
+        .current_dir(&t)
+        .assert()
+        .success();
+
+    Scarb::quick_snapbox()
+        .arg("prove")
+        .arg("--execution-id=1")
+        .current_dir(&t)
+        .assert()
+        .success();
+
+    tamper_proof(&t);
+
+    let output = Scarb::quick_snapbox()
+        .arg("--json")
+        .arg("verify")
+        .arg("--proof-file=target/execute/hello/execution1/proof/proof.json")
+        .current_dir(&t)
+        .assert()
+        .code(4);
+    let result = last_json_line(&output);
+    assert_eq!(result["verified"], false);
+    assert_eq!(
+        result["proof_path"],
+        "target/execute/hello/execution1/proof/proof.json"
+    );
+    assert!(result["error"]
+        .as_str()
+        .unwrap()
+        .starts_with("failed to verify proof: "));
+}
+
+#[test]
+fn verify_prints_json_result_when_proof_file_not_found() {
+    let t = build_executable_project();
+
+    let output = Scarb::quick_snapbox()
+        .arg("--json")
+        .arg("verify")
+        .arg("--proof-file=nonexistent.json")
+        .current_dir(&t)
+        .assert()
+        .failure();
+    assert_eq!(
+        last_json_line(&output),
+        serde_json::json!({
+            "verified": false,
+            "proof_path": "nonexistent.json",
+            "error": "proof file does not exist at path: nonexistent.json",
+        })
+    );
+}
+
+/// Change the claim of the first proof, so that it no longer matches the proof itself.
+#[cfg(not(windows))]
+fn tamper_proof(t: &TempDir) {
+    let proof_file = t.child("target/execute/hello/execution1/proof/proof.json");
+    let mut proof: Value = serde_json::from_str(&fs::read_to_string(&proof_file).unwrap()).unwrap();
+    let ap = &mut proof["claim"]["public_data"]["final_state"]["ap"];
+    *ap = Value::from(ap.as_u64().unwrap() + 1);
+    proof_file
+        .write_str(&serde_json::to_string(&proof).unwrap())
+        .unwrap();
+}
+
+fn last_json_line(output: &OutputAssert) -> Value {
+    let stdout = String::from_utf8(output.get_output().stdout.clone()).unwrap();
+    let line = stdout
+        .lines()
+        .rev()
+        .find(|line| line.starts_with('{'))
+        .unwrap();
+    serde_json::from_str(line).unwrap()
+}
+
 fn output_assert(output: OutputAssert, expected: &str) {
     #[cfg(windows)]
     output.stdout_matches(format!(
@@ -29,6 +29,26 @@ mod types;
 
 pub use types::*;
 
+/// Create a [`TokenStream`] consisting of a single Cairo identifier, built from format arguments.
+///
+/// Expands to [`TokenStream::ident`] called with the formatted string, so it fails with an error
+/// [`Diagnostic`] if the result is not a valid Cairo identifier.
+///
+/// ```
+/// use cairo_lang_macro::format_ident;
+///
+/// let name = "some";
+/// let wrapper_name = format_ident!("{name}_wrapper").unwrap();
+/// assert_eq!(wrapper_name.to_string(), "some_wrapper");
+/// assert!(format_ident!("{name}::wrapper").is_err());
+/// ```
+#[macro_export]
+macro_rules! format_ident {
+    ($($arg:tt)*) => {
+        $crate::TokenStream::ident(::std::format!($($arg)*))
+    };
+}
+
 #[doc(hidden)]
 #[derive(Clone)]
 pub struct ExpansionDefinition {
@@ -59,6 +59,24 @@ impl TokenStream {
         self
     }
 
+    /// Create a [`TokenStream`] consisting of a single Cairo identifier.
+    ///
+    /// Fails with an error [`Diagnostic`] if `name` is not a valid identifier, i.e. it is empty,
+    /// starts with a digit, or contains characters other than ASCII letters, digits and `_`.
+    /// See also the [`format_ident!`][crate::format_ident] macro.
+    pub fn ident(name: impl ToString) -> Result<Self, Diagnostic> {
+        let name = name.to_string();
+        let mut chars = name.chars();
+        let is_valid = matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+            && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if !is_valid {
+            return Err(Diagnostic::error(format!(
+                "`{name}` is not a valid Cairo identifier"
+            )));
+        }
+        Ok(Self::new(name))
+    }
+
     /// Get `[TokenStreamMetadata`] associated with this [`TokenStream`].
     ///
     /// The metadata struct can be used to describe the [`TokenStream`] origin.
@@ -69,6 +87,94 @@ impl TokenStream {
     pub fn is_empty(&self) -> bool {
         self.to_string().is_empty()
     }
+
+    /// Create a copy of this [`TokenStream`] in a canonical form, with its tokens separated by
+    /// single spaces.
+    ///
+    /// Whitespace between tokens is insignificant, so for example both `f()->felt252` and
+    /// `f ( ) -> felt252` are normalized to `f ( ) -> felt252`. String literals and line comments
+    /// are kept verbatim, and line comments are followed by a newline. The metadata is preserved.
+    pub fn normalized(&self) -> Self {
+        Self {
+            value: join_tokens(&tokenize(&self.value)),
+            metadata: self.metadata.clone(),
+        }
+    }
+
+    /// Check whether two [`TokenStream`]s consist of the same tokens, ignoring insignificant
+    /// whitespace and metadata.
+    ///
+    /// This is useful for asserting on macro output, which may contain whitespace inserted
+    /// by code generation.
+    pub fn tokens_equal(&self, other: &TokenStream) -> bool {
+        tokenize(&self.value) == tokenize(&other.value)
+    }
+}
+
+/// Punctuation made of multiple characters, longest first.
+const MULTI_CHAR_PUNCTUATION: &[&str] = &[
+    "..=", "::", "->", "=>", "==", "!=", "<=", ">=", "&&", "||", "+=", "-=", "*=", "/=", "%=", "..",
+];
+
+/// Split Cairo source code into tokens, without validating them.
+///
+/// Identifiers and number literals are runs of alphanumeric characters and `_`. String literals,
+/// delimited by `"` or `'`, and line comments are single tokens. Other characters are
+/// punctuation, which is a single character unless it forms one of [`MULTI_CHAR_PUNCTUATION`].
+fn tokenize(code: &str) -> Vec<&str> {
+    let is_word = |c: char| c.is_alphanumeric() || c == '_';
+    let mut tokens = Vec::new();
+    let mut rest = code.trim_start();
+    while let Some(c) = rest.chars().next() {
+        let len = if is_word(c) {
+            rest.find(|c| !is_word(c)).unwrap_or(rest.len())
+        } else if c == '"' || c == '\'' {
+            string_literal_len(rest, c)
+        } else if rest.starts_with("//") {
+            rest.find('\n').unwrap_or(rest.len())
+        } else {
+            MULTI_CHAR_PUNCTUATION
+                .iter()
+                .find(|punctuation| rest.starts_with(*punctuation))
+                .map_or(c.len_utf8(), |punctuation| punctuation.len())
+        };
+        let (token, tail) = rest.split_at(len);
+        tokens.push(token.trim_end());
+        rest = tail.trim_start();
+    }
+    tokens
+}
+
+/// Join tokens with single spaces, or newlines after line comments.
+fn join_tokens(tokens: &[&str]) -> String {
+    let mut code = String::new();
+    for (index, token) in tokens.iter().enumerate() {
+        if index > 0 {
+            let separator = if tokens[index - 1].starts_with("//") {
+                '\n'
+            } else {
+                ' '
+            };
+            code.push(separator);
+        }
+        code.push_str(token);
+    }
+    code
+}
+
+/// Length of the string literal at the start of `code`, delimited by `quote`, including both
+/// quotes, or the whole `code` if the literal is not terminated.
+fn string_literal_len(code: &str, quote: char) -> usize {
+    let mut escaped = false;
+    for (index, c) in code.char_indices().skip(1) {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            _ if c == quote => return index + c.len_utf8(),
+            _ => {}
+        }
+    }
+    code.len()
 }
 
 impl Display for TokenStream {
@@ -85,6 +191,16 @@ impl TokenStreamMetadata {
             file_id: Some(file_id.to_string()),
         }
     }
+
+    /// The path to the file from which the [`TokenStream`] has been created, if known.
+    pub fn original_file_path(&self) -> Option<&str> {
+        self.original_file_path.as_deref()
+    }
+
+    /// ID of the file from which the [`TokenStream`] has been created, if known.
+    pub fn file_id(&self) -> Option<&str> {
+        self.file_id.as_deref()
+    }
 }
 
 /// **Auxiliary data** returned by procedural macro code generation.
@@ -296,6 +412,16 @@ impl ProcMacroResult {
         self.diagnostics.extend(diagnostics);
         self
     }
+
+    /// Get the [`TokenStream`] produced by the procedural macro.
+    pub fn token_stream(&self) -> &TokenStream {
+        &self.token_stream
+    }
+
+    /// Get diagnostics emitted by the procedural macro.
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
 }
 
 /// Input for the post-process callback.
@@ -320,7 +446,9 @@ pub struct FullPathMarker {
 
 #[cfg(test)]
 mod tests {
-    use crate::types::TokenStream;
+    use crate::types::{
+        Diagnostic, Diagnostics, ProcMacroResult, TokenStream, TokenStreamMetadata,
+    };
 
     #[test]
     fn new_token_stream_metadata_empty() {
@@ -328,4 +456,96 @@ mod tests {
         assert!(token_stream.metadata.file_id.is_none());
         assert!(token_stream.metadata.original_file_path.is_none());
     }
+
+    #[test]
+    fn token_stream_metadata_accessors() {
+        let token_stream = TokenStream::new("fn f() -> felt252 { 12 }".to_string())
+            .with_metadata(TokenStreamMetadata::new("src/lib.cairo", "some_id"));
+        let metadata = token_stream.metadata();
+        assert_eq!(metadata.original_file_path(), Some("src/lib.cairo"));
+        assert_eq!(metadata.file_id(), Some("some_id"));
+
+        let metadata = TokenStream::empty().metadata().clone();
+        assert_eq!(metadata.original_file_path(), None);
+        assert_eq!(metadata.file_id(), None);
+    }
+
+    #[test]
+    fn proc_macro_result_accessors() {
+        let result = ProcMacroResult::new(TokenStream::new("fn f() -> felt252 { 12 }".to_string()))
+            .with_diagnostics(Diagnostics::new(vec![Diagnostic::warn("some warning")]))
+            .with_diagnostics(Diagnostic::error("some error").into());
+        assert_eq!(
+            result.token_stream().to_string(),
+            "fn f() -> felt252 { 12 }"
+        );
+        assert_eq!(
+            result.diagnostics(),
+            &[
+                Diagnostic::warn("some warning"),
+                Diagnostic::error("some error")
+            ]
+        );
+    }
+
+    #[test]
+    fn ident_from_parts() {
+        let name = "some";
+        let ident = crate::format_ident!("{name}_wrapper").unwrap();
+        assert_eq!(ident.to_string(), "some_wrapper");
+        assert_eq!(
+            crate::format_ident!("_{}{}", name, 2).unwrap().to_string(),
+            "_some2"
+        );
+    }
+
+    #[test]
+    fn invalid_ident() {
+        for name in ["", "2wrapper", "some wrapper", "some::wrapper", "zażółć"] {
+            assert_eq!(
+                TokenStream::ident(name).unwrap_err(),
+                Diagnostic::error(format!("`{name}` is not a valid Cairo identifier"))
+            );
+        }
+    }
+
+    #[test]
+    fn normalized_token_stream() {
+        let token_stream = TokenStream::new("\n  fn  f()\t->felt252 {\n    12\n}\n ".to_string())
+            .with_metadata(TokenStreamMetadata::new("lib.cairo", "some_id"));
+        let normalized = token_stream.normalized();
+        assert_eq!(normalized.to_string(), "fn f ( ) -> felt252 { 12 }");
+        assert_eq!(normalized.metadata(), token_stream.metadata());
+    }
+
+    #[test]
+    fn normalized_token_stream_keeps_literals_and_comments() {
+        let token_stream = TokenStream::new(
+            "let  a = \"x  \\\"y\";  // some   comment\nlet b='p  q';".to_string(),
+        );
+        assert_eq!(
+            token_stream.normalized().to_string(),
+            "let a = \"x  \\\"y\" ; // some   comment\nlet b = 'p  q' ;"
+        );
+    }
+
+    #[test]
+    fn tokens_equal_ignores_whitespace() {
+        let left = TokenStream::new("fn f() -> felt252 { 12 }".to_string());
+        let right = TokenStream::new("\nfn f()->felt252 {\n    12\n}\n".to_string())
+            .with_metadata(TokenStreamMetadata::new("lib.cairo", "some_id"));
+        assert!(left.tokens_equal(&right));
+        assert!(right.tokens_equal(&left));
+        assert_ne!(left, right);
+
+        let other = TokenStream::new("fn g() -> felt252 { 12 }".to_string());
+        assert!(!left.tokens_equal(&other));
+
+        let split_arrow = TokenStream::new("fn f() - > felt252 { 12 }".to_string());
+        assert!(!left.tokens_equal(&split_arrow));
+
+        let literal = TokenStream::new("\"a b\"".to_string());
+        let spaced_literal = TokenStream::new("\"a  b\"".to_string());
+        assert!(!literal.tokens_equal(&spaced_literal));
+    }
 }
@@ -123,6 +123,11 @@ impl InternalScarbCommandBuilder {
         self
     }
 
+    /// Whether standard error of `scarb` is shown in this process's standard error.
+    pub fn inherits_stderr(&self) -> bool {
+        self.inherit_stderr
+    }
+
     /// Build executable `scarb` command.
     pub fn command(&self) -> Command {
         let scarb = self
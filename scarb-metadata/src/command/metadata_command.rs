@@ -1,8 +1,12 @@
 use std::ffi::OsStr;
 use std::io;
+use std::io::Read;
 use std::ops::RangeInclusive;
 use std::path::PathBuf;
-use std::process::Command;
+use std::process::{Child, Command, Output, Stdio};
+use std::thread;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
 
 use thiserror::Error;
 
@@ -28,6 +32,17 @@ pub enum MetadataCommandError {
     #[error("failed to deserialize `scarb metadata` output")]
     Json(#[from] serde_json::Error),
 
+    /// Failed to deserialize `scarb metadata` output, with location of the offending output.
+    #[error("failed to deserialize `scarb metadata` output: {0}")]
+    Parse(#[from] MetadataParseError),
+
+    /// `scarb metadata` did not finish within the time set with [`MetadataCommand::timeout`].
+    #[error("`scarb metadata` did not finish within {timeout:?}")]
+    Timeout {
+        /// The exceeded time limit.
+        timeout: Duration,
+    },
+
     /// Error during execution of `scarb metadata`.
     #[error("`scarb metadata` exited with error\n\nstdout:\n{stdout}\nstderr:\n{stderr}")]
     ScarbError {
@@ -45,6 +60,55 @@ impl MetadataCommandError {
     }
 }
 
+/// Failed to deserialize JSON printed by `scarb metadata`, pointing at the offending output.
+#[derive(Error, Debug)]
+#[error("invalid JSON at byte {offset}, near `{excerpt}`")]
+pub struct MetadataParseError {
+    /// Byte offset in standard output of `scarb metadata` at which deserialization failed.
+    pub offset: usize,
+    /// Fragment of the output line surrounding [`MetadataParseError::offset`].
+    pub excerpt: String,
+    /// The underlying deserialization error.
+    #[source]
+    pub source: serde_json::Error,
+}
+
+impl MetadataParseError {
+    /// Maximum number of bytes included in the excerpt on each side of the error location.
+    const EXCERPT_RADIUS: usize = 32;
+
+    /// Locate `source` in `stdout`, knowing that the deserialized JSON starts at line `first_line`.
+    fn new(source: serde_json::Error, stdout: &str, first_line: usize) -> Self {
+        let line_index = first_line + source.line().saturating_sub(1);
+        let line_start = stdout
+            .split('\n')
+            .take(line_index)
+            .map(|line| line.len() + 1)
+            .sum::<usize>();
+        let line = stdout
+            .split('\n')
+            .nth(line_index)
+            .unwrap_or_default()
+            .trim_end();
+        let column = source.column().saturating_sub(1).min(line.len());
+
+        let mut start = column.saturating_sub(Self::EXCERPT_RADIUS);
+        while !line.is_char_boundary(start) {
+            start -= 1;
+        }
+        let mut end = (column + Self::EXCERPT_RADIUS).min(line.len());
+        while !line.is_char_boundary(end) {
+            end += 1;
+        }
+
+        Self {
+            offset: (line_start + column).min(stdout.len()),
+            excerpt: line[start..end].to_string(),
+            source,
+        }
+    }
+}
+
 /// A builder for `scarb metadata` command invocation.
 ///
 /// In detail, this will always execute `scarb --json metadata --format-version N`, where `N`
@@ -53,8 +117,12 @@ impl MetadataCommandError {
 pub struct MetadataCommand {
     inner: InternalScarbCommandBuilder,
     no_deps: bool,
+    unit: Option<String>,
+    include_manifest_source: bool,
+    include_target_defaults: bool,
     inherit_stdout: bool,
     json: bool,
+    timeout: Option<Duration>,
 }
 
 impl MetadataCommand {
@@ -82,6 +150,11 @@ impl MetadataCommand {
     }
 
     /// Current directory of the `scarb metadata` process.
+    ///
+    /// If not set, the process inherits the current directory of the caller.
+    /// The workspace is looked for starting from this directory, unless
+    /// [`MetadataCommand::manifest_path`] is set, in which case a relative manifest path is
+    /// resolved against this directory.
     pub fn current_dir(&mut self, path: impl Into<PathBuf>) -> &mut Self {
         self.inner.current_dir(path);
         self
@@ -93,6 +166,31 @@ impl MetadataCommand {
         self
     }
 
+    /// Output information only about the compilation unit with the given ID,
+    /// and packages it consists of.
+    pub fn unit(&mut self, id: impl Into<String>) -> &mut Self {
+        self.unit = Some(id.into());
+        self
+    }
+
+    /// Include raw contents of the manifest file of each package, in
+    /// [`PackageMetadata::manifest_source`][crate::PackageMetadata::manifest_source].
+    pub fn include_manifest_source(&mut self, include: bool) -> &mut Self {
+        self.include_manifest_source = include;
+        self
+    }
+
+    /// Include default values of target parameters in
+    /// [`TargetMetadata::params`][crate::TargetMetadata::params].
+    ///
+    /// The defaults are taken from compilers built into the invoked Scarb, so they reflect its
+    /// version and may change between Scarb versions. Targets of kinds unknown to Scarb are
+    /// reported without defaults.
+    pub fn include_target_defaults(&mut self, include: bool) -> &mut Self {
+        self.include_target_defaults = include;
+        self
+    }
+
     /// Defines profile to use for `scarb metadata` command.
     pub fn profile(&mut self, profile: impl AsRef<OsStr>) -> &mut Self {
         self.env("SCARB_PROFILE", profile)
@@ -157,6 +255,19 @@ impl MetadataCommand {
         self
     }
 
+    /// Kill `scarb metadata` if it does not finish within the given time, and fail with
+    /// [`MetadataCommandError::Timeout`].
+    ///
+    /// On Unix, the process is started in a new process group, which is killed as a whole,
+    /// so that no processes spawned by Scarb outlive it.
+    /// On Windows, the whole process tree is killed.
+    ///
+    /// If not set, the command runs for as long as it takes.
+    pub fn timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
     fn scarb_command(&self) -> Command {
         let mut builder = self.inner.clone();
         if self.json {
@@ -167,6 +278,16 @@ impl MetadataCommand {
         if self.no_deps {
             builder.arg("--no-deps");
         }
+        if let Some(unit) = &self.unit {
+            builder.arg("--unit");
+            builder.arg(unit);
+        }
+        if self.include_manifest_source {
+            builder.arg("--include-manifest-source");
+        }
+        if self.include_target_defaults {
+            builder.arg("--include-target-defaults");
+        }
         builder.command()
     }
 
@@ -174,7 +295,10 @@ impl MetadataCommand {
     pub fn exec(&self) -> Result<Metadata, MetadataCommandError> {
         let mut cmd = self.scarb_command();
 
-        let output = cmd.output()?;
+        let output = match self.timeout {
+            Some(timeout) => output_with_timeout(cmd, self.inner.inherits_stderr(), timeout)?,
+            None => cmd.output()?,
+        };
 
         let stdout_string = String::from_utf8_lossy(&output.stdout).to_string();
 
@@ -215,6 +339,85 @@ impl MetadataCommand {
     }
 }
 
+/// Run the command like [`Command::output`], killing it if it does not finish within `timeout`.
+fn output_with_timeout(
+    mut cmd: Command,
+    inherit_stderr: bool,
+    timeout: Duration,
+) -> Result<Output, MetadataCommandError> {
+    const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+    cmd.stdout(Stdio::piped());
+    if !inherit_stderr {
+        cmd.stderr(Stdio::piped());
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        cmd.process_group(0);
+    }
+
+    let mut child = cmd.spawn()?;
+    // Pipes are drained in background, so that the child does not block on full pipe buffers.
+    let stdout = child.stdout.take().map(read_to_end_in_background);
+    let stderr = child.stderr.take().map(read_to_end_in_background);
+
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        let now = Instant::now();
+        if now >= deadline {
+            kill_process_tree(&mut child);
+            return Err(MetadataCommandError::Timeout { timeout });
+        }
+        thread::sleep(POLL_INTERVAL.min(deadline - now));
+    };
+
+    let join = |reader: Option<JoinHandle<io::Result<Vec<u8>>>>| -> io::Result<Vec<u8>> {
+        reader
+            .map(|reader| reader.join().expect("pipe reader thread has panicked"))
+            .unwrap_or(Ok(Vec::new()))
+    };
+    Ok(Output {
+        status,
+        stdout: join(stdout)?,
+        stderr: join(stderr)?,
+    })
+}
+
+fn read_to_end_in_background(
+    mut pipe: impl Read + Send + 'static,
+) -> JoinHandle<io::Result<Vec<u8>>> {
+    thread::spawn(move || {
+        let mut buf = Vec::new();
+        pipe.read_to_end(&mut buf)?;
+        Ok(buf)
+    })
+}
+
+fn kill_process_tree(child: &mut Child) {
+    #[cfg(unix)]
+    {
+        // The child leads its own process group, so its ID is also the ID of the group.
+        // SAFETY: `kill` has no memory safety preconditions.
+        unsafe {
+            libc::kill(-(child.id() as libc::pid_t), libc::SIGKILL);
+        }
+    }
+    #[cfg(windows)]
+    {
+        let _ = Command::new("taskkill")
+            .args(["/F", "/T", "/PID", &child.id().to_string()])
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status();
+    }
+    let _ = child.kill();
+    let _ = child.wait();
+}
+
 #[derive(Debug)]
 struct ParseResult {
     metadata: Metadata,
@@ -248,7 +451,7 @@ fn parse_stream(stdout: String) -> Result<ParseResult, MetadataCommandError> {
     {
         match serde_json::from_str(line) {
             Ok(metadata) => return Ok(ParseResult::new(metadata, n..=n)),
-            Err(serde_err) => err = Some(serde_err.into()),
+            Err(serde_err) => err = Some(MetadataParseError::new(serde_err, &stdout, n).into()),
         }
     }
     // multiline json's
@@ -265,15 +468,19 @@ fn parse_stream(stdout: String) -> Result<ParseResult, MetadataCommandError> {
             (Some((first, _)), Some((last, _))) => *first - 1..=*last + 1,
             _ => break,
         };
+        // Lines are joined with newlines, so that error locations match lines of the output.
         let json_string = json_lines
             .into_iter()
             .map(|(_, line)| line)
             .collect::<Vec<_>>()
-            .join("");
+            .join("\n");
 
-        match serde_json::from_str(&format!("{OPEN_BRACKET}{json_string}{CLOSE_BRACKET}")) {
+        match serde_json::from_str(&format!("{OPEN_BRACKET}\n{json_string}\n{CLOSE_BRACKET}")) {
             Ok(metadata) => return Ok(ParseResult::new(metadata, used_lines)),
-            Err(serde_err) => err = Some(serde_err.into()),
+            Err(serde_err) => {
+                let first_line = *used_lines.start();
+                err = Some(MetadataParseError::new(serde_err, &stdout, first_line).into())
+            }
         }
     }
 
@@ -343,19 +550,19 @@ mod tests {
 
     #[test]
     fn parse_stream_garbage_message() {
-        check_parse_stream!("{\"foo\":1}", Err(MetadataCommandError::Json(_)));
+        check_parse_stream!("{\"foo\":1}", Err(MetadataCommandError::Parse(_)));
     }
 
     #[test]
     fn parse_stream_garbage_message_nl() {
-        check_parse_stream!("{\"foo\":1}\n", Err(MetadataCommandError::Json(_)));
+        check_parse_stream!("{\"foo\":1}\n", Err(MetadataCommandError::Parse(_)));
     }
 
     #[test]
     fn parse_stream_garbage_messages() {
         check_parse_stream!(
             "{\"foo\":1}\n{\"bar\":1}",
-            Err(MetadataCommandError::Json(_))
+            Err(MetadataCommandError::Parse(_))
         );
     }
 
@@ -363,7 +570,7 @@ mod tests {
     fn parse_stream_not_serializable() {
         check_parse_stream!(
             "{\"version\":\"x\",\"foo\":1}",
-            Err(MetadataCommandError::Json(_))
+            Err(MetadataCommandError::Parse(_))
         );
     }
 
@@ -371,7 +578,7 @@ mod tests {
     fn parse_stream_version_0() {
         check_parse_stream!(
             "{\"version\":0,\"foo\":1}",
-            Err(MetadataCommandError::Json(_))
+            Err(MetadataCommandError::Parse(_))
         );
     }
 
@@ -380,6 +587,25 @@ mod tests {
         check_parse_stream!("{\"version\":0,\"foo\":1}\n{meta}", Ok(_));
     }
 
+    #[test]
+    fn parse_stream_corrupted_points_at_error() {
+        for meta in [minimal_metadata_json(), minimal_metadata_json_pretty()] {
+            let stdout = format!(
+                "plugin output\n{}\n",
+                meta.replacen("\"app_exe\"", "oops\"app_exe\"", 1)
+            );
+            let offset = stdout.find("oops").unwrap();
+            let Err(MetadataCommandError::Parse(err)) =
+                crate::command::metadata_command::parse_stream(stdout.clone())
+            else {
+                panic!("corrupted metadata should fail to deserialize");
+            };
+            assert_eq!(err.offset, offset);
+            assert!(err.excerpt.contains("oops\"app_exe\""));
+            assert!(stdout.contains(&err.excerpt));
+        }
+    }
+
     #[test]
     fn parse_stream_crlf() {
         check_parse_stream!(
@@ -422,6 +648,8 @@ mod tests {
             compilation_units: Default::default(),
             current_profile: "dev".into(),
             profiles: vec!["dev".into()],
+            lockfile_path: Default::default(),
+            lockfile_up_to_date: Default::default(),
             extra: Default::default(),
         }
     }
@@ -21,6 +21,7 @@ use std::collections::{BTreeMap, HashMap};
 use std::fmt;
 use std::ops::Index;
 use std::path::PathBuf;
+use std::str::FromStr;
 
 use camino::{Utf8Path, Utf8PathBuf};
 #[cfg(feature = "builder")]
@@ -136,6 +137,9 @@ fn current_profile_default() -> String {
 fn profiles_default() -> Vec<String> {
     vec!["release".to_string()]
 }
+fn publish_default() -> bool {
+    true
+}
 
 /// Top level data structure printed by `scarb metadata`.
 #[derive(Clone, Serialize, Deserialize, Debug, Eq, PartialEq)]
@@ -189,6 +193,27 @@ pub struct Metadata {
     #[serde(default = "profiles_default")]
     pub profiles: Vec<String>,
 
+    /// Settings of the currently selected profile, resolved after profile inheritance.
+    ///
+    /// This is an object with `cairo` and `tool` keys, holding the compiler settings and
+    /// tool metadata overrides of the profile, as defined in the workspace root manifest.
+    #[serde(default)]
+    #[cfg_attr(feature = "builder", builder(default))]
+    pub profile_definition: serde_json::Value,
+
+    /// Path to the `Scarb.lock` file of this workspace.
+    #[serde(default)]
+    #[cfg_attr(feature = "builder", builder(default))]
+    pub lockfile_path: Option<Utf8PathBuf>,
+
+    /// Whether the lockfile was up to date, i.e. resolving the workspace did not change it.
+    ///
+    /// This is `None` if the workspace has not been resolved, for example, in the output
+    /// of `scarb metadata --no-deps`.
+    #[serde(default)]
+    #[cfg_attr(feature = "builder", builder(default))]
+    pub lockfile_up_to_date: Option<bool>,
+
     /// Additional data not captured by deserializer.
     #[cfg_attr(feature = "builder", builder(default))]
     #[serde(flatten)]
@@ -258,6 +283,26 @@ pub struct PackageMetadata {
     #[serde(default)]
     pub experimental_features: Vec<String>,
 
+    /// Whether this package can be published to a registry.
+    ///
+    /// This is `false` if the manifest sets `publish = false`.
+    #[cfg_attr(feature = "builder", builder(default = "true"))]
+    #[serde(default = "publish_default")]
+    pub publish: bool,
+
+    /// Minimum version of Scarb required by this package, as given in `Scarb.toml`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "builder", builder(default))]
+    pub minimum_scarb_version: Option<VersionReq>,
+
+    /// Raw contents of the manifest file of this package.
+    ///
+    /// This is only present if requested with the `--include-manifest-source` flag of
+    /// `scarb metadata`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "builder", builder(default))]
+    pub manifest_source: Option<String>,
+
     /// Additional data not captured by deserializer.
     #[cfg_attr(feature = "builder", builder(default))]
     #[serde(flatten)]
@@ -308,9 +353,14 @@ pub struct TargetMetadata {
     pub name: String,
     /// Path to the main source file of the target.
     pub source_path: Utf8PathBuf,
+    /// Cairo edition of the target, resolved from the target or package edition.
+    #[serde(default)]
+    #[cfg_attr(feature = "builder", builder(default))]
+    pub edition: Option<String>,
     /// Unstructured target parameters, excluding default values.
     ///
-    /// Default values are omitted because they are unknown to Scarb, they are applied by compilers.
+    /// Default values are omitted because they are applied by compilers, unless requested with
+    /// the `--include-target-defaults` flag of `scarb metadata`.
     pub params: serde_json::Value,
 
     /// Additional data not captured by deserializer.
@@ -423,6 +473,27 @@ pub struct CompilationUnitCairoPluginMetadata {
     /// Whether Scarb will attempt to load prebuilt binaries associated with this plugin.
     pub prebuilt_allowed: Option<bool>,
 
+    /// Resolved version of the plugin package.
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[serde(default)]
+    pub version: Option<Version>,
+
+    /// Whether the plugin is built into Scarb, or compiled from source.
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[serde(default)]
+    pub builtin: bool,
+
+    /// Path to the shared library of the plugin, if it has already been built,
+    /// or a prebuilt binary is available.
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[serde(default)]
+    pub artifact_path: Option<Utf8PathBuf>,
+
+    /// Whether a prebuilt binary is used instead of compiling the plugin from source.
+    #[cfg_attr(feature = "builder", builder(default))]
+    #[serde(default)]
+    pub prebuilt: bool,
+
     /// Additional data not captured by deserializer.
     #[cfg_attr(feature = "builder", builder(default))]
     #[serde(flatten)]
@@ -523,7 +594,99 @@ pub enum Cfg {
     Name(String),
 }
 
+/// Formats the option as it is written in the `#[cfg(...)]` attribute: `name` or `key: 'value'`.
+impl fmt::Display for Cfg {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::KV(key, value) => write!(f, "{key}: '{value}'"),
+            Self::Name(name) => write!(f, "{name}"),
+        }
+    }
+}
+
+/// Parses the option from the form produced by its [`Display`][fmt::Display] implementation.
+///
+/// Names and keys must be non-empty and cannot contain whitespace, colons or quotes.
+/// Values may contain anything but single quotes, so options with such values do not round-trip.
+impl FromStr for Cfg {
+    type Err = ParseCfgError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = |reason| ParseCfgError {
+            input: s.to_string(),
+            reason,
+        };
+        let check_name = |name: &str| {
+            if name.is_empty() {
+                Err(err("name is empty"))
+            } else if name
+                .chars()
+                .any(|c| c.is_whitespace() || c == ':' || c == '\'' || c == '"')
+            {
+                Err(err("name contains whitespace, a colon or a quote"))
+            } else {
+                Ok(name.to_string())
+            }
+        };
+
+        match s.split_once(':') {
+            Some((key, value)) => {
+                let key = check_name(key)?;
+                let value = value
+                    .trim_start()
+                    .strip_prefix('\'')
+                    .and_then(|value| value.strip_suffix('\''))
+                    .ok_or_else(|| err("value is not enclosed in single quotes"))?;
+                if value.contains('\'') {
+                    return Err(err("value contains a single quote"));
+                }
+                Ok(Self::KV(key, value.to_string()))
+            }
+            None => Ok(Self::Name(check_name(s)?)),
+        }
+    }
+}
+
+/// Error returned when parsing a [`Cfg`] from a string fails.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParseCfgError {
+    input: String,
+    reason: &'static str,
+}
+
+impl fmt::Display for ParseCfgError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid cfg `{}`: {}", self.input, self.reason)
+    }
+}
+
+impl std::error::Error for ParseCfgError {}
+
 impl Metadata {
+    /// Create metadata of the given workspace, without any packages and compilation units.
+    ///
+    /// All other fields are set to placeholder values, with Scarb and Cairo versions set to
+    /// the version of this crate. This is meant for tools and tests that need to synthesize
+    /// metadata, and then fill in the fields they care about, without the `builder` feature.
+    pub fn minimal(workspace: WorkspaceMetadata) -> Self {
+        Self {
+            version: VersionPin,
+            app_exe: None,
+            app_version_info: crate_version_info(),
+            target_dir: None,
+            runtime_manifest: workspace.manifest_path.clone(),
+            workspace,
+            packages: Vec::new(),
+            compilation_units: Vec::new(),
+            current_profile: current_profile_default(),
+            profiles: profiles_default(),
+            profile_definition: serde_json::Value::default(),
+            lockfile_path: None,
+            lockfile_up_to_date: None,
+            extra: HashMap::new(),
+        }
+    }
+
     /// Returns reference to [`PackageMetadata`] corresponding to the [`PackageId`].
     pub fn get_package(&self, id: &PackageId) -> Option<&PackageMetadata> {
         self.packages.iter().find(|p| p.id == *id)
@@ -533,8 +696,98 @@ impl Metadata {
     pub fn get_compilation_unit(&self, id: &CompilationUnitId) -> Option<&CompilationUnitMetadata> {
         self.compilation_units.iter().find(|p| p.id == *id)
     }
+
+    /// Returns reference to the only [`PackageMetadata`] with the given name.
+    ///
+    /// Fails if there is no such package, or if multiple packages share this name,
+    /// for example, when different versions or sources of a package are resolved.
+    pub fn find_package_by_name(&self, name: &str) -> Result<&PackageMetadata, FindError> {
+        let mut packages = self.packages.iter().filter(|p| p.name == name);
+        let Some(package) = packages.next() else {
+            return Err(FindError::NotFound {
+                name: name.to_string(),
+            });
+        };
+        let others = packages.map(|p| p.id.clone()).collect::<Vec<_>>();
+        if others.is_empty() {
+            Ok(package)
+        } else {
+            Err(FindError::Ambiguous {
+                name: name.to_string(),
+                ids: [package.id.clone()].into_iter().chain(others).collect(),
+            })
+        }
+    }
+
+    /// Iterate over [`PackageMetadata`] of workspace members, in the order of
+    /// [`WorkspaceMetadata::members`].
+    ///
+    /// Members without matching package metadata are skipped.
+    pub fn workspace_members(&self) -> impl Iterator<Item = &PackageMetadata> {
+        self.workspace_members_with_ids()
+            .map(|(_, package)| package)
+    }
+
+    /// Iterate over [`PackageId`]s of workspace members together with their [`PackageMetadata`],
+    /// in the order of [`WorkspaceMetadata::members`].
+    ///
+    /// Members without matching package metadata are skipped.
+    pub fn workspace_members_with_ids(
+        &self,
+    ) -> impl Iterator<Item = (&PackageId, &PackageMetadata)> {
+        self.workspace
+            .members
+            .iter()
+            .filter_map(|id| Some((id, self.get_package(id)?)))
+    }
+
+    /// Iterate over [`PackageId`]s of workspace members together with paths to their manifest
+    /// files, in the order of [`WorkspaceMetadata::members`].
+    ///
+    /// Members without matching package metadata are skipped.
+    pub fn workspace_member_manifest_paths(&self) -> impl Iterator<Item = (&PackageId, &Utf8Path)> {
+        self.workspace_members_with_ids()
+            .map(|(id, package)| (id, package.manifest_path.as_path()))
+    }
+}
+
+/// Error returned when looking up a package by name in [`Metadata`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum FindError {
+    /// No package with the given name exists.
+    NotFound {
+        /// Name of the looked-up package.
+        name: String,
+    },
+    /// Multiple packages share the given name.
+    Ambiguous {
+        /// Name of the looked-up package.
+        name: String,
+        /// Identifiers of all packages with this name.
+        ids: Vec<PackageId>,
+    },
+}
+
+impl fmt::Display for FindError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotFound { name } => write!(f, "package `{name}` not found"),
+            Self::Ambiguous { name, ids } => {
+                write!(f, "package name `{name}` is ambiguous, it matches: ")?;
+                for (i, id) in ids.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{id}")?;
+                }
+                Ok(())
+            }
+        }
+    }
 }
 
+impl std::error::Error for FindError {}
+
 impl<'a> Index<&'a PackageId> for Metadata {
     type Output = PackageMetadata;
 
@@ -553,12 +806,118 @@ impl<'a> Index<&'a CompilationUnitId> for Metadata {
     }
 }
 
+impl WorkspaceMetadata {
+    /// Create metadata of a workspace defined by the given manifest, without any members.
+    pub fn new(manifest_path: impl Into<Utf8PathBuf>, root: impl Into<Utf8PathBuf>) -> Self {
+        Self {
+            manifest_path: manifest_path.into(),
+            root: root.into(),
+            members: Vec::new(),
+            extra: HashMap::new(),
+        }
+    }
+}
+
+/// Placeholder version information, with Scarb and Cairo versions set to the version of this crate.
+fn crate_version_info() -> VersionInfo {
+    let version = Version::parse(env!("CARGO_PKG_VERSION")).unwrap();
+    VersionInfo {
+        version: version.clone(),
+        commit_info: None,
+        cairo: CairoVersionInfo {
+            version,
+            commit_info: None,
+            extra: HashMap::new(),
+        },
+        extra: HashMap::new(),
+    }
+}
+
 impl PackageMetadata {
+    /// Get the edition of this package, falling back to the edition Scarb uses for packages
+    /// without an `edition` field, if the metadata does not specify one.
+    pub fn edition_or_default(&self) -> &str {
+        self.edition.as_deref().unwrap_or("2023_01")
+    }
+
     /// Get value of the `[tool.*]` section in this package's manifest, for specific `tool_name`,
     /// including any transformations applied by Scarb.
     pub fn tool_metadata(&self, tool_name: &str) -> Option<&serde_json::Value> {
         self.manifest_metadata.tool.as_ref()?.get(tool_name)
     }
+
+    /// Find a dependency of this package by name.
+    ///
+    /// If the package depends on multiple packages with the same name, but from different
+    /// sources, the first one listed in [`PackageMetadata::dependencies`] is returned.
+    /// Use [`PackageMetadata::dependencies_by_source`] to tell them apart.
+    pub fn dependency(&self, name: &str) -> Option<&DependencyMetadata> {
+        self.dependencies.iter().find(|dep| dep.name == name)
+    }
+
+    /// Iterate over dependencies of this package which come from the given source.
+    pub fn dependencies_by_source<'a>(
+        &'a self,
+        source: &'a SourceId,
+    ) -> impl Iterator<Item = &'a DependencyMetadata> + 'a {
+        self.dependencies
+            .iter()
+            .filter(move |dep| &dep.source == source)
+    }
+}
+
+#[cfg(feature = "builder")]
+impl MetadataBuilder {
+    /// Create a builder of metadata of an empty workspace, with all required fields set to
+    /// placeholder values.
+    ///
+    /// This is meant for tests, which can then override only the fields they care about.
+    pub fn fixture() -> Self {
+        let mut builder = Self::default();
+        builder
+            .app_exe(None::<PathBuf>)
+            .app_version_info(crate_version_info())
+            .target_dir(None::<Utf8PathBuf>)
+            .runtime_manifest("/workspace/Scarb.toml")
+            .workspace(WorkspaceMetadata {
+                manifest_path: "/workspace/Scarb.toml".into(),
+                root: "/workspace".into(),
+                members: Vec::new(),
+                extra: HashMap::new(),
+            })
+            .packages(Vec::new())
+            .compilation_units(Vec::new())
+            .current_profile(current_profile_default())
+            .profiles(profiles_default());
+        builder
+    }
+}
+
+#[cfg(feature = "builder")]
+impl PackageMetadataBuilder {
+    /// Create a builder of metadata of a local package without dependencies and targets,
+    /// with all required fields filled.
+    ///
+    /// The package is placed at `/<name>`. This is meant for tests, which can then override
+    /// only the fields they care about.
+    pub fn minimal(name: impl Into<String>, version: Version) -> Self {
+        let name = name.into();
+        let source = SourceId::from(format!("path+file:///{name}/Scarb.toml"));
+        let mut builder = Self::default();
+        builder
+            .id(PackageId::from(format!("{name} {version} ({source})")))
+            .source(source)
+            .manifest_path(format!("/{name}/Scarb.toml"))
+            .root(format!("/{name}"))
+            .name(name)
+            .version(version)
+            .edition(None::<String>)
+            .dependencies(Vec::new())
+            .targets(Vec::new())
+            .manifest_metadata(ManifestMetadata::default())
+            .experimental_features(Vec::new());
+        builder
+    }
 }
 
 impl TargetMetadata {
@@ -568,6 +927,92 @@ impl TargetMetadata {
             .parent()
             .expect("Source path is guaranteed to point to a file.")
     }
+
+    /// Name of output files of this target.
+    ///
+    /// This is the target name, unless targets are grouped with the `group-id` parameter,
+    /// in which case all targets of the group share output files named after the group.
+    pub fn output_name(&self) -> &str {
+        self.param_str("group-id").unwrap_or(&self.name)
+    }
+
+    /// Get a string parameter of this target.
+    ///
+    /// Returns `None` if the parameter is missing or is not a string.
+    pub fn param_str(&self, key: &str) -> Option<&str> {
+        self.params.get(key)?.as_str()
+    }
+
+    /// Get a boolean parameter of this target.
+    ///
+    /// Returns `None` if the parameter is missing or is not a boolean.
+    pub fn param_bool(&self, key: &str) -> Option<bool> {
+        self.params.get(key)?.as_bool()
+    }
+
+    /// Get an unsigned integer parameter of this target.
+    ///
+    /// Returns `None` if the parameter is missing or is not an unsigned integer.
+    pub fn param_u64(&self, key: &str) -> Option<u64> {
+        self.params.get(key)?.as_u64()
+    }
+
+    /// Path of the function an `executable` target runs, as set by its `function` parameter.
+    ///
+    /// Returns `None` if the parameter is not set, in which case the executable runs the only
+    /// function marked with `#[executable]` in the package.
+    pub fn executable_function(&self) -> Option<&str> {
+        self.param_str("function")
+    }
+
+    /// Path to the `.executable.json` file produced by an `executable` target
+    /// in the given target directory and profile.
+    pub fn executable_json_path(&self, target_dir: &Utf8Path, profile: &str) -> Utf8PathBuf {
+        self.output_path(target_dir, profile, "executable.json")
+    }
+
+    /// Path to the `.sierra.json` file produced by a `lib` target
+    /// in the given target directory and profile.
+    pub fn sierra_json_path(&self, target_dir: &Utf8Path, profile: &str) -> Utf8PathBuf {
+        self.output_path(target_dir, profile, "sierra.json")
+    }
+
+    /// Path to the `.test.json` file with compiled tests metadata produced by a `test` target
+    /// in the given target directory and profile.
+    pub fn test_json_path(&self, target_dir: &Utf8Path, profile: &str) -> Utf8PathBuf {
+        self.output_path(target_dir, profile, "test.json")
+    }
+
+    /// Path to the `.test.sierra.json` file with the Sierra program produced by a `test` target
+    /// in the given target directory and profile.
+    pub fn test_sierra_json_path(&self, target_dir: &Utf8Path, profile: &str) -> Utf8PathBuf {
+        self.output_path(target_dir, profile, "test.sierra.json")
+    }
+
+    fn output_path(&self, target_dir: &Utf8Path, profile: &str, extension: &str) -> Utf8PathBuf {
+        target_dir
+            .join(profile)
+            .join(format!("{}.{extension}", self.output_name()))
+    }
+}
+
+impl CompilationUnitMetadata {
+    /// Paths to source directories of all components of this compilation unit.
+    ///
+    /// Paths are returned in the same order as [`CompilationUnitMetadata::components`].
+    pub fn source_roots(&self) -> Vec<&Utf8Path> {
+        self.components.iter().map(|c| c.source_root()).collect()
+    }
+
+    /// Items for the Cairo's `#[cfg(...)]` attribute enabled for the given component.
+    ///
+    /// Falls back to [`CompilationUnitMetadata::cfg`] if the component does not specify its own.
+    pub fn component_cfg<'a>(
+        &'a self,
+        component: &'a CompilationUnitComponentMetadata,
+    ) -> &'a [Cfg] {
+        component.cfg.as_deref().unwrap_or(&self.cfg)
+    }
 }
 
 impl CompilationUnitComponentMetadata {
@@ -0,0 +1,396 @@
+use camino::Utf8Path;
+use semver::{Version, VersionReq};
+use serde_json::json;
+
+use scarb_metadata::{
+    Cfg, CompilationUnitComponentDependencyMetadata, CompilationUnitComponentId,
+    CompilationUnitComponentMetadata, CompilationUnitComponentMetadataBuilder,
+    CompilationUnitMetadata, CompilationUnitMetadataBuilder, DepKind, DependencyMetadataBuilder,
+    FindError, Metadata, MetadataBuilder, PackageId, PackageMetadata, PackageMetadataBuilder,
+    SourceId, TargetMetadata, TargetMetadataBuilder,
+};
+
+const REGISTRY: &str = "registry+https://scarbs.xyz/";
+const GIT: &str = "git+https://github.com/foo/bar.git";
+
+fn package(name: &str) -> PackageMetadata {
+    PackageMetadataBuilder::minimal(name, Version::new(0, 1, 0))
+        .build()
+        .unwrap()
+}
+
+fn package_from_source(name: &str, version: Version, source: &str) -> PackageMetadata {
+    let source = SourceId::from(source.to_string());
+    PackageMetadataBuilder::minimal(name, version.clone())
+        .id(PackageId::from(format!("{name} {version} ({source})")))
+        .source(source)
+        .build()
+        .unwrap()
+}
+
+fn metadata(members: &[&str], packages: Vec<PackageMetadata>) -> Metadata {
+    let mut metadata = MetadataBuilder::fixture()
+        .packages(packages)
+        .build()
+        .unwrap();
+    metadata.workspace.members = members.iter().map(|name| package(name).id).collect();
+    metadata
+}
+
+fn target(kind: &str, name: &str, params: serde_json::Value) -> TargetMetadata {
+    TargetMetadataBuilder::default()
+        .kind(kind)
+        .name(name)
+        .source_path("/hello/src/lib.cairo")
+        .params(params)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn minimal_metadata_round_trips() {
+    let package = PackageMetadataBuilder::minimal("hello", Version::new(0, 1, 0))
+        .build()
+        .unwrap();
+    assert_eq!(
+        package.id.to_string(),
+        "hello 0.1.0 (path+file:///hello/Scarb.toml)"
+    );
+    assert_eq!(package.manifest_path, "/hello/Scarb.toml");
+    assert!(package.publish);
+
+    let metadata = MetadataBuilder::fixture()
+        .packages(vec![package.clone()])
+        .build()
+        .unwrap();
+    assert_eq!(metadata.current_profile, "dev");
+    assert_eq!(metadata.packages, vec![package]);
+
+    let json = serde_json::to_string(&metadata).unwrap();
+    let deserialized: Metadata = serde_json::from_str(&json).unwrap();
+    assert_eq!(deserialized, metadata);
+}
+
+#[test]
+fn preset_fields_can_be_overridden() {
+    let package = PackageMetadataBuilder::minimal("hello", Version::new(0, 1, 0))
+        .edition(Some("2024_07".to_string()))
+        .publish(false)
+        .build()
+        .unwrap();
+    assert_eq!(package.edition.as_deref(), Some("2024_07"));
+    assert!(!package.publish);
+}
+
+#[test]
+fn edition_round_trips() {
+    for edition in [None, Some("2023_11".to_string())] {
+        let package = PackageMetadataBuilder::minimal("hello", Version::new(0, 1, 0))
+            .edition(edition.clone())
+            .build()
+            .unwrap();
+        let json = serde_json::to_value(&package).unwrap();
+        assert_eq!(
+            json.get("edition").and_then(|e| e.as_str()),
+            edition.as_deref()
+        );
+
+        let deserialized: PackageMetadata = serde_json::from_value(json).unwrap();
+        assert_eq!(deserialized.edition, edition);
+        assert_eq!(
+            deserialized.edition_or_default(),
+            edition.as_deref().unwrap_or("2023_01")
+        );
+    }
+}
+
+#[test]
+fn dependency_lookup() {
+    let dependency = |name: &str, source: &str| {
+        DependencyMetadataBuilder::default()
+            .name(name)
+            .version_req(VersionReq::STAR)
+            .source(SourceId::from(source.to_string()))
+            .kind(None::<DepKind>)
+            .build()
+            .unwrap()
+    };
+    let package = PackageMetadataBuilder::minimal("hello", Version::new(0, 1, 0))
+        .dependencies(vec![
+            dependency("starknet", "std"),
+            dependency("foo", GIT),
+            dependency("foo", REGISTRY),
+            dependency("bar", REGISTRY),
+        ])
+        .build()
+        .unwrap();
+
+    assert_eq!(package.dependency("starknet").unwrap().source.repr, "std");
+    assert!(package.dependency("cairo_test").is_none());
+    // Dependency with a duplicate name resolves to the first one.
+    assert_eq!(package.dependency("foo").unwrap().source.repr, GIT);
+
+    let registry = SourceId::from(REGISTRY.to_string());
+    let names = package
+        .dependencies_by_source(&registry)
+        .map(|dep| dep.name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["foo", "bar"]);
+
+    let git = SourceId::from(GIT.to_string());
+    let names = package
+        .dependencies_by_source(&git)
+        .map(|dep| dep.name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["foo"]);
+
+    let path = SourceId::from("path+file:///other/Scarb.toml".to_string());
+    assert_eq!(package.dependencies_by_source(&path).count(), 0);
+}
+
+#[test]
+fn find_package_by_name() {
+    let metadata = metadata(
+        &["hello"],
+        vec![
+            package("hello"),
+            package_from_source("dep", Version::new(1, 0, 0), REGISTRY),
+            package_from_source("dep", Version::new(2, 0, 0), GIT),
+        ],
+    );
+
+    let package = metadata.find_package_by_name("hello").unwrap();
+    assert_eq!(package.name, "hello");
+    assert_eq!(package.version.to_string(), "0.1.0");
+
+    let err = metadata.find_package_by_name("missing").unwrap_err();
+    assert_eq!(
+        err,
+        FindError::NotFound {
+            name: "missing".to_string()
+        }
+    );
+    assert_eq!(err.to_string(), "package `missing` not found");
+
+    let err = metadata.find_package_by_name("dep").unwrap_err();
+    assert_eq!(
+        err,
+        FindError::Ambiguous {
+            name: "dep".to_string(),
+            ids: vec![
+                PackageId::from(format!("dep 1.0.0 ({REGISTRY})")),
+                PackageId::from(format!("dep 2.0.0 ({GIT})")),
+            ]
+        }
+    );
+    assert_eq!(
+        err.to_string(),
+        "package name `dep` is ambiguous, it matches: \
+        dep 1.0.0 (registry+https://scarbs.xyz/), \
+        dep 2.0.0 (git+https://github.com/foo/bar.git)"
+    );
+}
+
+#[test]
+fn workspace_members_preserve_member_order() {
+    let metadata = metadata(
+        &["second", "first"],
+        vec![package("dep"), package("first"), package("second")],
+    );
+    let names = metadata
+        .workspace_members()
+        .map(|package| package.name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["second", "first"]);
+}
+
+#[test]
+fn workspace_members_skip_missing_packages() {
+    let metadata = metadata(
+        &["first", "missing", "second"],
+        vec![package("second"), package("first")],
+    );
+    let members = metadata
+        .workspace_members_with_ids()
+        .map(|(id, package)| {
+            assert_eq!(*id, package.id);
+            id.clone()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(members, [package("first").id, package("second").id]);
+}
+
+#[test]
+fn workspace_member_manifest_paths() {
+    let metadata = metadata(
+        &["first", "second"],
+        vec![package("dep"), package("second"), package("first")],
+    );
+    let paths = metadata
+        .workspace_member_manifest_paths()
+        .map(|(id, path)| (id.clone(), path.to_string()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        paths,
+        [
+            (package("first").id, "/first/Scarb.toml".to_string()),
+            (package("second").id, "/second/Scarb.toml".to_string())
+        ]
+    );
+}
+
+fn compilation_unit() -> CompilationUnitMetadata {
+    let component = |package: &PackageMetadata, cfg: Option<Vec<Cfg>>| {
+        CompilationUnitComponentMetadataBuilder::default()
+            .package(package.id.clone())
+            .name(package.name.clone())
+            .source_path(package.root.join("src/lib.cairo"))
+            .cfg(cfg)
+            .id(None::<CompilationUnitComponentId>)
+            .discriminator(None::<String>)
+            .dependencies(None::<Vec<CompilationUnitComponentDependencyMetadata>>)
+            .build()
+            .unwrap()
+    };
+    let hello = package("hello");
+    let components: Vec<CompilationUnitComponentMetadata> = vec![
+        component(&package("core"), Some(Vec::new())),
+        component(
+            &hello,
+            Some(vec![
+                Cfg::KV("target".into(), "test".into()),
+                Cfg::Name("test".into()),
+            ]),
+        ),
+        component(&package("dep"), None),
+    ];
+    CompilationUnitMetadataBuilder::default()
+        .id("hello_unittest".to_string())
+        .package(hello.id)
+        .target(target("test", "hello_unittest", json!({})))
+        .compiler_config(json!({}))
+        .components(components)
+        .cairo_plugins(Vec::new())
+        .cfg(vec![Cfg::KV("target".into(), "test".into())])
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn source_roots_of_all_components() {
+    let cu = compilation_unit();
+    assert_eq!(
+        cu.source_roots(),
+        vec![
+            Utf8Path::new("/core/src"),
+            Utf8Path::new("/hello/src"),
+            Utf8Path::new("/dep/src"),
+        ]
+    );
+}
+
+#[test]
+fn component_cfg_falls_back_to_unit_cfg() {
+    let cu = compilation_unit();
+    assert_eq!(cu.component_cfg(&cu.components[0]), &[]);
+    assert_eq!(
+        cu.component_cfg(&cu.components[1]),
+        &[
+            Cfg::KV("target".into(), "test".into()),
+            Cfg::Name("test".into())
+        ]
+    );
+    assert_eq!(
+        cu.component_cfg(&cu.components[2]),
+        &[Cfg::KV("target".into(), "test".into())]
+    );
+}
+
+#[test]
+fn executable_json_path() {
+    let target = target("executable", "hello", json!({}));
+    assert_eq!(
+        target.executable_json_path(Utf8Path::new("/hello/target"), "dev"),
+        Utf8Path::new("/hello/target/dev/hello.executable.json")
+    );
+}
+
+#[test]
+fn sierra_json_path() {
+    let target = target("lib", "hello", json!({"sierra": true}));
+    assert_eq!(
+        target.sierra_json_path(Utf8Path::new("/hello/target"), "release"),
+        Utf8Path::new("/hello/target/release/hello.sierra.json")
+    );
+}
+
+#[test]
+fn test_json_paths() {
+    let target = target("test", "hello_unittest", json!({"test-type": "unit"}));
+    let target_dir = Utf8Path::new("/hello/target");
+    assert_eq!(
+        target.test_json_path(target_dir, "dev"),
+        Utf8Path::new("/hello/target/dev/hello_unittest.test.json")
+    );
+    assert_eq!(
+        target.test_sierra_json_path(target_dir, "dev"),
+        Utf8Path::new("/hello/target/dev/hello_unittest.test.sierra.json")
+    );
+}
+
+#[test]
+fn grouped_test_json_paths() {
+    let target = target(
+        "test",
+        "hello_integrationtest_foo",
+        json!({"group-id": "hello_integrationtest", "test-type": "integration"}),
+    );
+    let target_dir = Utf8Path::new("/hello/target");
+    assert_eq!(target.output_name(), "hello_integrationtest");
+    assert_eq!(
+        target.test_json_path(target_dir, "dev"),
+        Utf8Path::new("/hello/target/dev/hello_integrationtest.test.json")
+    );
+    assert_eq!(
+        target.test_sierra_json_path(target_dir, "dev"),
+        Utf8Path::new("/hello/target/dev/hello_integrationtest.test.sierra.json")
+    );
+}
+
+#[test]
+fn typed_params() {
+    let target = target(
+        "executable",
+        "hello",
+        json!({"function": "hello::main", "allow-syscalls": true, "max-steps": 100}),
+    );
+    assert_eq!(target.param_str("function"), Some("hello::main"));
+    assert_eq!(target.param_bool("allow-syscalls"), Some(true));
+    assert_eq!(target.param_u64("max-steps"), Some(100));
+    assert_eq!(target.executable_function(), Some("hello::main"));
+}
+
+#[test]
+fn typed_params_of_wrong_types() {
+    let target = target(
+        "executable",
+        "hello",
+        json!({"function": 1, "allow-syscalls": "yes", "max-steps": -1}),
+    );
+    assert_eq!(target.param_str("function"), None);
+    assert_eq!(target.param_bool("allow-syscalls"), None);
+    assert_eq!(target.param_u64("max-steps"), None);
+    assert_eq!(target.executable_function(), None);
+}
+
+#[test]
+fn typed_params_missing() {
+    for params in [json!({}), json!(null)] {
+        let target = target("executable", "hello", params);
+        assert_eq!(target.param_str("function"), None);
+        assert_eq!(target.param_bool("allow-syscalls"), None);
+        assert_eq!(target.param_u64("max-steps"), None);
+        assert_eq!(target.executable_function(), None);
+        assert_eq!(target.output_name(), "hello");
+    }
+}
@@ -0,0 +1,53 @@
+use scarb_metadata::Cfg;
+
+#[test]
+fn display() {
+    assert_eq!(Cfg::Name("test".into()).to_string(), "test");
+    assert_eq!(
+        Cfg::KV("target".into(), "test".into()).to_string(),
+        "target: 'test'"
+    );
+}
+
+#[test]
+fn round_trips() {
+    for cfg in [
+        Cfg::Name("test".into()),
+        Cfg::KV("target".into(), "test".into()),
+        Cfg::KV("url".into(), "https://example.com:8080".into()),
+        Cfg::KV("empty".into(), "".into()),
+        Cfg::KV("spaced".into(), " a b ".into()),
+    ] {
+        assert_eq!(cfg.to_string().parse::<Cfg>().unwrap(), cfg);
+    }
+}
+
+#[test]
+fn parses_value_containing_colons() {
+    assert_eq!(
+        "a: 'b: c'".parse::<Cfg>().unwrap(),
+        Cfg::KV("a".into(), "b: c".into())
+    );
+}
+
+#[test]
+fn rejects_invalid_names() {
+    for input in [
+        "", " test", "test ", "te st", " a: 'b'", "a : 'b'", ": 'b'", "'test'",
+    ] {
+        assert!(input.parse::<Cfg>().is_err(), "`{input}` should not parse");
+    }
+}
+
+#[test]
+fn rejects_invalid_values() {
+    assert_eq!(
+        "a: b".parse::<Cfg>().unwrap_err().to_string(),
+        "invalid cfg `a: b`: value is not enclosed in single quotes"
+    );
+    assert_eq!(
+        "a: 'b'c'".parse::<Cfg>().unwrap_err().to_string(),
+        "invalid cfg `a: 'b'c'`: value contains a single quote"
+    );
+    assert!("a: 'b' ".parse::<Cfg>().is_err());
+}
@@ -75,3 +75,84 @@ fn init_project(t: &TempDir) {
         .assert()
         .success();
 }
+
+#[test]
+fn relative_manifest_path_is_resolved_against_current_dir() {
+    let t = TempDir::new().unwrap();
+    init_project(&t);
+    let name = t.path().file_name().unwrap();
+
+    let metadata = MetadataCommand::new()
+        .scarb_path(scarb_bin())
+        .current_dir(t.path().parent().unwrap())
+        .manifest_path(PathBuf::from(name).join("Scarb.toml"))
+        .inherit_stderr()
+        .exec()
+        .unwrap();
+    assert_eq!(
+        metadata.workspace.root.file_name(),
+        Some(name.to_str().unwrap())
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn timeout_kills_hanging_scarb() {
+    use std::os::unix::fs::PermissionsExt;
+    use std::time::{Duration, Instant};
+
+    use scarb_metadata::MetadataCommandError;
+
+    let t = TempDir::new().unwrap();
+    let pid_file = t.path().join("sleep.pid");
+    let scarb = t.path().join("scarb");
+    std::fs::write(
+        &scarb,
+        format!(
+            "#!/bin/sh\nsleep 60 &\necho $! > '{}'\nwait\n",
+            pid_file.display()
+        ),
+    )
+    .unwrap();
+    std::fs::set_permissions(&scarb, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+    let start = Instant::now();
+    let error = MetadataCommand::new()
+        .scarb_path(&scarb)
+        .current_dir(t.path())
+        .timeout(Duration::from_millis(500))
+        .exec()
+        .unwrap_err();
+    assert!(matches!(error, MetadataCommandError::Timeout { .. }));
+    assert!(start.elapsed() < Duration::from_secs(30));
+
+    // The process spawned by the stub must be killed together with it.
+    let pid = std::fs::read_to_string(&pid_file).unwrap();
+    let is_alive = || {
+        std::process::Command::new("kill")
+            .args(["-0", pid.trim()])
+            .stderr(std::process::Stdio::null())
+            .status()
+            .unwrap()
+            .success()
+    };
+    let deadline = Instant::now() + Duration::from_secs(10);
+    while is_alive() && Instant::now() < deadline {
+        std::thread::sleep(Duration::from_millis(50));
+    }
+    assert!(!is_alive());
+}
+
+#[test]
+fn timeout_does_not_affect_fast_scarb() {
+    let t = TempDir::new().unwrap();
+    init_project(&t);
+
+    MetadataCommand::new()
+        .scarb_path(scarb_bin())
+        .current_dir(t.path())
+        .timeout(std::time::Duration::from_secs(300))
+        .inherit_stderr()
+        .exec()
+        .unwrap();
+}
@@ -0,0 +1,21 @@
+use scarb_metadata::{Metadata, PackageId, VersionPin, WorkspaceMetadata};
+
+#[test]
+fn minimal_metadata_round_trips() {
+    let mut workspace = WorkspaceMetadata::new("/workspace/Scarb.toml", "/workspace");
+    workspace.members.push(PackageId::from(
+        "hello 0.1.0 (path+file:///workspace/Scarb.toml)".to_string(),
+    ));
+    let mut metadata = Metadata::minimal(workspace);
+    metadata.current_profile = "dev".to_string();
+
+    assert!(metadata.packages.is_empty());
+    assert!(metadata.compilation_units.is_empty());
+    assert_eq!(metadata.runtime_manifest, "/workspace/Scarb.toml");
+
+    let json = serde_json::to_value(&metadata).unwrap();
+    assert_eq!(json["version"], VersionPin.numeric());
+    assert_eq!(json["workspace"]["root"], "/workspace");
+    assert_eq!(json["current_profile"], "dev");
+    assert_eq!(serde_json::from_value::<Metadata>(json).unwrap(), metadata);
+}
@@ -4,6 +4,7 @@
 
 use std::collections::BTreeMap;
 use std::ffi::OsString;
+use std::num::NonZeroUsize;
 
 use anyhow::Result;
 use camino::Utf8PathBuf;
@@ -16,7 +17,7 @@ use scarb::core::PackageName;
 use scarb::manifest_editor::DepId;
 use scarb::manifest_editor::SectionArgs;
 use scarb::version;
-use scarb_ui::args::{FeaturesSpec, PackagesFilter, VerbositySpec};
+use scarb_ui::args::{ColorSpec, FeaturesSpec, PackagesFilter, VerbositySpec};
 use scarb_ui::OutputFormat;
 
 /// The Cairo package manager.
@@ -65,6 +66,10 @@ pub struct ScarbArgs {
     #[arg(long)]
     pub json: bool,
 
+    /// Coloring of the output.
+    #[command(flatten)]
+    pub color: ColorSpec,
+
     /// Run without accessing the network.
     #[arg(long, env = "SCARB_OFFLINE", hide_short_help = true)]
     pub offline: bool,
@@ -221,8 +226,13 @@ pub struct BuildArgs {
     #[arg(short, long, default_value_t = false)]
     pub test: bool,
 
-    /// Comma separated list of target names to compile.
-    #[arg(long, value_delimiter = ',', env = "SCARB_TARGET_NAMES")]
+    /// Comma separated list of target names to compile. Can be passed multiple times.
+    #[arg(
+        long,
+        visible_alias = "target",
+        value_delimiter = ',',
+        env = "SCARB_TARGET_NAMES"
+    )]
     pub target_names: Vec<String>,
 
     /// Comma separated list of target kinds to compile.
@@ -234,6 +244,26 @@ pub struct BuildArgs {
     )]
     pub target_kinds: Vec<String>,
 
+    /// Copy final artifacts of the compiled targets to this directory.
+    #[arg(long, value_name = "PATH")]
+    pub out_dir: Option<Utf8PathBuf>,
+
+    /// Print a report of code which will be rejected by a future version of Cairo.
+    #[arg(long, default_value_t = false)]
+    pub future_incompat_report: bool,
+
+    /// Write a `<artifact>.meta.json` file describing the provenance of each final artifact.
+    #[arg(long, default_value_t = false)]
+    pub emit_metadata: bool,
+
+    /// Print the graph of compilation units as JSON and exit, without building.
+    #[arg(
+        long,
+        default_value_t = false,
+        conflicts_with_all = ["out_dir", "future_incompat_report", "emit_metadata"]
+    )]
+    pub unit_graph: bool,
+
     /// Specify features to enable.
     #[command(flatten)]
     pub features: FeaturesSpec,
@@ -241,6 +271,10 @@ pub struct BuildArgs {
     /// Do not error on `cairo-version` mismatch.
     #[arg(long)]
     pub ignore_cairo_version: bool,
+
+    /// Number of procedural macro plugins to build in parallel, defaults to the number of CPUs.
+    #[arg(short, long, value_name = "N", env = "SCARB_JOBS")]
+    pub jobs: Option<NonZeroUsize>,
 }
 
 /// Arguments accepted by the `expand` command.
@@ -332,6 +366,31 @@ pub struct MetadataArgs {
     /// Do not error on `cairo-version` mismatch.
     #[arg(long)]
     pub ignore_cairo_version: bool,
+
+    /// Keep running and print new metadata whenever workspace manifests or the lockfile change.
+    #[arg(long, conflicts_with = "diff")]
+    pub watch: bool,
+
+    /// Compare metadata against a baseline file and fail if resolved packages differ.
+    #[arg(long, value_name = "BASELINE")]
+    pub diff: Option<Utf8PathBuf>,
+
+    /// Ignore workspace members when comparing against a baseline.
+    #[arg(long, requires = "diff")]
+    pub only_dependencies: bool,
+
+    /// Output information only about the compilation unit with the given ID,
+    /// and packages it consists of.
+    #[arg(long, value_name = "ID", conflicts_with = "no_deps")]
+    pub unit: Option<String>,
+
+    /// Include raw contents of the manifest file of each package.
+    #[arg(long)]
+    pub include_manifest_source: bool,
+
+    /// Include default values of target parameters, as applied by compilers of the targets.
+    #[arg(long)]
+    pub include_target_defaults: bool,
 }
 
 /// Arguments accepted by the `new` command.
@@ -531,6 +590,10 @@ pub struct PublishArgs {
     /// Do not error on `cairo-version` mismatch.
     #[arg(long)]
     pub ignore_cairo_version: bool,
+
+    /// Perform all checks without uploading.
+    #[arg(long)]
+    pub dry_run: bool,
 }
 
 #[derive(Parser, Clone, Debug)]
@@ -1,25 +1,75 @@
-use anyhow::Result;
+use anyhow::{ensure, Result};
+use itertools::Itertools;
 
 use crate::args::BuildArgs;
-use scarb::core::Config;
+use scarb::core::{Config, Package};
 use scarb::ops;
 use scarb::ops::CompileOpts;
+use scarb_ui::components::MachineMessage;
 
 #[tracing::instrument(skip_all, level = "info")]
 pub fn run(args: BuildArgs, config: &Config) -> Result<()> {
     let ws = ops::read_workspace(config.manifest_path(), config)?;
-    let packages = args
-        .packages_filter
-        .match_many(&ws)?
-        .into_iter()
-        .map(|p| p.id)
-        .collect::<Vec<_>>();
-    let opts = CompileOpts::try_new(
-        args.features,
-        args.ignore_cairo_version,
-        args.test,
-        args.target_names,
-        args.target_kinds,
-    )?;
+    let packages = args.packages_filter.match_many(&ws)?;
+    // Test targets requested with `--test` are optional, as not all packages have them.
+    let validate_kinds = !args.target_kinds.is_empty();
+    let opts = CompileOpts {
+        out_dir: args.out_dir,
+        future_incompat_report: args.future_incompat_report,
+        emit_metadata: args.emit_metadata,
+        jobs: args.jobs,
+        ..CompileOpts::try_new(
+            args.features,
+            args.ignore_cairo_version,
+            args.test,
+            args.target_names,
+            args.target_kinds,
+        )?
+    };
+    if validate_kinds {
+        validate_target_kinds(&packages, &opts)?;
+    }
+    validate_target_names(&packages, &opts)?;
+    let packages = packages.into_iter().map(|p| p.id).collect::<Vec<_>>();
+    if args.unit_graph {
+        let unit_graph = ops::unit_graph(packages, &opts, &ws)?;
+        config.ui().force_print(MachineMessage(unit_graph));
+        return Ok(());
+    }
     ops::compile(packages, opts, &ws)
 }
+
+fn validate_target_kinds(packages: &[Package], opts: &CompileOpts) -> Result<()> {
+    ensure!(
+        packages
+            .iter()
+            .flat_map(|p| p.manifest.targets.iter())
+            .any(|t| opts.include_target_kinds.contains(&t.kind)),
+        "no targets of kind {} found in selected packages",
+        opts.include_target_kinds
+            .iter()
+            .map(|kind| format!("`{kind}`"))
+            .join(", ")
+    );
+    Ok(())
+}
+
+fn validate_target_names(packages: &[Package], opts: &CompileOpts) -> Result<()> {
+    let missing = opts
+        .include_target_names
+        .iter()
+        .filter(|name| {
+            !packages
+                .iter()
+                .flat_map(|p| p.manifest.targets.iter())
+                .any(|t| t.name == **name)
+        })
+        .map(|name| format!("`{name}`"))
+        .collect_vec();
+    ensure!(
+        missing.is_empty(),
+        "no targets named {} found in selected packages",
+        missing.join(", ")
+    );
+    Ok(())
+}
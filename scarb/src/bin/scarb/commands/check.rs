@@ -1,4 +1,4 @@
-use anyhow::Result;
+use anyhow::{ensure, Result};
 
 use crate::args::BuildArgs;
 use scarb::core::Config;
@@ -7,6 +7,14 @@ use scarb::ops::CompileOpts;
 
 #[tracing::instrument(skip_all, level = "info")]
 pub fn run(args: BuildArgs, config: &Config) -> Result<()> {
+    ensure!(
+        args.out_dir.is_none(),
+        "`--out-dir` is not supported by `scarb check`, as it does not produce any artifacts"
+    );
+    ensure!(
+        !args.emit_metadata,
+        "`--emit-metadata` is not supported by `scarb check`, as it does not produce any artifacts"
+    );
     let ws = ops::read_workspace(config.manifest_path(), config)?;
     let packages = args
         .packages_filter
@@ -14,12 +22,15 @@ pub fn run(args: BuildArgs, config: &Config) -> Result<()> {
         .into_iter()
         .map(|p| p.id)
         .collect::<Vec<_>>();
-    let opts = CompileOpts::try_new(
-        args.features,
-        args.ignore_cairo_version,
-        args.test,
-        args.target_names,
-        args.target_kinds,
-    )?;
+    let opts = CompileOpts {
+        jobs: args.jobs,
+        ..CompileOpts::try_new(
+            args.features,
+            args.ignore_cairo_version,
+            args.test,
+            args.target_names,
+            args.target_kinds,
+        )?
+    };
     ops::check(packages, opts, &ws)
 }
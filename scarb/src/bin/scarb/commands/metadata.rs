@@ -1,26 +1,307 @@
-use anyhow::Result;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+use std::fs;
+use std::process::ExitCode;
+use std::sync::mpsc;
+use std::time::Duration;
 
-use scarb::core::Config;
+use anyhow::{bail, Context, Result};
+use camino::{Utf8Path, Utf8PathBuf};
+use notify::{RecursiveMode, Watcher};
+use scarb_metadata::{Metadata, PackageMetadata, SourceId};
+use semver::Version;
+use serde::{Serialize, Serializer};
+
+use scarb::core::{Config, Workspace};
 use scarb::ops;
 use scarb_ui::components::MachineMessage;
+use scarb_ui::Message;
 
 use crate::args::MetadataArgs;
+use crate::errors::error_with_exit_code;
+
+/// How long to wait for the file system to settle before emitting new metadata.
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(200);
 
 #[tracing::instrument(skip_all, level = "info")]
 pub fn run(args: MetadataArgs, config: &Config) -> Result<()> {
-    let ws = ops::read_workspace(config.manifest_path(), config)?;
-
     let features = args.features.try_into()?;
     let opts = ops::MetadataOptions {
         version: args.format_version,
         no_deps: args.no_deps,
         features,
         ignore_cairo_version: args.ignore_cairo_version,
+        unit: args.unit,
+        include_manifest_source: args.include_manifest_source,
+        include_target_defaults: args.include_target_defaults,
     };
 
-    let metadata = ops::collect_metadata(&opts, &ws)?;
-
-    config.ui().force_print(MachineMessage(metadata));
+    if args.watch {
+        watch(&opts, config)
+    } else if let Some(baseline) = args.diff {
+        let ws = ops::read_workspace(config.manifest_path(), config)?;
+        diff(&baseline, args.only_dependencies, &opts, &ws)
+    } else {
+        let ws = ops::read_workspace(config.manifest_path(), config)?;
+        emit(&opts, &ws)
+    }
+}
 
+fn emit(opts: &ops::MetadataOptions, ws: &Workspace<'_>) -> Result<()> {
+    let metadata = ops::collect_metadata(opts, ws)?;
+    ws.config().ui().force_print(MachineMessage(metadata));
     Ok(())
 }
+
+/// Emit metadata, and then emit it again whenever any workspace manifest or the lockfile changes.
+///
+/// Errors occurring while reading the workspace are reported, and watching continues, so that
+/// a temporarily broken manifest does not stop the watcher.
+fn watch(opts: &ops::MetadataOptions, config: &Config) -> Result<()> {
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(tx).context("failed to start file watcher")?;
+    let mut watched_dirs = BTreeSet::new();
+    let mut watched_files = BTreeSet::from([config.manifest_path().to_path_buf()]);
+
+    loop {
+        match ops::read_workspace(config.manifest_path(), config) {
+            Ok(ws) => {
+                watched_files = watched_paths(&ws);
+                if let Err(err) = emit(opts, &ws) {
+                    config.ui().anyhow(&err);
+                }
+            }
+            Err(err) => config.ui().anyhow(&err),
+        }
+
+        // Watch parent directories instead of files, because editors often save files
+        // by replacing them, which would silently end watching a file directly.
+        let dirs = watched_files
+            .iter()
+            .filter_map(|path| path.parent())
+            .map(Utf8Path::to_path_buf)
+            .collect::<BTreeSet<_>>();
+        for dir in dirs.difference(&watched_dirs) {
+            watcher
+                .watch(dir.as_std_path(), RecursiveMode::NonRecursive)
+                .with_context(|| format!("failed to watch directory: {dir}"))?;
+        }
+        for dir in watched_dirs.difference(&dirs) {
+            // The directory might have been removed in the meantime.
+            let _ = watcher.unwatch(dir.as_std_path());
+        }
+        watched_dirs = dirs;
+
+        // Resolution rewrites the lockfile, so only emit metadata again if contents of watched
+        // files actually differ from what has been used to produce the last emission.
+        let contents = read_contents(&watched_files);
+        loop {
+            wait_for_change(&rx, &watched_files)?;
+            if read_contents(&watched_files) != contents {
+                break;
+            }
+        }
+    }
+}
+
+fn read_contents(files: &BTreeSet<Utf8PathBuf>) -> Vec<Option<Vec<u8>>> {
+    files.iter().map(|path| fs::read(path).ok()).collect()
+}
+
+fn watched_paths(ws: &Workspace<'_>) -> BTreeSet<Utf8PathBuf> {
+    ws.members()
+        .map(|package| package.manifest_path().to_path_buf())
+        .chain([ws.manifest_path().to_path_buf(), ws.lockfile_path()])
+        .collect()
+}
+
+/// Block until any of the given files changes, and then until no more events arrive
+/// for [`WATCH_DEBOUNCE`].
+fn wait_for_change(
+    rx: &mpsc::Receiver<notify::Result<notify::Event>>,
+    files: &BTreeSet<Utf8PathBuf>,
+) -> Result<()> {
+    // Only parent directories of watched files are watched, so checking file names is enough.
+    // Comparing full paths is unreliable, as some platforms report canonicalized paths.
+    let file_names = files
+        .iter()
+        .filter_map(|path| path.file_name())
+        .collect::<BTreeSet<_>>();
+    let is_relevant = |event: notify::Result<notify::Event>| -> Result<bool> {
+        let event = event.context("failed to watch workspace files")?;
+        Ok(!event.kind.is_access()
+            && event.paths.iter().any(|path| {
+                path.file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| file_names.contains(name))
+            }))
+    };
+
+    while !is_relevant(rx.recv().context("file watcher disconnected")?)? {}
+    loop {
+        match rx.recv_timeout(WATCH_DEBOUNCE) {
+            Ok(event) => {
+                is_relevant(event)?;
+            }
+            Err(mpsc::RecvTimeoutError::Timeout) => return Ok(()),
+            Err(mpsc::RecvTimeoutError::Disconnected) => {
+                bail!("file watcher disconnected")
+            }
+        }
+    }
+}
+
+/// Compare metadata of the workspace against a baseline, and fail if packages differ.
+fn diff(
+    baseline: &Utf8Path,
+    only_dependencies: bool,
+    opts: &ops::MetadataOptions,
+    ws: &Workspace<'_>,
+) -> Result<()> {
+    let baseline: Metadata = fs::read_to_string(baseline)
+        .map_err(anyhow::Error::from)
+        .and_then(|contents| Ok(serde_json::from_str(&contents)?))
+        .with_context(|| format!("failed to read metadata baseline: {baseline}"))?;
+    let current = ops::collect_metadata(opts, ws)?;
+
+    let diff = MetadataDiff::new(&baseline, &current, only_dependencies);
+    let is_empty = diff.is_empty();
+    ws.config().ui().force_print(diff);
+    if is_empty {
+        Ok(())
+    } else {
+        error_with_exit_code(ExitCode::FAILURE)
+    }
+}
+
+#[derive(Serialize, Debug, Default)]
+struct MetadataDiff {
+    added: Vec<PackageSummary>,
+    removed: Vec<PackageSummary>,
+    changed: Vec<PackageChange>,
+}
+
+#[derive(Serialize, Debug)]
+struct PackageSummary {
+    name: String,
+    version: Version,
+    source: SourceId,
+}
+
+#[derive(Serialize, Debug)]
+struct PackageChange {
+    name: String,
+    old: PackageSummary,
+    new: PackageSummary,
+}
+
+impl PackageSummary {
+    fn new(package: &PackageMetadata) -> Self {
+        Self {
+            name: package.name.clone(),
+            version: package.version.clone(),
+            source: package.source.clone(),
+        }
+    }
+
+    fn differs_from(&self, other: &Self) -> bool {
+        // Path sources contain absolute paths, which differ between checkouts of the same code.
+        let is_path = |source: &SourceId| source.repr.starts_with("path+");
+        self.version != other.version
+            || (self.source != other.source && !(is_path(&self.source) && is_path(&other.source)))
+    }
+}
+
+impl MetadataDiff {
+    fn new(baseline: &Metadata, current: &Metadata, only_dependencies: bool) -> Self {
+        let packages = |metadata: &Metadata| {
+            metadata
+                .packages
+                .iter()
+                .filter(|package| {
+                    !only_dependencies || !metadata.workspace.members.contains(&package.id)
+                })
+                .map(|package| (package.id.repr.clone(), PackageSummary::new(package)))
+                .collect::<BTreeMap<_, _>>()
+        };
+        let mut old = packages(baseline);
+        let mut new = packages(current);
+        old.retain(|id, _| new.remove(id).is_none());
+
+        // Packages which are not present under the same ID are matched by name, as long as the
+        // name is not ambiguous on either side, so that version bumps are reported as changes.
+        let by_name = |packages: BTreeMap<String, PackageSummary>| {
+            let mut by_name = BTreeMap::<String, Vec<PackageSummary>>::new();
+            for package in packages.into_values() {
+                by_name
+                    .entry(package.name.clone())
+                    .or_default()
+                    .push(package);
+            }
+            by_name
+        };
+        let mut old = by_name(old);
+
+        let mut diff = Self::default();
+        for (name, mut new) in by_name(new) {
+            match old.remove(&name) {
+                Some(mut old) if old.len() == 1 && new.len() == 1 => {
+                    let (old, new) = (old.pop().unwrap(), new.pop().unwrap());
+                    if old.differs_from(&new) {
+                        diff.changed.push(PackageChange { name, old, new });
+                    }
+                }
+                old => {
+                    diff.added.extend(new);
+                    diff.removed.extend(old.into_iter().flatten());
+                }
+            }
+        }
+        diff.removed.extend(old.into_values().flatten());
+        diff
+    }
+
+    fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+impl Message for MetadataDiff {
+    fn text(self) -> String {
+        if self.is_empty() {
+            return "no differences found".to_string();
+        }
+        let mut text = String::new();
+        for package in &self.added {
+            writeln!(
+                text,
+                "added   {} v{} ({})",
+                package.name, package.version, package.source
+            )
+            .unwrap();
+        }
+        for package in &self.removed {
+            writeln!(
+                text,
+                "removed {} v{} ({})",
+                package.name, package.version, package.source
+            )
+            .unwrap();
+        }
+        for change in &self.changed {
+            write!(text, "changed {} v{}", change.name, change.old.version).unwrap();
+            if change.old.version != change.new.version {
+                write!(text, " -> v{}", change.new.version).unwrap();
+            }
+            if change.old.source != change.new.source {
+                write!(text, " ({} -> {})", change.old.source, change.new.source).unwrap();
+            }
+            writeln!(text).unwrap();
+        }
+        text.trim_end().to_string()
+    }
+
+    fn structured<S: Serializer>(self, ser: S) -> Result<S::Ok, S::Error> {
+        self.serialize(ser)
+    }
+}
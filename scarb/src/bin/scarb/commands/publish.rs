@@ -11,14 +11,21 @@ use crate::args::PublishArgs;
 #[tracing::instrument(skip_all, level = "info")]
 pub fn run(args: PublishArgs, config: &Config) -> Result<()> {
     let ws = ops::read_workspace(config.manifest_path(), config)?;
-    let package = args.packages_filter.match_one(&ws)?;
-    let index = match args.index {
+    let packages = if args.packages_filter.is_workspace() {
+        args.packages_filter.match_many(&ws)?
+    } else {
+        vec![args.packages_filter.match_one(&ws)?]
+    };
+    let index = match args
+        .index
+        .or_else(|| config.config_file().registry.index.clone())
+    {
         Some(index) => index,
         None => Url::from_str(DEFAULT_REGISTRY_INDEX)?,
     };
 
     let features_opts = args.features.try_into()?;
-    validate_features(&[package.clone()], &features_opts)?;
+    validate_features(&packages, &features_opts)?;
     let ops = PublishOpts {
         index_url: index,
         package_opts: PackageOpts {
@@ -28,7 +35,13 @@ pub fn run(args: PublishArgs, config: &Config) -> Result<()> {
             features: features_opts,
             ignore_cairo_version: args.ignore_cairo_version,
         },
+        dry_run: args.dry_run,
     };
 
-    ops::publish(package.id, &ops, &ws)
+    if args.packages_filter.is_workspace() {
+        let package_ids = packages.iter().map(|p| p.id).collect::<Vec<_>>();
+        ops::publish_many(&package_ids, &ops, &ws)
+    } else {
+        ops::publish(packages[0].id, &ops, &ws)
+    }
 }
@@ -7,7 +7,10 @@ use scarb::ops::ResolveOpts;
 #[tracing::instrument(skip_all, level = "info")]
 pub fn run(config: &Config) -> Result<()> {
     let ws = ops::read_workspace(config.manifest_path(), config)?;
-    let opts = ResolveOpts { update: true };
+    let opts = ResolveOpts {
+        update: true,
+        ..Default::default()
+    };
     ops::resolve_workspace_with_opts(&ws, &opts)?;
     Ok(())
 }
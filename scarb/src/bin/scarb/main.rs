@@ -1,4 +1,5 @@
 use std::env;
+use std::io::{self, IsTerminal};
 use std::process::ExitCode;
 use std::str::FromStr;
 
@@ -11,7 +12,7 @@ use args::ScarbArgs;
 use scarb::core::errors::ScriptExecutionError;
 use scarb::core::Config;
 use scarb::ops;
-use scarb_ui::Ui;
+use scarb_ui::{TextWrap, Ui};
 
 use crate::errors::ErrorWithExitCode;
 
@@ -24,7 +25,8 @@ fn main() -> ExitCode {
     let args = ScarbArgs::parse();
 
     // Pre-create Ui used in logging & error reporting, because we will move `args` to `cli_main`.
-    let ui = Ui::new(args.verbose.clone().into(), args.output_format());
+    let ui = Ui::new(args.verbose.clone().into(), args.output_format()).with_text_wrap(text_wrap());
+    args.color.apply(&ui);
 
     tracing_subscriber::fmt()
         .with_writer(std::io::stderr)
@@ -76,10 +78,22 @@ fn cli_main(args: ScarbArgs) -> Result<()> {
         .target_dir_override(args.target_dir)
         .ui_verbosity(args.verbose.clone().into())
         .ui_output_format(ui_output_format)
-        .offline(args.offline)
+        .ui_text_wrap(text_wrap())
+        .ui_color(args.color.color)
+        .offline(args.offline.then_some(true))
         .log_filter_directive(Some(scarb_log))
         .profile(args.profile_spec.determine()?)
         .build()?;
 
     commands::run(args.command, &mut config)
 }
+
+/// Wrap long status messages to the terminal width, but only when printing to a terminal, so that
+/// piped output and test snapshots stay verbatim.
+fn text_wrap() -> TextWrap {
+    if io::stdout().is_terminal() {
+        TextWrap::Terminal
+    } else {
+        TextWrap::Disabled
+    }
+}
@@ -1,6 +1,6 @@
 use anyhow::{ensure, Result};
 use cairo_lang_filesystem::cfg::CfgSet;
-use cairo_lang_filesystem::db::CrateIdentifier;
+use cairo_lang_filesystem::db::{CrateIdentifier, Edition};
 use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 use smol_str::SmolStr;
@@ -343,6 +343,13 @@ impl CompilationUnitComponent {
         &self.targets[0]
     }
 
+    /// Cairo edition of this component, which targets may override per package.
+    pub fn edition(&self) -> Edition {
+        self.first_target()
+            .edition
+            .unwrap_or(self.package.manifest.edition)
+    }
+
     pub fn target_kind(&self) -> TargetKind {
         self.first_target().kind.clone()
     }
@@ -15,6 +15,11 @@ impl Compiler for ExecutableCompiler {
         TargetKind::EXECUTABLE.clone()
     }
 
+    fn default_params(&self) -> toml::Value {
+        // Executable targets do not accept any parameters.
+        toml::Value::Table(Default::default())
+    }
+
     fn compile(
         &self,
         unit: CairoCompilationUnit,
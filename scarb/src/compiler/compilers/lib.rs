@@ -40,6 +40,10 @@ impl Compiler for LibCompiler {
         TargetKind::LIB.clone()
     }
 
+    fn default_params(&self) -> toml::Value {
+        toml::Value::try_from(Props::default()).expect("props should serialize to TOML")
+    }
+
     fn compile(
         &self,
         unit: CairoCompilationUnit,
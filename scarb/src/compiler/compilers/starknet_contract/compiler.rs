@@ -74,6 +74,10 @@ impl Compiler for StarknetContractCompiler {
         TargetKind::STARKNET_CONTRACT.clone()
     }
 
+    fn default_params(&self) -> toml::Value {
+        toml::Value::try_from(Props::default()).expect("props should serialize to TOML")
+    }
+
     fn compile(
         &self,
         unit: CairoCompilationUnit,
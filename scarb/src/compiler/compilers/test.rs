@@ -28,6 +28,12 @@ impl Compiler for TestCompiler {
         TargetKind::TEST.clone()
     }
 
+    fn default_params(&self) -> toml::Value {
+        // `test-type` is always set when test targets are generated, and no external contracts
+        // are built unless listed in the manifest.
+        toml::Value::Table(Default::default())
+    }
+
     fn compile(
         &self,
         unit: CairoCompilationUnit,
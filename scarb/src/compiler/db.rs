@@ -169,7 +169,7 @@ fn build_project_config(unit: &CairoCompilationUnit) -> Result<ProjectConfig> {
                 component.id.to_crate_identifier(),
                 CrateSettings {
                     name: Some(component.cairo_package_name()),
-                    edition: component.package.manifest.edition,
+                    edition: component.edition(),
                     cfg_set: component.cfg_set.clone(),
                     version: Some(component.package.id.version.clone()),
                     dependencies,
@@ -0,0 +1,182 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::Mutex;
+
+use anyhow::{Context, Result};
+use cairo_lang_filesystem::ids::CAIRO_FILE_EXTENSION;
+use camino::{Utf8Path, Utf8PathBuf};
+use scarb_stable_hash::StableHasher;
+use scarb_ui::Ui;
+use serde::{Deserialize, Serialize};
+use walkdir::WalkDir;
+
+use crate::compiler::{CairoCompilationUnit, CompilationUnitAttributes};
+use crate::core::Workspace;
+use crate::internal::fsx;
+use crate::internal::fsx::{is_hidden, PathBufUtf8Ext};
+use crate::version;
+
+/// Fingerprint of all inputs of a Cairo compilation unit.
+///
+/// The fingerprint covers the Scarb and Cairo compiler versions, the unit's configuration,
+/// and the manifest and Cairo source files of every component.
+/// It is saved in the target directory after the unit has been compiled successfully, along with
+/// the artifacts and warnings of the compilation, so that compiling the unit again can be skipped
+/// as long as none of its inputs change and its artifacts are still in place.
+#[derive(Debug)]
+pub struct UnitFingerprint {
+    hash: String,
+    path: Utf8PathBuf,
+    target_dir: Utf8PathBuf,
+}
+
+/// Contents of a saved fingerprint file.
+#[derive(Serialize, Deserialize)]
+struct SavedFingerprint {
+    hash: String,
+    /// Artifacts produced by the compilation, relative to the target directory of the unit.
+    artifacts: Vec<String>,
+    /// Warnings emitted by the compilation, reported again whenever compiling the unit is skipped.
+    warnings: Vec<UnitWarning>,
+}
+
+impl UnitFingerprint {
+    /// Compute the fingerprint of the given unit.
+    ///
+    /// Returns `None` for units which load procedural macros, as these can read arbitrary inputs
+    /// and must always be compiled.
+    pub fn try_new(unit: &CairoCompilationUnit, ws: &Workspace<'_>) -> Result<Option<Self>> {
+        if unit.cairo_plugins.iter().any(|plugin| !plugin.builtin) {
+            return Ok(None);
+        }
+
+        let mut hasher = StableHasher::new();
+        version::get().long().hash(&mut hasher);
+        unit.digest().hash(&mut hasher);
+        unit.cfg_set.hash(&mut hasher);
+        for plugin in &unit.cairo_plugins {
+            plugin.package.id.hash(&mut hasher);
+        }
+        for component in &unit.components {
+            component.cfg_set.hash(&mut hasher);
+            hash_file(component.package.manifest_path(), &mut hasher)?;
+            hash_sources(component.first_target().source_root(), &mut hasher)?;
+        }
+
+        let target_dir = unit.target_dir(ws).path_unchecked().to_path_buf();
+        let path = target_dir.join(".fingerprint").join(unit.id());
+        Ok(Some(Self {
+            hash: hasher.finish_as_short_hash(),
+            path,
+            target_dir,
+        }))
+    }
+
+    /// Stable hash of all inputs of the unit.
+    pub fn hash(&self) -> &str {
+        &self.hash
+    }
+
+    /// Check whether the unit has been compiled successfully with exactly the same inputs, and all
+    /// artifacts of that compilation are still in place.
+    ///
+    /// Returns the warnings emitted by that compilation if so.
+    pub fn check_fresh(&self) -> Option<Vec<UnitWarning>> {
+        let saved = fsx::read_to_string(&self.path).ok()?;
+        let saved: SavedFingerprint = serde_json::from_str(&saved).ok()?;
+        let is_fresh = saved.hash == self.hash
+            && saved
+                .artifacts
+                .iter()
+                .all(|artifact| self.target_dir.join(artifact).is_file());
+        is_fresh.then_some(saved.warnings)
+    }
+
+    /// Record that the unit has been compiled successfully, producing the given artifacts and
+    /// emitting the given warnings.
+    ///
+    /// Artifact names are relative to the target directory of the unit.
+    pub fn save(&self, artifacts: Vec<String>, warnings: Vec<UnitWarning>) -> Result<()> {
+        let saved = SavedFingerprint {
+            hash: self.hash.clone(),
+            artifacts,
+            warnings,
+        };
+        fsx::create_dir_all(self.path.parent().expect("fingerprint path has a parent"))?;
+        fsx::write(&self.path, serde_json::to_string(&saved)?)
+    }
+
+    /// Forget the last successful compilation of the unit.
+    ///
+    /// Done before compiling, so that a failed compilation does not leave a stale fingerprint of
+    /// previous inputs behind.
+    pub fn clear(&self) -> Result<()> {
+        if self.path.exists() {
+            fsx::remove_file(&self.path)?;
+        }
+        Ok(())
+    }
+}
+
+/// A compiler warning emitted while compiling a unit.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct UnitWarning {
+    code: Option<String>,
+    message: String,
+}
+
+impl UnitWarning {
+    /// Report the warning again, the same way the compiler reported it.
+    pub fn print(&self, ui: &Ui) {
+        match &self.code {
+            Some(code) => ui.warn_with_code(code, &self.message),
+            None => ui.warn(&self.message),
+        }
+    }
+}
+
+/// Compiler warnings emitted while compiling units, not yet saved with their fingerprints.
+#[derive(Debug, Default)]
+pub struct UnitWarnings(Mutex<HashMap<String, Vec<UnitWarning>>>);
+
+impl UnitWarnings {
+    /// Record a compiler warning emitted while compiling the given unit.
+    pub fn record(&self, unit_id: &str, code: Option<&str>, message: &str) {
+        self.0
+            .lock()
+            .unwrap()
+            .entry(unit_id.to_string())
+            .or_default()
+            .push(UnitWarning {
+                code: code.map(ToString::to_string),
+                message: message.to_string(),
+            });
+    }
+
+    /// Take all warnings recorded for the given unit.
+    pub fn take(&self, unit_id: &str) -> Vec<UnitWarning> {
+        self.0.lock().unwrap().remove(unit_id).unwrap_or_default()
+    }
+}
+
+fn hash_file(path: &Utf8Path, hasher: &mut StableHasher) -> Result<()> {
+    path.as_str().hash(hasher);
+    fsx::read(path)?.hash(hasher);
+    Ok(())
+}
+
+/// Hash all Cairo files in the given source root, in a stable order.
+fn hash_sources(root: &Utf8Path, hasher: &mut StableHasher) -> Result<()> {
+    let walker = WalkDir::new(root)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|entry| entry.depth() == 0 || !is_hidden(entry.path()));
+    for entry in walker {
+        let entry = entry.with_context(|| format!("failed to traverse directory: {root}"))?;
+        let path = entry.into_path().try_into_utf8()?;
+        if path.is_file() && path.extension() == Some(CAIRO_FILE_EXTENSION) {
+            hash_file(&path, hasher)?;
+        }
+    }
+    Ok(())
+}
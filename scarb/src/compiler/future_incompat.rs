@@ -0,0 +1,162 @@
+use std::collections::BTreeMap;
+
+use anyhow::{Context, Result};
+use cairo_lang_compiler::db::RootDatabase;
+use cairo_lang_defs::db::DefsGroup;
+use cairo_lang_diagnostics::{format_diagnostics, DiagnosticEntry};
+use cairo_lang_filesystem::ids::CrateId;
+use cairo_lang_semantic::db::SemanticGroup;
+use cairo_lang_semantic::diagnostic::SemanticDiagnosticKind;
+use cairo_lang_utils::Upcast;
+use camino::Utf8PathBuf;
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+use crate::compiler::{CairoCompilationUnit, CompilationUnitAttributes};
+use crate::core::{Config, PackageId, Workspace};
+use crate::internal::fsx;
+
+/// Collect compiler warnings about code of the given crates, which will be rejected by a future
+/// version of Cairo.
+///
+/// Usages of deprecated features are the only future incompatibilities that the compiler reports.
+/// They are recognized by the kind of the semantic diagnostic, which is already computed by the
+/// time a unit has been compiled.
+pub fn collect_future_incompat_notices(db: &RootDatabase, crate_ids: &[CrateId]) -> Vec<String> {
+    crate_ids
+        .iter()
+        .flat_map(|crate_id| db.crate_modules(*crate_id).iter().copied().collect_vec())
+        .flat_map(|module_id| db.module_semantic_diagnostics(module_id).ok())
+        .flat_map(|diagnostics| diagnostics.get_diagnostics_without_duplicates(db))
+        .filter(|diagnostic| {
+            matches!(
+                diagnostic.kind,
+                SemanticDiagnosticKind::DeprecatedFeature { .. }
+            )
+        })
+        .map(|diagnostic| {
+            let location = diagnostic.location(db).user_location(db.upcast());
+            let notice = format_diagnostics(db.upcast(), &diagnostic.format(db), location);
+            notice.trim_end().to_string()
+        })
+        .unique()
+        .collect()
+}
+
+/// File with future incompatibility notices of the last compilation of a unit.
+///
+/// Notices are kept per unit, so that they are reported even when a later build skips compiling
+/// the unit, because its inputs have not changed.
+pub struct UnitNoticesFile {
+    package: PackageId,
+    path: Utf8PathBuf,
+}
+
+impl UnitNoticesFile {
+    pub fn new(unit: &CairoCompilationUnit, ws: &Workspace<'_>) -> Self {
+        Self {
+            package: unit.main_package_id(),
+            path: unit
+                .target_dir(ws)
+                .path_unchecked()
+                .join(".future-incompat")
+                .join(format!("{}.json", unit.id())),
+        }
+    }
+
+    pub fn save(&self, notices: &[String]) -> Result<()> {
+        if notices.is_empty() {
+            if self.path.exists() {
+                fsx::remove_file(&self.path)?;
+            }
+            return Ok(());
+        }
+        fsx::create_dir_all(self.path.parent().expect("notices path has a parent"))?;
+        fsx::write(&self.path, serde_json::to_string(notices)?)
+    }
+
+    fn load(&self) -> Result<Vec<String>> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+        serde_json::from_str(&fsx::read_to_string(&self.path)?).with_context(|| {
+            format!(
+                "failed to parse future incompatibility notices: {}",
+                self.path
+            )
+        })
+    }
+}
+
+/// Summary of future incompatibility notices of packages compiled in the latest build.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct FutureIncompatReport {
+    pub packages: Vec<FutureIncompatPackage>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FutureIncompatPackage {
+    pub package: String,
+    pub notices: Vec<String>,
+}
+
+impl FutureIncompatReport {
+    pub const FILE_NAME: &'static str = "future-incompat-report.json";
+
+    /// Gather notices of the last compilation of all given units.
+    pub fn collect(units: &[UnitNoticesFile]) -> Result<Self> {
+        let mut packages: BTreeMap<PackageId, Vec<String>> = BTreeMap::new();
+        for unit in units {
+            packages
+                .entry(unit.package)
+                .or_default()
+                .extend(unit.load()?);
+        }
+        let packages = packages
+            .into_iter()
+            .filter(|(_, notices)| !notices.is_empty())
+            .map(|(package, notices)| FutureIncompatPackage {
+                package: package.to_string(),
+                notices: notices.into_iter().unique().collect(),
+            })
+            .collect();
+        Ok(Self { packages })
+    }
+
+    /// Persist the report in the target directory, replacing the previous one.
+    pub fn save(&self, ws: &Workspace<'_>) -> Result<()> {
+        let target_dir = ws.target_dir().path_existent()?;
+        fsx::write(
+            target_dir.join(Self::FILE_NAME),
+            serde_json::to_string_pretty(self)?,
+        )
+    }
+
+    pub fn print(&self, config: &Config) {
+        if self.packages.is_empty() {
+            config.ui().print(
+                "note: no packages contain code that will be rejected by a future version of Cairo",
+            );
+            return;
+        }
+
+        config.ui().warn(format!(
+            "the following packages contain code that will be rejected by a future version of \
+            Cairo: {}",
+            self.packages.iter().map(|p| &p.package).join(", ")
+        ));
+        for package in &self.packages {
+            let notices = package
+                .notices
+                .iter()
+                .flat_map(|notice| notice.lines())
+                .map(|line| format!("> {line}"))
+                .join("\n");
+            config.ui().print(format!(
+                "The package `{}` currently triggers the following future incompatibility \
+                notices:\n{notices}",
+                package.package
+            ));
+        }
+    }
+}
@@ -53,8 +53,9 @@ pub fn build_compiler_config<'c>(
         .collect_vec();
     let diagnostics_reporter = DiagnosticsReporter::callback({
         let config = ws.config();
+        let unit_id = unit.id();
 
-        |entry: FormattedDiagnosticEntry| {
+        move |entry: FormattedDiagnosticEntry| {
             let msg = entry
                 .message()
                 .strip_suffix('\n')
@@ -68,6 +69,11 @@ pub fn build_compiler_config<'c>(
                     }
                 }
                 Severity::Warning => {
+                    config.unit_warnings().record(
+                        &unit_id,
+                        entry.error_code().as_ref().map(|code| code.as_str()),
+                        msg,
+                    );
                     if let Some(code) = entry.error_code() {
                         config.ui().warn_with_code(code.as_str(), msg)
                     } else {
@@ -124,6 +130,15 @@ pub fn collect_main_crate_ids(unit: &CairoCompilationUnit, db: &RootDatabase) ->
     })]
 }
 
+/// Convert a value to JSON with all object keys sorted.
+///
+/// Some compiler outputs contain maps with nondeterministic iteration order (like `HashMap`).
+/// Objects in [`serde_json::Value`] are always sorted by key, so serializing artifacts through it
+/// makes them byte-identical across builds of the same input.
+fn to_deterministic_json(value: impl Serialize) -> serde_json::Result<serde_json::Value> {
+    serde_json::to_value(value)
+}
+
 pub fn write_json(
     file_name: &str,
     description: &str,
@@ -131,6 +146,8 @@ pub fn write_json(
     ws: &Workspace<'_>,
     value: impl Serialize,
 ) -> Result<()> {
+    let value =
+        to_deterministic_json(value).with_context(|| format!("failed to serialize {file_name}"))?;
     let file = target_dir.create_rw(file_name, description, ws.config())?;
     let file = BufWriter::new(&*file);
     serde_json::to_writer(file, &value)
@@ -145,6 +162,8 @@ pub fn write_json_with_byte_count(
     ws: &Workspace<'_>,
     value: impl Serialize,
 ) -> Result<usize> {
+    let value =
+        to_deterministic_json(value).with_context(|| format!("failed to serialize {file_name}"))?;
     let file = target_dir.create_rw(file_name, description, ws.config())?;
     let file = BufWriter::new(&*file);
     let mut writer = CountingWriter::new(file);
@@ -164,3 +183,22 @@ pub fn write_string(
     file.write_all(value.to_string().as_bytes())?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use itertools::Itertools;
+
+    use super::to_deterministic_json;
+
+    #[test]
+    fn deterministic_json_sorts_keys() {
+        let map = (0..32)
+            .map(|i| (format!("key{i:02}"), i))
+            .collect::<HashMap<_, _>>();
+        let json = serde_json::to_string(&to_deterministic_json(&map).unwrap()).unwrap();
+        let expected = (0..32).map(|i| format!(r#""key{i:02}":{i}"#)).join(",");
+        assert_eq!(json, format!("{{{expected}}}"));
+    }
+}
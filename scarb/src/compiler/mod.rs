@@ -2,6 +2,8 @@ use anyhow::Result;
 use cairo_lang_compiler::db::RootDatabase;
 
 pub use compilation_unit::*;
+pub use fingerprint::*;
+pub use future_incompat::*;
 pub use profile::*;
 pub use repository::*;
 
@@ -10,6 +12,8 @@ use crate::core::{TargetKind, Workspace};
 mod compilation_unit;
 mod compilers;
 pub mod db;
+mod fingerprint;
+mod future_incompat;
 pub mod helpers;
 pub mod plugin;
 mod profile;
@@ -18,6 +22,9 @@ mod repository;
 pub trait Compiler: Sync {
     fn target_kind(&self) -> TargetKind;
 
+    /// Values of target parameters which this compiler uses, unless set in the manifest.
+    fn default_params(&self) -> toml::Value;
+
     fn compile(
         &self,
         unit: CairoCompilationUnit,
@@ -1,5 +1,5 @@
 use std::collections::hash_map::Entry;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fmt;
 
 use crate::compiler::plugin::builtin::{BuiltinExecutablePlugin, BuiltinTestAssertsPlugin};
@@ -10,7 +10,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::compiler::plugin::builtin::BuiltinCairoRunPlugin;
 use crate::compiler::plugin::proc_macro::compilation::SharedLibraryProvider;
-use crate::core::{Package, PackageId, TargetKind, Workspace};
+use crate::core::{Package, PackageId, TargetKind, TomlCairoPluginPrebuilt, Workspace};
 
 use self::builtin::{BuiltinStarknetPlugin, BuiltinTestPlugin};
 
@@ -24,6 +24,9 @@ pub struct CairoPluginProps {
     /// Mark this macro plugin as builtin.
     /// Builtin plugins are assumed to be available in `CairoPluginRepository` for the whole Scarb execution.
     pub builtin: bool,
+    /// Prebuilt shared libraries of this macro plugin, keyed by target triple.
+    #[serde(default)]
+    pub prebuilt: BTreeMap<String, TomlCairoPluginPrebuilt>,
 }
 
 pub fn fetch_cairo_plugin(package: &Package, ws: &Workspace<'_>) -> Result<()> {
@@ -32,8 +35,8 @@ pub fn fetch_cairo_plugin(package: &Package, ws: &Workspace<'_>) -> Result<()> {
     let props: CairoPluginProps = target.props()?;
     // There is no need to run `cargo fetch` for builtin plugins.
     // The `fetch` will not be run for a proc macro that contains a prebuilt library file.
-    // Note, that in case the prebuilt lib file is corrupted, it will be later compiled with Cargo anyway.
-    if !props.builtin && package.prebuilt_lib_path().is_none() {
+    // Note, that errors of a declared prebuilt library are reported when the library is loaded.
+    if !props.builtin && !matches!(package.prebuilt_lib_path(), Ok(Some(_))) {
         proc_macro::fetch_crate(package, ws)?;
     }
     Ok(())
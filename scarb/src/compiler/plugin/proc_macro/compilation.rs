@@ -1,12 +1,13 @@
+use crate::compiler::plugin::CairoPluginProps;
 use crate::compiler::ProcMacroCompilationUnit;
-use crate::core::{Config, Package, Workspace};
+use crate::core::{Checksum, Config, Package, TargetKind, Workspace};
 use crate::flock::Filesystem;
 use crate::internal::fsx;
 use crate::ops::PackageOpts;
 use crate::process::exec_piping;
 use crate::CARGO_MANIFEST_FILE_NAME;
-use anyhow::{anyhow, Context, Result};
-use camino::Utf8PathBuf;
+use anyhow::{anyhow, ensure, Context, Result};
+use camino::{Utf8Component, Utf8Path, Utf8PathBuf};
 use cargo_metadata::MetadataCommand;
 use flate2::read::GzDecoder;
 use indoc::formatdoc;
@@ -15,12 +16,16 @@ use ra_ap_toolchain::Tool;
 use scarb_ui::{Message, OutputFormat};
 use serde::{Serialize, Serializer};
 use serde_json::value::RawValue;
+use std::collections::HashMap;
 use std::env::consts::DLL_SUFFIX;
 use std::fmt::Display;
 use std::fs;
+use std::fs::File;
 use std::io::{Seek, SeekFrom};
 use std::ops::Deref;
 use std::process::Command;
+use std::sync::{LazyLock, Mutex};
+use std::time::SystemTime;
 use tar::Archive;
 use target_triple::target;
 use tracing::trace_span;
@@ -34,7 +39,7 @@ pub trait SharedLibraryProvider {
     /// Location of the shared library for the package.
     fn shared_lib_path(&self, config: &Config) -> Result<Utf8PathBuf>;
     /// Location of the prebuilt binary for the package, if defined.
-    fn prebuilt_lib_path(&self) -> Option<Utf8PathBuf>;
+    fn prebuilt_lib_path(&self) -> Result<Option<Utf8PathBuf>>;
 }
 
 impl SharedLibraryProvider for Package {
@@ -66,9 +71,13 @@ impl SharedLibraryProvider for Package {
             .join(lib_name))
     }
 
-    fn prebuilt_lib_path(&self) -> Option<Utf8PathBuf> {
+    fn prebuilt_lib_path(&self) -> Result<Option<Utf8PathBuf>> {
         let target_triple = target!();
 
+        if let Some(path) = declared_prebuilt_lib_path(self, target_triple)? {
+            return Ok(Some(path));
+        }
+
         let prebuilt_name = format!(
             "{name}_v{version}_{target}{suffix}",
             name = self.id.name,
@@ -84,8 +93,79 @@ impl SharedLibraryProvider for Package {
             .join("cairo-plugin")
             .join(prebuilt_name);
 
-        prebuilt_path.exists().then_some(prebuilt_path)
+        Ok(prebuilt_path.exists().then_some(prebuilt_path))
+    }
+}
+
+/// Checksums of prebuilt libraries computed so far, keyed by library path and modification time.
+///
+/// Prebuilt libraries are looked up several times during a single build, and hashing them
+/// each time would be wasteful.
+static PREBUILT_CHECKSUMS: LazyLock<Mutex<HashMap<(Utf8PathBuf, SystemTime), Checksum>>> =
+    LazyLock::new(Default::default);
+
+/// Find the prebuilt library declared for the given target triple in the manifest of the package.
+///
+/// Fails if the declared path points outside the package root, or if the library does not match
+/// its declared checksum.
+fn declared_prebuilt_lib_path(
+    package: &Package,
+    target_triple: &str,
+) -> Result<Option<Utf8PathBuf>> {
+    let Some(target) = package.target(&TargetKind::CAIRO_PLUGIN) else {
+        return Ok(None);
+    };
+    let props: CairoPluginProps = target.props()?;
+    let Some(prebuilt) = props.prebuilt.get(target_triple) else {
+        return Ok(None);
+    };
+    ensure!(
+        is_within_package_root(&prebuilt.path),
+        "path of prebuilt library `{}` must be relative to the package root and stay within it",
+        prebuilt.path
+    );
+    let path = package.root().join(&prebuilt.path);
+    let checksum = prebuilt_lib_checksum(&path, &prebuilt.checksum)
+        .with_context(|| format!("failed to verify prebuilt library: {path}"))?;
+    ensure!(
+        checksum == prebuilt.checksum,
+        "checksum of prebuilt library `{path}` does not match, expected `{}`, got `{checksum}`",
+        prebuilt.checksum
+    );
+    Ok(Some(path))
+}
+
+/// Check that a relative path does not leave the directory it is relative to.
+fn is_within_package_root(path: &Utf8Path) -> bool {
+    let mut depth = 0usize;
+    for component in path.components() {
+        match component {
+            Utf8Component::Normal(_) => depth += 1,
+            Utf8Component::CurDir => {}
+            Utf8Component::ParentDir => match depth.checked_sub(1) {
+                Some(parent) => depth = parent,
+                None => return false,
+            },
+            Utf8Component::RootDir | Utf8Component::Prefix(_) => return false,
+        }
+    }
+    true
+}
+
+/// Compute the checksum of a prebuilt library file, using the algorithm of the declared checksum.
+fn prebuilt_lib_checksum(path: &Utf8Path, declared: &Checksum) -> Result<Checksum> {
+    let modified = path.metadata()?.modified()?;
+    let key = (path.to_path_buf(), modified);
+    if let Some(checksum) = PREBUILT_CHECKSUMS.lock().unwrap().get(&key) {
+        return Ok(checksum.clone());
     }
+    let file = File::open(path)?;
+    let checksum = declared.digest().update_read(file)?.finish();
+    PREBUILT_CHECKSUMS
+        .lock()
+        .unwrap()
+        .insert(key, checksum.clone());
+    Ok(checksum)
 }
 
 pub fn compile_unit(unit: ProcMacroCompilationUnit, ws: &Workspace<'_>) -> Result<()> {
@@ -93,6 +173,20 @@ pub fn compile_unit(unit: ProcMacroCompilationUnit, ws: &Workspace<'_>) -> Resul
     run_cargo(CargoAction::Build, &package, ws)
 }
 
+/// Build the unit like [`compile_unit`], alongside other builds running concurrently.
+///
+/// Cargo is limited to `jobs` parallel jobs, and each line of its textual output is prefixed
+/// with the package name, so that output of concurrent builds can be told apart.
+pub fn compile_unit_concurrently(
+    unit: ProcMacroCompilationUnit,
+    jobs: usize,
+    ws: &Workspace<'_>,
+) -> Result<()> {
+    let package = unit.components.first().unwrap().package.clone();
+    let prefix = package.id.name.to_string();
+    run_cargo_with(CargoAction::Build, &package, Some(jobs), Some(&prefix), ws)
+}
+
 pub fn check_unit(unit: ProcMacroCompilationUnit, ws: &Workspace<'_>) -> Result<()> {
     let package = unit.components.first().unwrap().package.clone();
     run_cargo(CargoAction::Check, &package, ws)
@@ -215,8 +309,19 @@ pub fn package_crate(package: &Package, opts: &PackageOpts, ws: &Workspace<'_>)
 }
 
 fn run_cargo(action: CargoAction, package: &Package, ws: &Workspace<'_>) -> Result<()> {
+    run_cargo_with(action, package, None, None, ws)
+}
+
+fn run_cargo_with(
+    action: CargoAction,
+    package: &Package,
+    jobs: Option<usize>,
+    output_prefix: Option<&str>,
+    ws: &Workspace<'_>,
+) -> Result<()> {
     let cmd = CargoCommand {
         action,
+        jobs,
         current_dir: package.root().to_path_buf(),
         output_format: ws.config().ui().output_format(),
         target_dir: package
@@ -228,7 +333,7 @@ fn run_cargo(action: CargoAction, package: &Package, ws: &Workspace<'_>) -> Resu
     let span = trace_span!("proc_macro");
     {
         let _guard = span.enter();
-        exec(&mut cmd.into(), ws.config())?;
+        exec(&mut cmd.into(), output_prefix, ws.config())?;
     }
     Ok(())
 }
@@ -243,6 +348,7 @@ enum CargoAction {
 
 struct CargoCommand<'c> {
     current_dir: Utf8PathBuf,
+    jobs: Option<usize>,
     target_dir: Utf8PathBuf,
     output_format: OutputFormat,
     action: CargoAction,
@@ -276,6 +382,7 @@ impl<'c> From<CargoCommand<'c>> for Command {
     fn from(args: CargoCommand<'c>) -> Self {
         let mut cmd = Command::new(Tool::Cargo.path());
         cmd.current_dir(args.current_dir);
+        cmd.envs(args.config.env());
         match args.action {
             CargoAction::Fetch => cmd.arg("fetch"),
             CargoAction::Build => cmd.arg("build"),
@@ -305,19 +412,28 @@ impl<'c> From<CargoCommand<'c>> for Command {
                 cmd.arg(output_format.to_string());
                 cmd.arg("--target-dir");
                 cmd.arg(args.target_dir);
+                if let Some(jobs) = args.jobs {
+                    cmd.arg("--jobs");
+                    cmd.arg(jobs.to_string());
+                }
             }
         }
         cmd
     }
 }
 
-fn exec(cmd: &mut Command, config: &Config) -> Result<()> {
-    exec_piping(
-        cmd,
-        config,
-        |line: &str| config.ui().print(PipedText::new(line)),
-        |line: &str| config.ui().print(PipedText::new(line)),
-    )
+fn exec(cmd: &mut Command, output_prefix: Option<&str>, config: &Config) -> Result<()> {
+    let print = |line: &str| {
+        let text = match output_prefix {
+            // Structured messages of Cargo are self-describing, so only text needs a prefix.
+            Some(prefix) if config.ui().output_format() == OutputFormat::Text => {
+                format!("[{prefix}] {line}")
+            }
+            _ => line.to_string(),
+        };
+        config.ui().print(PipedText::new(text))
+    };
+    exec_piping(cmd, config, print, print)
 }
 
 /// This message can be used for piped text from subprocesses.
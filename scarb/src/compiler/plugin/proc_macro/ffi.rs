@@ -75,7 +75,7 @@ impl ProcMacroInstance {
     pub fn try_load_prebuilt(package: Package) -> Result<Self> {
         trace!("loading prebuilt macro for `{}` package", package.id);
         let prebuilt_path = package
-            .prebuilt_lib_path()
+            .prebuilt_lib_path()?
             .context("could not resolve prebuilt library path")?;
         let plugin = unsafe { Plugin::try_new(prebuilt_path)? };
         Ok(Self {
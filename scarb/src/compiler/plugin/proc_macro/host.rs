@@ -30,7 +30,7 @@ use cairo_lang_syntax::node::ids::SyntaxStablePtrId;
 use cairo_lang_syntax::node::{ast, Terminal, TypedStablePtr, TypedSyntaxNode};
 use convert_case::{Case, Casing};
 use itertools::Itertools;
-use scarb_stable_hash::short_hash;
+use scarb_stable_hash::stable_hash_path;
 use smol_str::SmolStr;
 use std::any::Any;
 use std::collections::{HashMap, HashSet};
@@ -123,6 +123,29 @@ impl IntoIterator for EmittedAuxData {
 
 impl ProcMacroHostPlugin {
     pub fn try_new(macros: Vec<Arc<ProcMacroInstance>>) -> Result<Self> {
+        // Validate executable attributes.
+        // These would be reported as duplicate expansions below, but executable attributes
+        // deserve a more specific error message, as they are registered implicitly.
+        let mut executables = macros
+            .iter()
+            .flat_map(|m| {
+                m.executable_attributes()
+                    .into_iter()
+                    .map(|name| (name, m.package_id()))
+                    .collect_vec()
+            })
+            .collect::<Vec<_>>();
+        executables.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+        ensure!(
+            executables.windows(2).all(|w| w[0].0 != w[1].0),
+            "duplicate executable attributes defined for procedural macros: {duplicates}",
+            duplicates = executables
+                .windows(2)
+                .filter(|w| w[0].0 == w[1].0)
+                .map(|w| format!("{} ({} and {})", w[0].0, w[0].1, w[1].1))
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
         // Validate expansions.
         let mut expansions = macros
             .iter()
@@ -848,7 +871,7 @@ impl ProcMacroHostPlugin {
     fn calculate_metadata(db: &dyn SyntaxGroup, item_ast: ast::ModuleItem) -> TokenStreamMetadata {
         let stable_ptr = item_ast.clone().stable_ptr().untyped();
         let file_path = stable_ptr.file_id(db).full_path(db.upcast());
-        let file_id = short_hash(file_path.clone());
+        let file_id = stable_hash_path(&file_path);
         TokenStreamMetadata::new(file_path, file_id)
     }
 }
@@ -2,6 +2,6 @@ pub mod compilation;
 mod ffi;
 mod host;
 
-pub use compilation::{check_unit, compile_unit, fetch_crate};
+pub use compilation::{check_unit, compile_unit, compile_unit_concurrently, fetch_crate};
 pub use ffi::*;
 pub use host::*;
@@ -11,7 +11,7 @@ use crate::compiler::compilers::{
     ExecutableCompiler, LibCompiler, StarknetContractCompiler, TestCompiler,
 };
 use crate::compiler::{CairoCompilationUnit, CompilationUnitAttributes, Compiler};
-use crate::core::Workspace;
+use crate::core::{TargetKind, Workspace};
 
 pub struct CompilerRepository {
     compilers: HashMap<SmolStr, Box<dyn Compiler>>,
@@ -44,6 +44,14 @@ impl CompilerRepository {
         }
     }
 
+    /// Values of target parameters which the compiler of the given target kind uses, unless set
+    /// in the manifest, or `None` if there is no compiler for this target kind.
+    pub fn default_params(&self, target_kind: &TargetKind) -> Option<toml::Value> {
+        self.compilers
+            .get(target_kind.as_str())
+            .map(|compiler| compiler.default_params())
+    }
+
     pub fn compile(
         &self,
         unit: CairoCompilationUnit,
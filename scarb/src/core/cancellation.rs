@@ -0,0 +1,59 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use anyhow::Result;
+
+use crate::core::errors::Cancelled;
+
+/// Shared flag used to cancel long-running operations, like builds or workspace resolution,
+/// from another thread.
+///
+/// Operations check the token at safe points, for example, between compilation units,
+/// and return a [`Cancelled`] error once cancellation has been requested.
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Request cancellation of all operations observing this token.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+
+    /// Fail with [`Cancelled`] error if cancellation has been requested.
+    pub fn check(&self) -> Result<()> {
+        if self.is_cancelled() {
+            Err(Cancelled.into())
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Check an optional [`CancellationToken`], succeeding if no token is present.
+pub(crate) fn check_cancelled(token: Option<&CancellationToken>) -> Result<()> {
+    token.map_or(Ok(()), CancellationToken::check)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CancellationToken;
+    use crate::core::errors::Cancelled;
+
+    #[test]
+    fn cancel_is_visible_to_clones() {
+        let token = CancellationToken::new();
+        let clone = token.clone();
+        assert!(clone.check().is_ok());
+        token.cancel();
+        assert!(clone.is_cancelled());
+        assert!(clone.check().unwrap_err().is::<Cancelled>());
+    }
+}
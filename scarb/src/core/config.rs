@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::ffi::{OsStr, OsString};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
@@ -11,13 +12,14 @@ use tokio::runtime::{Builder, Handle, Runtime};
 use tracing::trace;
 use which::which_in;
 
-use scarb_ui::{OutputFormat, Ui, Verbosity};
+use scarb_ui::args::ColorChoice;
+use scarb_ui::{OutputFormat, TextWrap, Ui, Verbosity};
 
 use crate::compiler::plugin::CairoPluginRepository;
-use crate::compiler::{CompilerRepository, Profile};
-use crate::core::AppDirs;
+use crate::compiler::{CompilerRepository, Profile, UnitWarnings};
 #[cfg(doc)]
 use crate::core::Workspace;
+use crate::core::{AppDirs, ConfigFile};
 use crate::flock::AdvisoryLock;
 use crate::internal::fsx;
 use crate::SCARB_ENV;
@@ -32,6 +34,7 @@ pub struct Config {
     target_dir_override: Option<Utf8PathBuf>,
     app_exe: OnceCell<PathBuf>,
     ui: Ui,
+    ui_color: ColorChoice,
     creation_time: Instant,
     // HACK: This should be the lifetime of Config itself, but we cannot express that, so we
     //   put static lifetime here and transmute in getter function.
@@ -46,6 +49,9 @@ pub struct Config {
     tokio_handle: OnceCell<Handle>,
     profile: Profile,
     http_client: OnceCell<reqwest::Client>,
+    config_file: ConfigFile,
+    env: BTreeMap<OsString, OsString>,
+    unit_warnings: UnitWarnings,
 }
 
 impl Config {
@@ -56,7 +62,7 @@ impl Config {
     fn build(b: ConfigBuilder) -> Result<Self> {
         let creation_time = Instant::now();
 
-        let ui = Ui::new(b.ui_verbosity, b.ui_output_format);
+        let ui = Ui::new(b.ui_verbosity, b.ui_output_format).with_text_wrap(b.ui_text_wrap);
 
         let dirs = Arc::new(AppDirs::init(
             b.global_cache_dir_override,
@@ -70,9 +76,23 @@ impl Config {
             }
         }
 
+        let workspace_root = b
+            .manifest_path
+            .parent()
+            .expect("parent of manifest path must always exist");
+        let config_file = ConfigFile::load(&dirs, workspace_root)?;
+
+        let profile: Profile = b.profile.unwrap_or_default();
+
+        let env = config_file
+            .profile_env(&profile)
+            .into_iter()
+            .filter(|(key, value)| value.force() || env::var_os(key).is_none())
+            .map(|(key, value)| (OsString::from(key), OsString::from(value.value())))
+            .collect();
+
         let compilers = b.compilers.unwrap_or_else(CompilerRepository::std);
         let compiler_plugins = b.cairo_plugins.unwrap_or_else(CairoPluginRepository::std);
-        let profile: Profile = b.profile.unwrap_or_default();
         let tokio_handle: OnceCell<Handle> = OnceCell::new();
         if let Some(handle) = b.tokio_handle {
             tokio_handle.set(handle).unwrap();
@@ -84,10 +104,11 @@ impl Config {
             target_dir_override: b.target_dir_override,
             app_exe: OnceCell::new(),
             ui,
+            ui_color: b.ui_color,
             creation_time,
             package_cache_lock: OnceCell::new(),
             log_filter_directive: b.log_filter_directive.unwrap_or_default(),
-            offline: b.offline,
+            offline: b.offline.or(config_file.net.offline).unwrap_or(false),
             compilers,
             cairo_plugins: compiler_plugins,
             custom_source_patches: b.custom_source_patches,
@@ -95,6 +116,9 @@ impl Config {
             tokio_handle,
             profile,
             http_client: OnceCell::new(),
+            config_file,
+            env,
+            unit_warnings: UnitWarnings::default(),
         })
     }
 
@@ -174,6 +198,11 @@ impl Config {
         self.ui.clone()
     }
 
+    /// Coloring of the output, as requested by the user, to be passed to spawned processes.
+    pub fn ui_color(&self) -> ColorChoice {
+        self.ui_color
+    }
+
     pub fn set_ui(&mut self, ui: Ui) {
         self.ui = ui;
     }
@@ -234,6 +263,11 @@ impl Config {
         &self.cairo_plugins
     }
 
+    /// Warnings emitted by the compiler, not yet saved with fingerprints of their units.
+    pub fn unit_warnings(&self) -> &UnitWarnings {
+        &self.unit_warnings
+    }
+
     pub fn custom_source_patches(&self) -> &Option<Vec<ManifestDependency>> {
         &self.custom_source_patches
     }
@@ -242,6 +276,20 @@ impl Config {
         self.profile.clone()
     }
 
+    /// Settings read from global and local Scarb configuration files.
+    pub fn config_file(&self) -> &ConfigFile {
+        &self.config_file
+    }
+
+    /// Environment variables from the `[env]` and `[profile.<name>.env]` tables.
+    ///
+    /// These are not set in the environment of Scarb itself, but should be passed to all processes
+    /// spawned by Scarb. Variables already set in the environment, and not forced by the
+    /// configuration, are omitted.
+    pub fn env(&self) -> &BTreeMap<OsString, OsString> {
+        &self.env
+    }
+
     /// Returns handle to the global HTTP client.
     ///
     /// The global client maintains an internal connection pool, and is preconfigured with known
@@ -300,7 +348,9 @@ pub struct ConfigBuilder {
     target_dir_override: Option<Utf8PathBuf>,
     ui_verbosity: Verbosity,
     ui_output_format: OutputFormat,
-    offline: bool,
+    ui_text_wrap: TextWrap,
+    ui_color: ColorChoice,
+    offline: Option<bool>,
     log_filter_directive: Option<OsString>,
     compilers: Option<CompilerRepository>,
     cairo_plugins: Option<CairoPluginRepository>,
@@ -319,7 +369,9 @@ impl ConfigBuilder {
             target_dir_override: None,
             ui_verbosity: Verbosity::Normal,
             ui_output_format: OutputFormat::Text,
-            offline: false,
+            ui_text_wrap: TextWrap::Disabled,
+            ui_color: ColorChoice::Auto,
+            offline: None,
             log_filter_directive: None,
             compilers: None,
             cairo_plugins: None,
@@ -372,7 +424,18 @@ impl ConfigBuilder {
         self
     }
 
-    pub fn offline(mut self, offline: bool) -> Self {
+    pub fn ui_text_wrap(mut self, ui_text_wrap: TextWrap) -> Self {
+        self.ui_text_wrap = ui_text_wrap;
+        self
+    }
+
+    pub fn ui_color(mut self, ui_color: ColorChoice) -> Self {
+        self.ui_color = ui_color;
+        self
+    }
+
+    /// Turn the _Offline Mode_ on or off, overriding the `net.offline` configuration setting.
+    pub fn offline(mut self, offline: Option<bool>) -> Self {
         self.offline = offline;
         self
     }
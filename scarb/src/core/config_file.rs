@@ -0,0 +1,294 @@
+//! Scarb configuration files.
+//!
+//! Configuration is read from the following files, in order of increasing precedence:
+//! 1. The global configuration file, `config.toml` in the [config directory][AppDirs::config_dir].
+//! 2. Local configuration files, `.scarb/config.toml`, in the workspace directory and all its
+//!    ancestors, with files in nested directories overriding ones closer to the file system root.
+//!
+//! Values from files of higher precedence override values from files of lower precedence,
+//! tables are merged key by key.
+
+use std::collections::BTreeMap;
+
+use anyhow::{Context, Result};
+use camino::Utf8Path;
+use serde::Deserialize;
+use url::Url;
+
+use crate::compiler::Profile;
+use crate::core::AppDirs;
+use crate::internal::fsx;
+
+pub const CONFIG_FILE_NAME: &str = "config.toml";
+pub const LOCAL_CONFIG_DIR_NAME: &str = ".scarb";
+
+/// Settings read from Scarb configuration files.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ConfigFile {
+    #[serde(default)]
+    pub net: NetConfig,
+    #[serde(default)]
+    pub registry: RegistryConfig,
+    /// The `[env]` table, with environment variables to set for builds and spawned processes.
+    #[serde(default)]
+    pub env: BTreeMap<String, EnvConfigValue>,
+    /// The `[profile.<name>]` tables, with settings applied only when building with a profile.
+    #[serde(default)]
+    pub profile: BTreeMap<String, ProfileConfig>,
+}
+
+/// The `[net]` table.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct NetConfig {
+    /// Run without accessing the network, as if `--offline` was passed.
+    pub offline: Option<bool>,
+}
+
+/// The `[registry]` table.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct RegistryConfig {
+    /// Registry index URL used by `scarb publish` when `--index` is not passed.
+    pub index: Option<Url>,
+}
+
+/// A `[profile.<name>]` table.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ProfileConfig {
+    /// Environment variables overriding the `[env]` table for this profile.
+    #[serde(default)]
+    pub env: BTreeMap<String, EnvConfigValue>,
+}
+
+/// A value in the `[env]` table.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(untagged)]
+pub enum EnvConfigValue {
+    Simple(String),
+    Detailed(DetailedEnvConfigValue),
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct DetailedEnvConfigValue {
+    pub value: String,
+    /// Override the variable, even if it is already set in the environment.
+    #[serde(default)]
+    pub force: bool,
+    /// Treat the value as a path relative to the directory containing the `.scarb` directory.
+    ///
+    /// Relative paths are resolved when configuration files are read.
+    #[serde(default)]
+    pub relative: bool,
+}
+
+impl EnvConfigValue {
+    pub fn value(&self) -> &str {
+        match self {
+            Self::Simple(value) => value,
+            Self::Detailed(detailed) => &detailed.value,
+        }
+    }
+
+    pub fn force(&self) -> bool {
+        match self {
+            Self::Simple(_) => false,
+            Self::Detailed(detailed) => detailed.force,
+        }
+    }
+}
+
+impl ConfigFile {
+    /// Read and layer all configuration files applicable to the given workspace directory.
+    pub(crate) fn load(dirs: &AppDirs, workspace_root: &Utf8Path) -> Result<Self> {
+        let global_dir = dirs.config_dir.path_unchecked();
+        let global = (global_dir.join(CONFIG_FILE_NAME), global_dir);
+        let local = workspace_root
+            .ancestors()
+            .map(|dir| (dir.join(LOCAL_CONFIG_DIR_NAME).join(CONFIG_FILE_NAME), dir))
+            .collect::<Vec<_>>();
+
+        let mut table = toml::Table::new();
+        for (path, base_dir) in [global].into_iter().chain(local.into_iter().rev()) {
+            if let Some(layer) = read_layer(&path, base_dir)? {
+                merge(&mut table, layer);
+            }
+        }
+
+        toml::Value::Table(table)
+            .try_into()
+            .context("failed to parse Scarb configuration")
+    }
+
+    /// Environment variables to set when building with the given profile.
+    ///
+    /// These are the values of the `[env]` table, overridden by the `[profile.<name>.env]` table.
+    pub fn profile_env(&self, profile: &Profile) -> BTreeMap<&str, &EnvConfigValue> {
+        let profile_env = self
+            .profile
+            .get(profile.as_str())
+            .map(|config| &config.env)
+            .into_iter()
+            .flatten();
+        self.env
+            .iter()
+            .chain(profile_env)
+            .map(|(key, value)| (key.as_str(), value))
+            .collect()
+    }
+}
+
+/// Read a single configuration file, resolving relative `[env]` and `[profile.<name>.env]` values against `base_dir`.
+fn read_layer(path: &Utf8Path, base_dir: &Utf8Path) -> Result<Option<toml::Table>> {
+    if !path.is_file() {
+        return Ok(None);
+    }
+    let contents = fsx::read_to_string(path)?;
+    let mut table: toml::Table = toml::from_str(&contents)
+        .with_context(|| format!("failed to parse config file at: {path}"))?;
+    resolve_relative_env_table(table.get_mut("env"), base_dir);
+    if let Some(toml::Value::Table(profiles)) = table.get_mut("profile") {
+        for (_, profile) in profiles.iter_mut() {
+            if let toml::Value::Table(profile) = profile {
+                resolve_relative_env_table(profile.get_mut("env"), base_dir);
+            }
+        }
+    }
+    Ok(Some(table))
+}
+
+fn resolve_relative_env_table(env: Option<&mut toml::Value>, base_dir: &Utf8Path) {
+    if let Some(toml::Value::Table(env)) = env {
+        for (_, value) in env.iter_mut() {
+            resolve_relative_env(value, base_dir);
+        }
+    }
+}
+
+fn resolve_relative_env(value: &mut toml::Value, base_dir: &Utf8Path) {
+    let toml::Value::Table(detailed) = value else {
+        return;
+    };
+    if detailed.get("relative").and_then(toml::Value::as_bool) != Some(true) {
+        return;
+    }
+    if let Some(toml::Value::String(path)) = detailed.get_mut("value") {
+        *path = base_dir.join(&*path).into_string();
+    }
+    detailed.remove("relative");
+}
+
+/// Merge `source` into `target`, recursing into tables present in both.
+fn merge(target: &mut toml::Table, source: toml::Table) {
+    for (key, value) in source {
+        match (target.get_mut(&key), value) {
+            (Some(toml::Value::Table(target)), toml::Value::Table(source)) => {
+                merge(target, source);
+            }
+            (_, value) => {
+                target.insert(key, value);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use camino::Utf8Path;
+
+    use super::{merge, resolve_relative_env, ConfigFile};
+    use crate::compiler::Profile;
+
+    #[test]
+    fn resolve_relative_env_values() {
+        let mut env: toml::Table = toml::from_str(
+            r#"
+            SIMPLE = "a"
+            ABSOLUTE = { value = "b", force = true }
+            RELATIVE = { value = "c", relative = true }
+            "#,
+        )
+        .unwrap();
+
+        for (_, value) in env.iter_mut() {
+            resolve_relative_env(value, Utf8Path::new("/base"));
+        }
+
+        let expected: toml::Table = toml::from_str(
+            r#"
+            SIMPLE = "a"
+            ABSOLUTE = { value = "b", force = true }
+            RELATIVE = { value = "/base/c" }
+            "#,
+        )
+        .unwrap();
+        assert_eq!(env, expected);
+    }
+
+    #[test]
+    fn merge_overrides_values_and_merges_tables() {
+        let mut target: toml::Table = toml::from_str(
+            r#"
+            a = 1
+            [net]
+            offline = true
+            [registry]
+            index = "https://example.com/"
+            "#,
+        )
+        .unwrap();
+        let source: toml::Table = toml::from_str(
+            r#"
+            a = 2
+            [registry]
+            index = "https://example.org/"
+            "#,
+        )
+        .unwrap();
+
+        merge(&mut target, source);
+
+        let expected: toml::Table = toml::from_str(
+            r#"
+            a = 2
+            [net]
+            offline = true
+            [registry]
+            index = "https://example.org/"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(target, expected);
+    }
+
+    #[test]
+    fn profile_env_overrides_env() {
+        let config: ConfigFile = toml::from_str(
+            r#"
+            [env]
+            A = "base"
+            B = "base"
+            [profile.release.env]
+            B = "release"
+            C = "release"
+            "#,
+        )
+        .unwrap();
+
+        let values = |profile: Profile| {
+            config
+                .profile_env(&profile)
+                .into_iter()
+                .map(|(key, value)| format!("{key}={}", value.value()))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(values(Profile::DEV), ["A=base", "B=base"]);
+        assert_eq!(
+            values(Profile::RELEASE),
+            ["A=base", "B=release", "C=release"]
+        );
+    }
+}
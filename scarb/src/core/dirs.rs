@@ -3,8 +3,8 @@ use std::ffi::OsString;
 use std::fmt;
 use std::path::PathBuf;
 
-use anyhow::{anyhow, Result};
-use camino::Utf8PathBuf;
+use anyhow::{anyhow, ensure, Context, Result};
+use camino::{Utf8Path, Utf8PathBuf};
 use directories::ProjectDirs;
 
 use crate::flock::Filesystem;
@@ -47,7 +47,10 @@ impl AppDirs {
         };
 
         let cache_dir = match cache_dir_override {
-            Some(p) => p,
+            Some(p) => {
+                ensure_writable_dir(&p).with_context(|| format!("invalid cache directory: {p}"))?;
+                p
+            }
             None => pd.cache_dir().try_to_utf8()?,
         };
 
@@ -76,6 +79,21 @@ impl AppDirs {
     }
 }
 
+/// Check that the directory can be written to, if it already exists.
+///
+/// Missing directories are fine, as they are created on first use.
+/// Permission bits do not tell the whole story (ACLs, read-only mounts, ownership),
+/// so this creates a temporary file in the directory and removes it right away.
+fn ensure_writable_dir(path: &Utf8Path) -> Result<()> {
+    let Ok(metadata) = path.metadata() else {
+        return Ok(());
+    };
+    ensure!(metadata.is_dir(), "path is not a directory");
+    tempfile::NamedTempFile::new_in(path)
+        .and_then(|file| file.close())
+        .context("directory is not writable")
+}
+
 impl fmt::Display for AppDirs {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         writeln!(f, "cache dir:  {}", self.cache_dir)?;
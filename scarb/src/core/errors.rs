@@ -13,3 +13,8 @@ impl ScriptExecutionError {
         Self { exit_code }
     }
 }
+
+/// Error returned by operations cancelled with a [`CancellationToken`][crate::core::CancellationToken].
+#[derive(Debug, Error)]
+#[error("operation has been cancelled")]
+pub struct Cancelled;
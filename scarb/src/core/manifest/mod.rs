@@ -12,11 +12,14 @@ use toml::Value;
 pub use compiler_config::*;
 pub use dependency::*;
 pub use maybe_workspace::*;
+pub use profile_inheritance::*;
+pub use schema::*;
 pub use scripts::*;
 pub use summary::*;
 pub use target::*;
 pub use target_kind::*;
 pub use toml_manifest::*;
+pub use unknown_keys::*;
 pub use version_req::*;
 
 use crate::compiler::DefaultForProfile;
@@ -27,11 +30,14 @@ use super::PackageName;
 mod compiler_config;
 mod dependency;
 mod maybe_workspace;
+mod profile_inheritance;
+mod schema;
 mod scripts;
 mod summary;
 mod target;
 mod target_kind;
 mod toml_manifest;
+mod unknown_keys;
 mod version_req;
 
 pub type FeatureName = PackageName;
@@ -60,6 +66,10 @@ pub struct Manifest {
     /// Allow experimental features.
     #[builder(default)]
     pub experimental_features: Option<Vec<SmolStr>>,
+    /// Contents of the manifest file, as read when parsing it.
+    /// This is `None` for manifests which have not been read from a file.
+    #[builder(default)]
+    pub contents: Option<String>,
 }
 
 /// Subset of a [`Manifest`] that contains package metadata.
@@ -79,6 +89,7 @@ pub struct ManifestMetadata {
     #[serde(rename = "tool")]
     pub tool_metadata: Option<BTreeMap<SmolStr, Value>>,
     pub cairo_version: Option<VersionReq>,
+    pub scarb_version: Option<VersionReq>,
 }
 
 impl ManifestBuilder {
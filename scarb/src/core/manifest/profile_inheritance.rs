@@ -0,0 +1,367 @@
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+use std::ops::Range;
+
+use camino::Utf8Path;
+use smol_str::SmolStr;
+use toml_edit::ImDocument;
+
+use super::unknown_keys::{line_column, suggest};
+use super::TomlProfile;
+
+/// An invalid `inherits` key of a `[profile.<name>]` table.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InvalidProfileInheritance {
+    /// Name of the profile with the invalid `inherits` key.
+    pub profile: String,
+    /// Byte range of the `inherits` key in the manifest file.
+    pub span: Option<Range<usize>>,
+    pub kind: InvalidProfileInheritanceKind,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum InvalidProfileInheritanceKind {
+    /// Profiles inheriting from each other in a cycle, starting and ending with the same profile.
+    Cycle(Vec<String>),
+    /// Inheriting from a profile which is neither built-in nor defined in the manifest.
+    Undefined {
+        parent: String,
+        /// A defined profile with a similar name, which has likely been meant instead.
+        suggestion: Option<String>,
+    },
+}
+
+impl InvalidProfileInheritance {
+    /// Render an error about this key, pointing at its location in the manifest file.
+    pub fn to_error(&self, manifest_path: &Utf8Path, contents: &str) -> String {
+        let mut error = self.headline();
+        if let Some(span) = &self.span {
+            let (line, column) = line_column(contents, span.start);
+            error.push_str(&format!("\n --> {manifest_path}:{line}:{column}"));
+        }
+        error.push_str(&self.help());
+        error
+    }
+
+    fn headline(&self) -> String {
+        match &self.kind {
+            InvalidProfileInheritanceKind::Cycle(cycle) => format!(
+                "profile inheritance cycle: {}",
+                cycle
+                    .iter()
+                    .map(|profile| format!("`{profile}`"))
+                    .collect::<Vec<_>>()
+                    .join(" -> ")
+            ),
+            InvalidProfileInheritanceKind::Undefined { parent, .. } => format!(
+                "profile `{}` inherits from undefined profile `{parent}`",
+                self.profile
+            ),
+        }
+    }
+
+    fn help(&self) -> String {
+        match &self.kind {
+            InvalidProfileInheritanceKind::Undefined {
+                suggestion: Some(suggestion),
+                ..
+            } => format!("\nhelp: did you mean `{suggestion}`?"),
+            _ => String::new(),
+        }
+    }
+}
+
+/// Renders the error without its location, for manifests whose contents are not at hand.
+impl fmt::Display for InvalidProfileInheritance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.headline(), self.help())
+    }
+}
+
+/// Find `inherits` keys of `[profile.<name>]` tables which cannot be resolved.
+///
+/// Profiles may inherit from `dev`, `release`, or another profile defined in the manifest.
+/// Each cycle is reported once, at the `inherits` key closing it, which belongs to the cycle
+/// member appearing last when following the cycle from its member declared first.
+/// Manifests which cannot be parsed yield no errors, as these fail to deserialize anyway.
+pub fn find_invalid_profile_inheritance(contents: &str) -> Vec<InvalidProfileInheritance> {
+    let Ok(document) = ImDocument::parse(contents) else {
+        return Vec::new();
+    };
+    let Some(profiles) = document
+        .get("profile")
+        .and_then(|profiles| profiles.as_table_like())
+    else {
+        return Vec::new();
+    };
+
+    // Name of each declared profile, with the value and span of its `inherits` key.
+    let declared = profiles
+        .iter()
+        .map(|(name, item)| {
+            let inherits = item.as_table_like().and_then(|table| {
+                let (key, value) = table.get_key_value("inherits")?;
+                Some((value.as_str()?, key.span()))
+            });
+            (name, inherits)
+        })
+        .collect::<Vec<_>>();
+    find_invalid_inheritance(&declared)
+}
+
+/// Find profiles of deserialized `[profile]` tables, whose `inherits` keys cannot be resolved.
+///
+/// Same as [`find_invalid_profile_inheritance`], but the reported errors have no location.
+pub fn find_invalid_toml_profile_inheritance(
+    profiles: &BTreeMap<SmolStr, TomlProfile>,
+) -> Vec<InvalidProfileInheritance> {
+    let declared = profiles
+        .iter()
+        .map(|(name, profile)| {
+            let inherits = profile
+                .inherits
+                .as_ref()
+                .map(|parent| (parent.as_str(), None));
+            (name.as_str(), inherits)
+        })
+        .collect::<Vec<_>>();
+    find_invalid_inheritance(&declared)
+}
+
+/// Find invalid inheritance among profiles declared in the given order, each with the parent
+/// profile name and span of its `inherits` key, if it has one.
+fn find_invalid_inheritance(
+    declared: &[(&str, Option<(&str, Option<Range<usize>>)>)],
+) -> Vec<InvalidProfileInheritance> {
+    let parent_of = |profile: &str| {
+        declared
+            .iter()
+            .find(|(name, _)| *name == profile)
+            .and_then(|(_, inherits)| inherits.clone())
+            .filter(|(parent, _)| !is_built_in(parent))
+    };
+
+    let mut invalid = Vec::new();
+    for (name, inherits) in declared {
+        let Some((parent, span)) = inherits else {
+            continue;
+        };
+        if is_built_in(parent) || declared.iter().any(|(declared, _)| declared == parent) {
+            continue;
+        }
+        let candidates = ["dev", "release"]
+            .into_iter()
+            .chain(declared.iter().map(|(declared, _)| *declared))
+            .filter(|candidate| candidate != name);
+        invalid.push(InvalidProfileInheritance {
+            profile: name.to_string(),
+            span: span.clone(),
+            kind: InvalidProfileInheritanceKind::Undefined {
+                parent: parent.to_string(),
+                suggestion: suggest(parent, candidates).map(ToString::to_string),
+            },
+        });
+    }
+
+    let mut in_reported_cycle = HashSet::new();
+    for (name, _) in declared {
+        if is_built_in(name) || in_reported_cycle.contains(name) {
+            continue;
+        }
+        let mut path = vec![*name];
+        let mut current = *name;
+        while let Some((parent, span)) = parent_of(current) {
+            if let Some(position) = path.iter().position(|profile| *profile == parent) {
+                // Cycles not containing this profile are reported for their own members.
+                if position == 0 {
+                    in_reported_cycle.extend(path.iter().copied());
+                    path.push(parent);
+                    invalid.push(InvalidProfileInheritance {
+                        profile: current.to_string(),
+                        span,
+                        kind: InvalidProfileInheritanceKind::Cycle(
+                            path.iter().map(ToString::to_string).collect(),
+                        ),
+                    });
+                }
+                break;
+            }
+            path.push(parent);
+            current = parent;
+        }
+    }
+    invalid
+}
+
+fn is_built_in(profile: &str) -> bool {
+    profile == "dev" || profile == "release"
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+
+    use std::collections::BTreeMap;
+
+    use super::{
+        find_invalid_profile_inheritance, find_invalid_toml_profile_inheritance,
+        InvalidProfileInheritance, InvalidProfileInheritanceKind,
+    };
+    use crate::core::TomlProfile;
+
+    #[test]
+    fn valid_inheritance_chain() {
+        let contents = indoc! {r#"
+            [profile.a]
+            inherits = "b"
+
+            [profile.b]
+            inherits = "release"
+
+            [profile.c]
+        "#};
+        assert_eq!(find_invalid_profile_inheritance(contents), vec![]);
+    }
+
+    #[test]
+    fn two_profile_cycle() {
+        let contents = indoc! {r#"
+            [profile.a]
+            inherits = "b"
+
+            [profile.b]
+            inherits = "a"
+        "#};
+        let start = contents.rfind("inherits").unwrap();
+        assert_eq!(
+            find_invalid_profile_inheritance(contents),
+            vec![InvalidProfileInheritance {
+                profile: "b".to_string(),
+                span: Some(start..start + "inherits".len()),
+                kind: InvalidProfileInheritanceKind::Cycle(vec![
+                    "a".to_string(),
+                    "b".to_string(),
+                    "a".to_string()
+                ]),
+            }]
+        );
+    }
+
+    #[test]
+    fn cycle_is_reported_once() {
+        let contents = indoc! {r#"
+            [profile.x]
+            inherits = "b"
+
+            [profile.a]
+            inherits = "c"
+
+            [profile.b]
+            inherits = "a"
+
+            [profile.c]
+            inherits = "b"
+
+            [profile.d]
+            inherits = "d"
+        "#};
+        let cycles = find_invalid_profile_inheritance(contents)
+            .into_iter()
+            .map(|invalid| (invalid.profile, invalid.kind))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            cycles,
+            [
+                (
+                    "b".to_string(),
+                    InvalidProfileInheritanceKind::Cycle(
+                        ["a", "c", "b", "a"].map(ToString::to_string).to_vec()
+                    )
+                ),
+                (
+                    "d".to_string(),
+                    InvalidProfileInheritanceKind::Cycle(
+                        ["d", "d"].map(ToString::to_string).to_vec()
+                    )
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn undefined_parent_profile() {
+        let contents = indoc! {r#"
+            [profile.my-profile]
+            inherits = "release"
+
+            [profile.a]
+            inherits = "my-profle"
+
+            [profile.b]
+            inherits = "unrelated"
+        "#};
+        let start = contents.find("inherits = \"my-profle\"").unwrap();
+        let invalid = find_invalid_profile_inheritance(contents);
+        assert_eq!(
+            invalid,
+            vec![
+                InvalidProfileInheritance {
+                    profile: "a".to_string(),
+                    span: Some(start..start + "inherits".len()),
+                    kind: InvalidProfileInheritanceKind::Undefined {
+                        parent: "my-profle".to_string(),
+                        suggestion: Some("my-profile".to_string()),
+                    },
+                },
+                InvalidProfileInheritance {
+                    profile: "b".to_string(),
+                    span: invalid[1].span.clone(),
+                    kind: InvalidProfileInheritanceKind::Undefined {
+                        parent: "unrelated".to_string(),
+                        suggestion: None,
+                    },
+                },
+            ]
+        );
+        assert_eq!(
+            invalid[0].to_error("Scarb.toml".into(), contents),
+            "profile `a` inherits from undefined profile `my-profle`\n --> Scarb.toml:5:1\n\
+            help: did you mean `my-profile`?"
+        );
+    }
+
+    #[test]
+    fn error_points_at_inherits_key() {
+        let contents = "[profile.a]\ninherits = \"b\"\n\n[profile.b]\ninherits = \"a\"\n";
+        let invalid = find_invalid_profile_inheritance(contents);
+        assert_eq!(
+            invalid[0].to_error("Scarb.toml".into(), contents),
+            "profile inheritance cycle: `a` -> `b` -> `a`\n --> Scarb.toml:5:1"
+        );
+    }
+
+    #[test]
+    fn deserialized_profiles_are_checked_the_same_way() {
+        let profile = |inherits: &str| TomlProfile {
+            inherits: Some(inherits.into()),
+            ..Default::default()
+        };
+        let profiles = BTreeMap::from([
+            ("a".into(), profile("b")),
+            ("b".into(), profile("a")),
+            ("c".into(), profile("relase")),
+        ]);
+        let errors = find_invalid_toml_profile_inheritance(&profiles)
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            errors,
+            [
+                "profile `c` inherits from undefined profile `relase`\n\
+                help: did you mean `release`?",
+                "profile inheritance cycle: `a` -> `b` -> `a`",
+            ]
+        );
+    }
+}
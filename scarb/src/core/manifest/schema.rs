@@ -0,0 +1,389 @@
+use std::collections::BTreeMap;
+use std::sync::LazyLock;
+
+use anyhow::{anyhow, bail, ensure, Result};
+use cairo_lang_filesystem::db::Edition;
+use serde::de::{self, Deserializer, Visitor};
+use serde::{forward_to_deserialize_any, Deserialize};
+
+use crate::core::{
+    DetailedTomlDependency, InliningStrategy, PackageInheritableFields, TomlCairo,
+    TomlCairoPluginPrebuilt, TomlCairoPluginTargetParams, TomlExecutableTargetParams,
+    TomlLibTargetParams, TomlManifest, TomlPackage, TomlProfile, TomlWorkspace,
+};
+
+/// Keys common to all target tables.
+///
+/// These are not introspectable, because target parameters are flattened into target tables.
+const TARGET_KEYS: &[&str] = &["name", "source-path", "edition"];
+
+/// Structure of a `Scarb.toml` file, describing which keys are valid at which paths.
+///
+/// Keys of tables are taken from the serde definitions of manifest types, so that the schema
+/// cannot get out of sync with what Scarb actually deserializes.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ManifestSchema {
+    /// A table with a fixed set of keys.
+    Table(BTreeMap<&'static str, ManifestSchema>),
+    /// A table with arbitrary keys, with values of the same structure, like `[dependencies]`.
+    Map(Box<ManifestSchema>),
+    /// An array with items of the same structure, like `[[test]]`.
+    Array(Box<ManifestSchema>),
+    /// A plain value, like a string or a number.
+    Value,
+    /// A string value, which is one of a fixed set, like `edition`.
+    Enum(&'static [&'static str]),
+    /// Arbitrary contents, which Scarb does not interpret, like `[tool]`.
+    Any,
+}
+
+impl ManifestSchema {
+    /// Schema of a whole manifest file.
+    pub fn manifest() -> &'static Self {
+        static SCHEMA: LazyLock<ManifestSchema> = LazyLock::new(ManifestSchema::build);
+        &SCHEMA
+    }
+
+    /// List keys which are valid in the table at the given path, like `["package"]`.
+    ///
+    /// Arrays are indexed with numbers, and keys of maps, like names of dependencies, are arbitrary.
+    /// Values, arrays, maps and passthrough contents have no fixed keys, so these yield no keys.
+    pub fn child_keys<I>(&self, path: I) -> Result<Vec<String>>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        Ok(match self.resolve(path)? {
+            Some(Self::Table(keys)) => keys.keys().map(ToString::to_string).collect(),
+            _ => Vec::new(),
+        })
+    }
+
+    /// List values which are valid for the key at the given path, if only a fixed set of values
+    /// is accepted there, like for `["package", "edition"]`.
+    pub fn enum_values<I>(&self, path: I) -> Result<Option<Vec<String>>>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        Ok(match self.resolve(path)? {
+            Some(Self::Enum(values)) => Some(values.iter().map(ToString::to_string).collect()),
+            _ => None,
+        })
+    }
+
+    /// Find the schema of the given path.
+    ///
+    /// Returns `None` if the path leads into passthrough contents, which have no schema.
+    fn resolve<I>(&self, path: I) -> Result<Option<&Self>>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        let mut schema = self;
+        let mut walked = Vec::new();
+        for segment in path {
+            let segment = segment.as_ref();
+            walked.push(segment.to_string());
+            schema = match schema {
+                Self::Table(keys) => keys
+                    .get(segment)
+                    .ok_or_else(|| anyhow!("unknown manifest key: {}", walked.join(".")))?,
+                Self::Map(values) => values,
+                Self::Array(items) => {
+                    ensure!(
+                        segment.parse::<usize>().is_ok(),
+                        "manifest key `{}` is an array, expected an index",
+                        walked[..walked.len() - 1].join(".")
+                    );
+                    items
+                }
+                Self::Value | Self::Enum(_) => bail!(
+                    "manifest key `{}` is not a table",
+                    walked[..walked.len() - 1].join(".")
+                ),
+                Self::Any => return Ok(None),
+            };
+        }
+        Ok(Some(schema))
+    }
+
+    fn build() -> Self {
+        let dependency = Self::table::<DetailedTomlDependency>();
+        let package_dependency = dependency.clone().with_keys(&["workspace"]);
+        let edition = Self::enumeration::<Edition>();
+        let cairo = Self::table::<TomlCairo>()
+            .with("inlining-strategy", Self::enumeration::<InliningStrategy>());
+        let profile = Self::table::<TomlProfile>()
+            .with("cairo", cairo.clone())
+            .with("tool", Self::Any);
+
+        Self::table::<TomlManifest>()
+            .with(
+                "package",
+                Self::table::<TomlPackage>()
+                    .with("edition", edition.clone())
+                    .with("urls", Self::map(Self::Value)),
+            )
+            .with(
+                "workspace",
+                Self::table::<TomlWorkspace>()
+                    .with(
+                        "package",
+                        Self::table::<PackageInheritableFields>().with("edition", edition),
+                    )
+                    .with("dependencies", Self::map(dependency.clone()))
+                    .with("dev-dependencies", Self::map(dependency.clone()))
+                    .with("scripts", Self::map(Self::Value))
+                    .with("tool", Self::Any),
+            )
+            .with("dependencies", Self::map(package_dependency.clone()))
+            .with("dev-dependencies", Self::map(package_dependency))
+            .with("lib", Self::target::<TomlLibTargetParams>())
+            .with("executable", Self::target::<TomlExecutableTargetParams>())
+            .with(
+                "cairo-plugin",
+                Self::target::<TomlCairoPluginTargetParams>().with(
+                    "prebuilt",
+                    Self::map(Self::table::<TomlCairoPluginPrebuilt>()),
+                ),
+            )
+            .with("test", Self::array(Self::Any))
+            .with("target", Self::Any)
+            .with("cairo", cairo)
+            .with("profile", Self::map(profile))
+            .with("scripts", Self::map(Self::Value))
+            .with("tool", Self::Any)
+            .with("features", Self::map(Self::Value))
+            .with("patch", Self::map(Self::map(dependency)))
+    }
+
+    /// A table with the fields of the given struct, all of them being plain values.
+    fn table<T: for<'de> Deserialize<'de>>() -> Self {
+        Self::Table(BTreeMap::new()).with_keys(names::<T>())
+    }
+
+    /// A target table with the given parameters.
+    fn target<P: for<'de> Deserialize<'de>>() -> Self {
+        Self::table::<P>()
+            .with_keys(TARGET_KEYS)
+            .with("edition", Self::enumeration::<Edition>())
+    }
+
+    /// A value which is one of the variants of the given enum.
+    fn enumeration<T: for<'de> Deserialize<'de>>() -> Self {
+        Self::Enum(names::<T>())
+    }
+
+    fn map(values: Self) -> Self {
+        Self::Map(Box::new(values))
+    }
+
+    fn array(items: Self) -> Self {
+        Self::Array(Box::new(items))
+    }
+
+    /// Add plain value keys to this table.
+    fn with_keys(mut self, keys: &[&'static str]) -> Self {
+        let Self::Table(table) = &mut self else {
+            unreachable!("only tables have keys");
+        };
+        table.extend(keys.iter().map(|key| (*key, Self::Value)));
+        self
+    }
+
+    /// Describe the structure of an existing key of this table.
+    fn with(mut self, key: &'static str, schema: Self) -> Self {
+        let Self::Table(table) = &mut self else {
+            unreachable!("only tables have keys");
+        };
+        let value = table
+            .get_mut(key)
+            .unwrap_or_else(|| panic!("manifest schema does not have key: {key}"));
+        *value = schema;
+        self
+    }
+}
+
+/// Names of fields of a struct, or of variants of an enum, as they are spelled in the manifest.
+///
+/// Serde passes these to the deserializer of a struct or an enum, so a deserializer which captures
+/// them and then bails is enough to list them, without duplicating them by hand.
+fn names<T: for<'de> Deserialize<'de>>() -> &'static [&'static str] {
+    let mut collector = NamesCollector { names: &[] };
+    let _ = T::deserialize(&mut collector);
+    collector.names
+}
+
+struct NamesCollector {
+    names: &'static [&'static str],
+}
+
+impl<'de> Deserializer<'de> for &mut NamesCollector {
+    type Error = de::value::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Self::Error> {
+        Err(de::Error::custom("neither a struct nor an enum"))
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        _visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.names = fields;
+        Err(de::Error::custom("only collecting names"))
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        variants: &'static [&'static str],
+        _visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.names = variants;
+        Err(de::Error::custom("only collecting names"))
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf
+        option unit unit_struct newtype_struct seq tuple tuple_struct map identifier ignored_any
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ManifestSchema;
+
+    #[test]
+    fn child_keys_of_package() {
+        assert_eq!(
+            ManifestSchema::manifest().child_keys(["package"]).unwrap(),
+            [
+                "authors",
+                "cairo-version",
+                "description",
+                "documentation",
+                "edition",
+                "experimental-features",
+                "homepage",
+                "include",
+                "keywords",
+                "license",
+                "license-file",
+                "name",
+                "no-core",
+                "publish",
+                "readme",
+                "repository",
+                "scarb-version",
+                "urls",
+                "version",
+            ]
+        );
+    }
+
+    #[test]
+    fn child_keys_of_dependency() {
+        let schema = ManifestSchema::manifest();
+        assert_eq!(
+            schema.child_keys(["dependencies", "foo"]).unwrap(),
+            [
+                "branch",
+                "git",
+                "path",
+                "registry",
+                "rev",
+                "tag",
+                "version",
+                "workspace"
+            ]
+        );
+        assert_eq!(
+            schema
+                .child_keys(["workspace", "dependencies", "foo"])
+                .unwrap(),
+            ["branch", "git", "path", "registry", "rev", "tag", "version"]
+        );
+    }
+
+    #[test]
+    fn child_keys_of_non_tables() {
+        let schema = ManifestSchema::manifest();
+        assert!(schema.child_keys(["package", "name"]).unwrap().is_empty());
+        assert!(schema.child_keys(["dependencies"]).unwrap().is_empty());
+        assert!(schema.child_keys(["test"]).unwrap().is_empty());
+        assert!(schema.child_keys(["tool", "snforge"]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn enum_values_of_edition() {
+        let schema = ManifestSchema::manifest();
+        let editions = Some(vec![
+            "2023_01".to_string(),
+            "2023_10".to_string(),
+            "2023_11".to_string(),
+            "2024_07".to_string(),
+        ]);
+        assert_eq!(
+            schema.enum_values(["package", "edition"]).unwrap(),
+            editions
+        );
+        assert_eq!(schema.enum_values(["lib", "edition"]).unwrap(), editions);
+        assert_eq!(
+            schema
+                .enum_values(["profile", "release", "cairo", "inlining-strategy"])
+                .unwrap(),
+            Some(vec!["default".to_string(), "avoid".to_string()])
+        );
+    }
+
+    #[test]
+    fn enum_values_of_non_enums() {
+        let schema = ManifestSchema::manifest();
+        assert_eq!(schema.enum_values(["package", "name"]).unwrap(), None);
+        assert_eq!(schema.enum_values(["package"]).unwrap(), None);
+        assert_eq!(schema.enum_values(["tool", "snforge"]).unwrap(), None);
+    }
+
+    #[test]
+    fn child_keys_of_invalid_paths() {
+        let schema = ManifestSchema::manifest();
+        assert_eq!(
+            schema
+                .child_keys(["package", "editon"])
+                .unwrap_err()
+                .to_string(),
+            "unknown manifest key: package.editon"
+        );
+        assert_eq!(
+            schema
+                .child_keys(["package", "name", "foo"])
+                .unwrap_err()
+                .to_string(),
+            "manifest key `package.name` is not a table"
+        );
+    }
+
+    #[test]
+    fn lib_schema_has_target_and_parameter_keys() {
+        let ManifestSchema::Table(root) = ManifestSchema::manifest() else {
+            panic!("manifest schema is not a table");
+        };
+        let ManifestSchema::Table(lib) = &root["lib"] else {
+            panic!("lib schema is not a table");
+        };
+        assert_eq!(
+            lib.keys().copied().collect::<Vec<_>>(),
+            [
+                "casm",
+                "edition",
+                "name",
+                "sierra",
+                "sierra-text",
+                "source-path"
+            ]
+        );
+    }
+}
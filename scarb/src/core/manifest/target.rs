@@ -3,6 +3,7 @@ use std::ops::Deref;
 use std::sync::Arc;
 
 use anyhow::Result;
+use cairo_lang_filesystem::db::Edition;
 use camino::{Utf8Path, Utf8PathBuf};
 use serde::{Deserialize, Serialize};
 use smol_str::SmolStr;
@@ -21,6 +22,8 @@ pub struct TargetInner {
     pub name: SmolStr,
     pub source_path: Utf8PathBuf,
     pub group_id: Option<SmolStr>,
+    /// Cairo edition of this target, if it overrides the package edition.
+    pub edition: Option<Edition>,
     pub params: toml::Value,
 }
 
@@ -38,6 +41,7 @@ impl Target {
         name: impl Into<SmolStr>,
         source_path: impl Into<Utf8PathBuf>,
         group_id: Option<SmolStr>,
+        edition: Option<Edition>,
         params: toml::Value,
     ) -> Self {
         assert!(params.is_table(), "params must be a TOML table");
@@ -46,6 +50,7 @@ impl Target {
             name: name.into(),
             source_path: source_path.into(),
             group_id,
+            edition,
             params,
         }))
     }
@@ -60,6 +65,7 @@ impl Target {
             name,
             source_path,
             None,
+            None,
             toml::Value::Table(toml::Table::new()),
         )
     }
@@ -69,10 +75,18 @@ impl Target {
         name: impl Into<SmolStr>,
         source_path: impl Into<Utf8PathBuf>,
         group_id: Option<SmolStr>,
+        edition: Option<Edition>,
         params: impl Serialize,
     ) -> Result<Self> {
         let params = toml::Value::try_from(params)?;
-        Ok(Self::new(kind, name, source_path, group_id, params))
+        Ok(Self::new(
+            kind,
+            name,
+            source_path,
+            group_id,
+            edition,
+            params,
+        ))
     }
 
     pub fn is_lib(&self) -> bool {
@@ -106,6 +120,7 @@ impl Hash for TargetInner {
         self.kind.hash(state);
         self.name.hash(state);
         self.source_path.hash(state);
+        self.edition.hash(state);
         self.params.to_string().hash(state);
     }
 }
@@ -24,18 +24,22 @@ use crate::core::manifest::{ManifestDependency, ManifestMetadata, Summary, Targe
 use crate::core::package::PackageId;
 use crate::core::source::{GitReference, SourceId};
 use crate::core::{
-    Config, DepKind, DependencyVersionReq, InliningStrategy, ManifestBuilder,
+    Checksum, Config, DepKind, DependencyVersionReq, InliningStrategy, ManifestBuilder,
     ManifestCompilerConfig, PackageName, TargetKind, TestTargetProps, TestTargetType,
 };
 use crate::internal::fsx;
 use crate::internal::fsx::PathBufUtf8Ext;
 use crate::internal::serdex::{toml_merge, toml_merge_apply_strategy, RelativeUtf8PathBuf};
 use crate::internal::to_version::ToVersion;
+use crate::sources::canonical_url::CanonicalUrl;
 use crate::{
     DEFAULT_MODULE_MAIN_FILE, DEFAULT_SOURCE_PATH, DEFAULT_TESTS_PATH, MANIFEST_FILE_NAME,
 };
 
-use super::{FeatureName, Manifest};
+use super::{find_invalid_toml_profile_inheritance, FeatureName, Manifest};
+
+/// Key of the `[patch]` table, which stands for the default registry.
+const DEFAULT_REGISTRY_PATCH_KEY: &str = "scarbs-xyz";
 
 /// This type is used to deserialize `Scarb.toml` files.
 #[derive(Debug, Default, Deserialize, Serialize)]
@@ -55,6 +59,7 @@ pub struct TomlManifest {
     pub scripts: Option<BTreeMap<SmolStr, MaybeWorkspaceScriptDefinition>>,
     pub tool: Option<BTreeMap<SmolStr, MaybeWorkspaceTomlTool>>,
     pub features: Option<BTreeMap<FeatureName, Vec<FeatureName>>>,
+    pub patch: Option<BTreeMap<SmolStr, BTreeMap<PackageName, TomlDependency>>>,
 }
 
 type MaybeWorkspaceScriptDefinition = MaybeWorkspace<ScriptDefinition, WorkspaceScriptDefinition>;
@@ -121,6 +126,7 @@ pub struct PackageInheritableFields {
     pub readme: Option<PathOrBool>,
     pub repository: Option<String>,
     pub cairo_version: Option<VersionReq>,
+    pub scarb_version: Option<VersionReq>,
 }
 
 macro_rules! get_field {
@@ -142,6 +148,7 @@ impl PackageInheritableFields {
     get_field!(authors, VecOfStrings);
     get_field!(keywords, VecOfStrings);
     get_field!(cairo_version, VersionReq);
+    get_field!(scarb_version, VersionReq);
     get_field!(description, String);
     get_field!(documentation, String);
     get_field!(homepage, String);
@@ -202,6 +209,8 @@ pub struct TomlPackage {
     /// **UNSTABLE** This package does not depend on Cairo's `core`.
     pub no_core: Option<bool>,
     pub cairo_version: Option<MaybeWorkspaceField<VersionReq>>,
+    /// Minimum version of Scarb able to build this package.
+    pub scarb_version: Option<MaybeWorkspaceField<VersionReq>>,
     pub experimental_features: Option<Vec<SmolStr>>,
 }
 
@@ -283,6 +292,7 @@ pub struct DetailedTomlDependency {
 pub struct TomlTarget<P> {
     pub name: Option<SmolStr>,
     pub source_path: Option<Utf8PathBuf>,
+    pub edition: Option<Edition>,
 
     #[serde(flatten)]
     pub params: P,
@@ -304,6 +314,17 @@ pub struct TomlExecutableTargetParams {}
 #[serde(rename_all = "kebab-case")]
 pub struct TomlCairoPluginTargetParams {
     pub builtin: Option<bool>,
+    pub prebuilt: Option<BTreeMap<String, TomlCairoPluginPrebuilt>>,
+}
+
+/// Prebuilt shared library of a procedural macro, declared for a single target triple.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct TomlCairoPluginPrebuilt {
+    /// Path to the library, relative to the package root.
+    pub path: Utf8PathBuf,
+    /// Checksum of the library file, verified before loading it.
+    pub checksum: Checksum,
 }
 
 pub type TomlExternalTargetParams = BTreeMap<SmolStr, toml::Value>;
@@ -589,6 +610,11 @@ impl TomlManifest {
                 .clone()
                 .map(|mw| mw.resolve("cairo_version", || inheritable_package.cairo_version()))
                 .transpose()?,
+            scarb_version: package
+                .scarb_version
+                .clone()
+                .map(|mw| mw.resolve("scarb_version", || inheritable_package.scarb_version()))
+                .transpose()?,
         };
 
         let edition = package
@@ -714,10 +740,13 @@ impl TomlManifest {
                 .dedup()
                 .collect_vec();
             let source_path = self.lib.as_ref().and_then(|l| l.source_path.clone());
+            // Unit tests are compiled from the library sources, so they share its edition.
+            let edition = self.lib.as_ref().and_then(|l| l.edition);
             let target_name: SmolStr = format!("{package_name}_unittest").into();
             let target_config = TomlTarget::<TomlExternalTargetParams> {
                 name: Some(target_name),
                 source_path,
+                edition,
                 params: TestTargetProps::default()
                     .with_build_external_contracts(external_contracts.clone())
                     .try_into()?,
@@ -742,6 +771,7 @@ impl TomlManifest {
                     Ok(TomlTarget::<TomlExternalTargetParams> {
                         name: Some(target_name),
                         source_path: Some(source_path),
+                        edition: None,
                         params: TestTargetProps::new(TestTargetType::Integration)
                             .with_build_external_contracts(external_contracts.clone())
                             .try_into()?,
@@ -826,8 +856,14 @@ impl TomlManifest {
             .transpose()?
             .unwrap_or(default_source_path.to_path_buf());
 
-        let target =
-            Target::try_from_structured_params(kind, name, source_path, group_id, &target.params)?;
+        let target = Target::try_from_structured_params(
+            kind,
+            name,
+            source_path,
+            group_id,
+            target.edition,
+            &target.params,
+        )?;
 
         Ok(Some(target))
     }
@@ -845,39 +881,46 @@ impl TomlManifest {
             .unwrap_or(Ok(vec![]))
     }
 
-    fn collect_profile_definition(&self, profile: Profile) -> Result<TomlProfile> {
+    /// Resolve settings of the given profile defined in this manifest, after inheritance.
+    pub fn collect_profile_definition(&self, profile: Profile) -> Result<TomlProfile> {
         let toml_cairo = self.cairo.clone().unwrap_or_default();
-        let toml_profiles = self.profile.clone();
-
-        let profile_definition = toml_profiles
-            .clone()
-            .unwrap_or_default()
-            .get(profile.as_str())
-            .cloned();
+        let toml_profiles = self.profile.clone().unwrap_or_default();
 
-        let parent_profile = profile_definition
-            .clone()
-            .unwrap_or_default()
-            .inherits
-            .map(Profile::new)
-            .unwrap_or_else(|| {
-                if profile.is_custom() {
-                    Ok(Profile::default())
-                } else {
-                    Ok(profile.clone())
-                }
-            })?;
+        if let Some(invalid) = find_invalid_toml_profile_inheritance(&toml_profiles)
+            .into_iter()
+            .next()
+        {
+            bail!("{invalid}");
+        }
 
-        if parent_profile.is_custom() {
-            bail!(
-                "profile can inherit from `dev` or `release` only, found `{}`",
-                parent_profile.as_str()
-            );
+        // Follow the chain of custom profiles up to the built-in profile it inherits from,
+        // collecting definitions of profiles along the way.
+        let mut chain: Vec<(Profile, Option<TomlProfile>)> = Vec::new();
+        let mut current = profile;
+        loop {
+            let definition = toml_profiles.get(current.as_str()).cloned();
+            let parent = definition
+                .as_ref()
+                .and_then(|definition| definition.inherits.clone())
+                .map(Profile::new)
+                .transpose()?
+                .unwrap_or_else(|| {
+                    if current.is_custom() {
+                        Profile::default()
+                    } else {
+                        current.clone()
+                    }
+                });
+            chain.push((current, definition));
+            current = parent;
+            if !current.is_custom() {
+                break;
+            }
         }
 
+        let parent_profile = current;
         let parent_default = TomlProfile::default_for_profile(&parent_profile);
         let parent_definition = toml_profiles
-            .unwrap_or_default()
             .get(parent_profile.as_str())
             .cloned()
             .unwrap_or(parent_default.clone());
@@ -887,15 +930,62 @@ impl TomlManifest {
         let parent_cairo = toml_merge(&parent_definition.cairo, &toml_cairo)?;
         parent_definition.cairo = parent_cairo;
 
-        let profile = if let Some(profile_definition) = profile_definition {
-            toml_merge(&parent_definition, &profile_definition)?
-        } else {
-            parent_definition
-        };
+        let mut profile = parent_definition;
+        for (_, definition) in chain.into_iter().rev() {
+            if let Some(definition) = definition {
+                profile = toml_merge(&profile, &definition)?;
+            }
+        }
 
         Ok(profile)
     }
 
+    /// Collect dependencies overriding packages of other sources, from the `[patch]` table.
+    ///
+    /// Keys of the `[patch]` table are URLs of patched sources, or `scarbs-xyz` for the default
+    /// registry. Patches pointing at local paths must lead to an existing package.
+    pub fn collect_patch(
+        &self,
+        manifest_path: &Utf8Path,
+    ) -> Result<BTreeMap<CanonicalUrl, Vec<ManifestDependency>>> {
+        let Some(patch) = &self.patch else {
+            return Ok(BTreeMap::new());
+        };
+        patch
+            .iter()
+            .map(|(source, patches)| {
+                let source_url = if source == DEFAULT_REGISTRY_PATCH_KEY {
+                    SourceId::default_registry().canonical_url.clone()
+                } else {
+                    let url = Url::parse(source).with_context(|| {
+                        format!(
+                            "invalid patched source `{source}`, expected an URL or \
+                            `{DEFAULT_REGISTRY_PATCH_KEY}`"
+                        )
+                    })?;
+                    CanonicalUrl::new(&url)?
+                };
+                let patches = patches
+                    .iter()
+                    .map(|(name, dep)| {
+                        let dep =
+                            dep.to_dependency(name.clone(), manifest_path, DepKind::Normal)?;
+                        if let Some(path) = dep.source_id.to_path() {
+                            ensure!(
+                                path.is_file(),
+                                "patch for `{name}` points to `{}`, which does not contain \
+                                a manifest file",
+                                path.parent().unwrap_or(&path)
+                            );
+                        }
+                        Ok(dep)
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                Ok((source_url, patches))
+            })
+            .collect()
+    }
+
     fn collect_compiler_config(
         &self,
         profile: &Profile,
@@ -0,0 +1,285 @@
+use std::ops::Range;
+
+use camino::Utf8Path;
+use toml_edit::{ImDocument, Item, TableLike, Value};
+
+use crate::core::ManifestSchema;
+
+/// A key in a manifest file which Scarb does not recognize, most likely a typo.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UnknownManifestKey {
+    /// Dotted path to the key, like `package.editon`.
+    pub path: String,
+    /// Byte range of the key in the manifest file.
+    pub span: Option<Range<usize>>,
+    /// A known key with a similar name, which has likely been meant instead.
+    pub suggestion: Option<String>,
+}
+
+impl UnknownManifestKey {
+    /// Render a warning about this key, pointing at its location in the manifest file.
+    pub fn to_warning(&self, manifest_path: &Utf8Path, contents: &str) -> String {
+        let mut warning = format!("unused manifest key: {}", self.path);
+        if let Some(span) = &self.span {
+            let (line, column) = line_column(contents, span.start);
+            warning.push_str(&format!("\n --> {manifest_path}:{line}:{column}"));
+        }
+        if let Some(suggestion) = &self.suggestion {
+            warning.push_str(&format!("\nhelp: did you mean `{suggestion}`?"));
+        }
+        warning
+    }
+}
+
+/// Find keys which Scarb does not recognize, at any depth of the manifest.
+///
+/// Valid keys are determined by the [`ManifestSchema`], and passthrough tables, like `[tool]` or
+/// target parameters, are not checked.
+/// Manifests which cannot be parsed yield no keys, as these fail to deserialize anyway.
+/// Keys are returned in the order in which they appear in the manifest.
+pub fn find_unknown_manifest_keys(contents: &str) -> Vec<UnknownManifestKey> {
+    let Ok(document) = ImDocument::parse(contents) else {
+        return Vec::new();
+    };
+    let mut unknown = Vec::new();
+    check_table(
+        document.as_table(),
+        ManifestSchema::manifest(),
+        None,
+        &mut unknown,
+    );
+    unknown.sort_by_key(|key| key.span.as_ref().map(|span| span.start));
+    unknown
+}
+
+fn check_item(
+    item: &Item,
+    schema: &ManifestSchema,
+    path: &str,
+    unknown: &mut Vec<UnknownManifestKey>,
+) {
+    match schema {
+        ManifestSchema::Table(_) | ManifestSchema::Map(_) => {
+            if let Some(table) = item.as_table_like() {
+                check_table(table, schema, Some(path), unknown);
+            }
+        }
+        ManifestSchema::Array(items) => {
+            if let Some(array) = item.as_array_of_tables() {
+                for table in array {
+                    check_table(table, items, Some(path), unknown);
+                }
+            } else if let Some(array) = item.as_array() {
+                for table in array.iter().filter_map(Value::as_inline_table) {
+                    check_table(table, items, Some(path), unknown);
+                }
+            }
+        }
+        ManifestSchema::Value | ManifestSchema::Enum(_) | ManifestSchema::Any => {}
+    }
+}
+
+fn check_table(
+    table: &dyn TableLike,
+    schema: &ManifestSchema,
+    path: Option<&str>,
+    unknown: &mut Vec<UnknownManifestKey>,
+) {
+    for (name, item) in table.iter() {
+        let key_path = match path {
+            Some(path) => format!("{path}.{name}"),
+            None => name.to_string(),
+        };
+        match schema {
+            ManifestSchema::Table(keys) => match keys.get(name) {
+                Some(schema) => check_item(item, schema, &key_path, unknown),
+                None => unknown.push(UnknownManifestKey {
+                    path: key_path,
+                    span: table.get_key_value(name).and_then(|(key, _)| key.span()),
+                    suggestion: suggest(name, keys.keys().copied()).map(ToString::to_string),
+                }),
+            },
+            ManifestSchema::Map(values) => check_item(item, values, &key_path, unknown),
+            ManifestSchema::Array(_)
+            | ManifestSchema::Value
+            | ManifestSchema::Enum(_)
+            | ManifestSchema::Any => {}
+        }
+    }
+}
+
+/// Find the candidate most similar to `name`, if any is similar enough to be a likely typo.
+pub(super) fn suggest<'a>(
+    name: &str,
+    candidates: impl IntoIterator<Item = &'a str>,
+) -> Option<&'a str> {
+    let max_distance = (name.chars().count() / 3).max(1);
+    candidates
+        .into_iter()
+        .map(|candidate| (edit_distance(name, candidate), candidate))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+/// Levenshtein distance between two strings.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut row = (0..=b.len()).collect::<Vec<_>>();
+    for (i, a) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, b) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(a != *b);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(row[j + 1] + 1);
+        }
+    }
+    row[b.len()]
+}
+
+/// One-based line and column of a byte offset.
+pub(super) fn line_column(contents: &str, offset: usize) -> (usize, usize) {
+    let before = &contents[..offset];
+    let line = before.matches('\n').count() + 1;
+    let column = before.len() - before.rfind('\n').map_or(0, |i| i + 1) + 1;
+    (line, column)
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+
+    use super::{edit_distance, find_unknown_manifest_keys, UnknownManifestKey};
+
+    #[test]
+    fn edit_distance_counts_single_character_edits() {
+        assert_eq!(edit_distance("edition", "edition"), 0);
+        assert_eq!(edit_distance("editon", "edition"), 1);
+        assert_eq!(edit_distance("dependancies", "dependencies"), 1);
+        assert_eq!(edit_distance("", "abc"), 3);
+    }
+
+    #[test]
+    fn misspelled_package_key() {
+        let contents = indoc! {r#"
+            [package]
+            name = "hello"
+            version = "0.1.0"
+            editon = "2024_07"
+        "#};
+        let start = contents.find("editon").unwrap();
+        assert_eq!(
+            find_unknown_manifest_keys(contents),
+            vec![UnknownManifestKey {
+                path: "package.editon".to_string(),
+                span: Some(start..start + "editon".len()),
+                suggestion: Some("edition".to_string()),
+            }]
+        );
+    }
+
+    #[test]
+    fn misspelled_top_level_section() {
+        let contents = indoc! {r#"
+            [package]
+            name = "hello"
+            version = "0.1.0"
+
+            [dependancies]
+            foo = "1.0.0"
+        "#};
+        let keys = find_unknown_manifest_keys(contents);
+        assert_eq!(keys.len(), 1);
+        assert_eq!(keys[0].path, "dependancies");
+        assert_eq!(keys[0].suggestion.as_deref(), Some("dependencies"));
+    }
+
+    #[test]
+    fn unrelated_keys_have_no_suggestion() {
+        let contents = indoc! {r#"
+            [package]
+            name = "hello"
+            version = "0.1.0"
+            completely-unrelated = true
+        "#};
+        let keys = find_unknown_manifest_keys(contents);
+        assert_eq!(keys.len(), 1);
+        assert_eq!(keys[0].suggestion, None);
+    }
+
+    #[test]
+    fn misspelled_nested_keys() {
+        let contents = indoc! {r#"
+            [package]
+            name = "hello"
+            version = "0.1.0"
+
+            [dependencies]
+            foo = { path = "../foo", brnch = "main" }
+            bar.workspace = true
+            bar.optinal = true
+
+            [workspace.package]
+            editon = "2024_07"
+
+            [profile.release.cairo]
+            sierra-replace-id = true
+
+            [lib]
+            siera = true
+        "#};
+        let keys = find_unknown_manifest_keys(contents)
+            .into_iter()
+            .map(|key| (key.path, key.suggestion))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            keys,
+            [
+                (
+                    "dependencies.foo.brnch".to_string(),
+                    Some("branch".to_string())
+                ),
+                ("dependencies.bar.optinal".to_string(), None),
+                (
+                    "workspace.package.editon".to_string(),
+                    Some("edition".to_string())
+                ),
+                (
+                    "profile.release.cairo.sierra-replace-id".to_string(),
+                    Some("sierra-replace-ids".to_string())
+                ),
+                ("lib.siera".to_string(), Some("sierra".to_string())),
+            ]
+        );
+    }
+
+    #[test]
+    fn passthrough_tables_are_not_checked() {
+        let contents = indoc! {r#"
+            [package]
+            name = "hello"
+            version = "0.1.0"
+
+            [lib]
+            sierra = true
+
+            [tool.snforge]
+            anything = "goes"
+
+            [[target.starknet-contract]]
+            custom-param = true
+        "#};
+        assert_eq!(find_unknown_manifest_keys(contents), vec![]);
+    }
+
+    #[test]
+    fn warning_points_at_key() {
+        let contents = "[package]\nname = \"hello\"\neditonn = \"2024_07\"\n";
+        let keys = find_unknown_manifest_keys(contents);
+        assert_eq!(
+            keys[0].to_warning("Scarb.toml".into(), contents),
+            "unused manifest key: package.editonn\n --> Scarb.toml:3:1\nhelp: did you mean `edition`?"
+        );
+    }
+}
@@ -2,8 +2,12 @@
 //!
 //! For read operations and workspace mutations, see [`crate::ops`] module.
 
+pub use cancellation::CancellationToken;
 pub use checksum::*;
 pub use config::Config;
+pub use config_file::{
+    ConfigFile, DetailedEnvConfigValue, EnvConfigValue, NetConfig, ProfileConfig, RegistryConfig,
+};
 pub use dirs::AppDirs;
 pub use manifest::*;
 pub use package::{Package, PackageId, PackageIdInner, PackageInner, PackageName};
@@ -11,8 +15,10 @@ pub use resolver::Resolve;
 pub use source::{GitReference, SourceId, SourceIdInner, SourceKind};
 pub use workspace::{Utf8PathWorkspaceExt, Workspace};
 
+pub(crate) mod cancellation;
 mod checksum;
 pub(crate) mod config;
+pub(crate) mod config_file;
 mod dirs;
 pub mod errors;
 pub(crate) mod lockfile;
@@ -47,6 +47,7 @@ pub fn prepare_manifest_for_publish(pkg: &Package) -> Result<TomlManifest> {
         scripts: None,
         tool,
         features: None,
+        patch: None,
     })
 }
 
@@ -80,6 +81,7 @@ fn generate_package(pkg: &Package) -> Box<TomlPackage> {
         }),
         no_core: summary.no_core.then_some(true),
         cairo_version: metadata.cairo_version.clone().map(MaybeWorkspace::Defined),
+        scarb_version: metadata.scarb_version.clone().map(MaybeWorkspace::Defined),
         experimental_features: pkg.manifest.experimental_features.clone(),
     })
 }
@@ -157,8 +159,10 @@ fn generate_cairo_plugin(pkg: &Package) -> Option<TomlTarget<TomlCairoPluginTarg
     Some(TomlTarget {
         name: Some(target.name.clone()),
         source_path: None,
+        edition: None,
         params: TomlCairoPluginTargetParams {
             builtin: params.builtin.and_then(|b| b.then_some(true)),
+            prebuilt: params.prebuilt,
         },
     })
 }
@@ -1,5 +1,6 @@
-use anyhow::Result;
+use anyhow::{bail, Result};
 use async_trait::async_trait;
+use semver::VersionReq;
 use tracing::debug;
 
 use crate::core::registry::patch_map::PatchMap;
@@ -27,11 +28,31 @@ impl Registry for RegistryPatcher<'_> {
     async fn query(&self, dependency: &ManifestDependency) -> Result<Vec<Summary>> {
         let patch = self.patch_map.lookup(dependency);
 
-        if patch != dependency {
-            debug!(%dependency, %patch);
+        if patch == dependency {
+            return self.registry.query(dependency).await;
         }
 
-        self.registry.query(patch).await
+        debug!(%dependency, %patch);
+        let summaries = self.registry.query(patch).await?;
+
+        // Local packages replace packages of other sources as they are, so their versions must
+        // still satisfy the requirement of the patched dependency.
+        if patch.source_id.is_path() {
+            let req = VersionReq::from(dependency.version_req.clone());
+            if let Some(summary) = summaries
+                .iter()
+                .find(|summary| !req.matches(&summary.package_id.version))
+            {
+                bail!(
+                    "patch for `{}` points to version {}, which does not match \
+                    the version requirement `{req}`",
+                    dependency.name,
+                    summary.package_id.version,
+                );
+            }
+        }
+
+        Ok(summaries)
     }
 
     async fn download(&self, package_id: PackageId) -> Result<Package> {
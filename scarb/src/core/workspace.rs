@@ -1,4 +1,4 @@
-use std::collections::{BTreeMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
 use std::fmt;
 
 use anyhow::{anyhow, bail, Result};
@@ -10,8 +10,10 @@ use smol_str::SmolStr;
 use crate::compiler::Profile;
 use crate::core::config::Config;
 use crate::core::package::Package;
-use crate::core::{PackageId, ScriptDefinition, Target};
+use crate::core::{ManifestDependency, PackageId, ScriptDefinition, Target, TomlProfile};
 use crate::flock::Filesystem;
+use crate::internal::fsx;
+use crate::sources::canonical_url::CanonicalUrl;
 use crate::{DEFAULT_TARGET_DIR_NAME, LOCK_FILE_NAME, MANIFEST_FILE_NAME};
 
 /// The core abstraction for working with a workspace of packages.
@@ -23,7 +25,9 @@ pub struct Workspace<'c> {
     members: BTreeMap<PackageId, Package>,
     manifest_path: Utf8PathBuf,
     profiles: Vec<Profile>,
+    profile_definition: TomlProfile,
     scripts: BTreeMap<SmolStr, ScriptDefinition>,
+    patch: BTreeMap<CanonicalUrl, Vec<ManifestDependency>>,
     root_package: Option<PackageId>,
     target_dir: Filesystem,
 }
@@ -35,8 +39,12 @@ impl<'c> Workspace<'c> {
         root_package: Option<PackageId>,
         config: &'c Config,
         profiles: Vec<Profile>,
+        profile_definition: TomlProfile,
         scripts: BTreeMap<SmolStr, ScriptDefinition>,
+        patch: BTreeMap<CanonicalUrl, Vec<ManifestDependency>>,
     ) -> Result<Self> {
+        warn_duplicate_package_names(packages, config);
+
         let targets = packages
             .iter()
             .flat_map(|p| p.manifest.targets.iter())
@@ -58,10 +66,12 @@ impl<'c> Workspace<'c> {
             config,
             manifest_path,
             profiles,
+            profile_definition,
             root_package,
             target_dir,
             members: packages,
             scripts,
+            patch,
         })
     }
 
@@ -69,6 +79,8 @@ impl<'c> Workspace<'c> {
         package: Package,
         config: &'c Config,
         profiles: Vec<Profile>,
+        profile_definition: TomlProfile,
+        patch: BTreeMap<CanonicalUrl, Vec<ManifestDependency>>,
     ) -> Result<Self> {
         let manifest_path = package.manifest_path().to_path_buf();
         let root_package = Some(package.id);
@@ -79,7 +91,9 @@ impl<'c> Workspace<'c> {
             root_package,
             config,
             profiles,
+            profile_definition,
             BTreeMap::new(),
+            patch,
         )
     }
 
@@ -155,6 +169,70 @@ impl<'c> Workspace<'c> {
         self.members_count() == 1
     }
 
+    /// Returns all packages in this workspace, sorted so that each package comes after all
+    /// workspace members it depends on.
+    ///
+    /// Only dependencies that propagate to dependents (i.e. not `[dev-dependencies]`) are taken
+    /// into account, and only when they point to a workspace member by path.
+    /// Packages with no ordering constraints between them are sorted by their IDs.
+    ///
+    /// Returns an error describing the cycle if members depend on each other cyclically.
+    pub fn members_in_topological_order(&self) -> Result<Vec<Package>> {
+        // Maps each member to the set of members it depends on.
+        let mut dependencies: BTreeMap<PackageId, BTreeSet<PackageId>> = self
+            .members
+            .values()
+            .map(|package| (package.id, self.member_dependencies(package)))
+            .collect();
+
+        let mut sorted = Vec::with_capacity(dependencies.len());
+        while !dependencies.is_empty() {
+            let ready = dependencies
+                .iter()
+                .filter(|(_, deps)| deps.is_empty())
+                .map(|(id, _)| *id)
+                .collect_vec();
+
+            if ready.is_empty() {
+                bail!(
+                    "workspace members form a dependency cycle: {}",
+                    find_cycle(&dependencies)
+                        .iter()
+                        .map(|id| format!("`{}`", id.name))
+                        .join(" -> ")
+                );
+            }
+
+            for id in ready {
+                dependencies.remove(&id);
+                for deps in dependencies.values_mut() {
+                    deps.remove(&id);
+                }
+                sorted.push(self.members[&id].clone());
+            }
+        }
+        Ok(sorted)
+    }
+
+    fn member_dependencies(&self, package: &Package) -> BTreeSet<PackageId> {
+        package
+            .manifest
+            .summary
+            .full_dependencies()
+            .filter(|dep| dep.kind.is_propagated())
+            .filter_map(|dep| {
+                let dep_manifest = dep.source_id.to_path()?;
+                let dep_manifest = fsx::canonicalize_utf8(&dep_manifest).unwrap_or(dep_manifest);
+                self.members
+                    .values()
+                    .find(|member| {
+                        member.id.name == dep.name && member.manifest_path() == dep_manifest
+                    })
+                    .map(|member| member.id)
+            })
+            .collect()
+    }
+
     pub fn has_profile(&self, profile: &Profile) -> bool {
         self.profiles.contains(profile)
     }
@@ -167,6 +245,11 @@ impl<'c> Workspace<'c> {
         Ok(profile)
     }
 
+    /// Settings of the current profile, resolved from the workspace root manifest.
+    pub fn profile_definition(&self) -> &TomlProfile {
+        &self.profile_definition
+    }
+
     pub fn profile_names(&self) -> Vec<String> {
         let mut names = self
             .profiles
@@ -184,11 +267,57 @@ impl<'c> Workspace<'c> {
         &self.scripts
     }
 
+    /// Dependencies overriding packages of other sources, keyed by the patched source.
+    ///
+    /// Only the `[patch]` table of the workspace root manifest is taken into account.
+    pub fn patch(&self) -> &BTreeMap<CanonicalUrl, Vec<ManifestDependency>> {
+        &self.patch
+    }
+
     pub fn script(&self, name: &SmolStr) -> Option<&ScriptDefinition> {
         self.scripts.get(name)
     }
 }
 
+/// Finds a cycle in a dependency graph in which each node has at least one dependency.
+///
+/// The returned path starts and ends with the same package.
+fn find_cycle(dependencies: &BTreeMap<PackageId, BTreeSet<PackageId>>) -> Vec<PackageId> {
+    let mut path: Vec<PackageId> = Vec::new();
+    let mut current = *dependencies
+        .keys()
+        .next()
+        .expect("dependency graph must not be empty");
+    loop {
+        if let Some(start) = path.iter().position(|id| *id == current) {
+            let mut cycle = path.split_off(start);
+            cycle.push(current);
+            return cycle;
+        }
+        path.push(current);
+        current = *dependencies[&current]
+            .first()
+            .expect("each package in a cycle must have a dependency");
+    }
+}
+
+fn warn_duplicate_package_names(packages: &[Package], config: &Config) {
+    let by_name = packages.iter().into_group_map_by(|p| p.id.name.clone());
+    for (name, packages) in by_name.into_iter().sorted_by(|(a, _), (b, _)| a.cmp(b)) {
+        if packages.len() > 1 {
+            let manifests = packages
+                .iter()
+                .map(|p| format!("\n --> {}", p.manifest_path()))
+                .sorted()
+                .collect::<String>();
+            config.ui().warn(format!(
+                "workspace contains multiple packages named `{name}`{manifests}\n\
+                 help: use different package names to resolve the conflict"
+            ));
+        }
+    }
+}
+
 fn check_unique_targets(targets: &Vec<&Target>) -> Result<()> {
     let mut used = HashSet::with_capacity(targets.len());
     for target in targets {
@@ -258,6 +387,14 @@ impl PackagesSource for Workspace<'_> {
         package.id.name.as_str()
     }
 
+    fn package_version_of(package: &Self::Package) -> String {
+        package.id.version.to_string()
+    }
+
+    fn package_id_of(package: &Self::Package) -> String {
+        package.id.to_serialized_string()
+    }
+
     fn members(&self) -> Vec<Self::Package> {
         Workspace::members(self).collect()
     }
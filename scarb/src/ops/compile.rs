@@ -2,24 +2,38 @@ use anyhow::{anyhow, Context, Error, Result};
 use cairo_lang_compiler::db::RootDatabase;
 use cairo_lang_compiler::diagnostics::DiagnosticsError;
 use cairo_lang_utils::Upcast;
+use camino::{Utf8Path, Utf8PathBuf};
 use indoc::formatdoc;
-use itertools::Itertools;
+use itertools::{Either, Itertools};
 use scarb_ui::args::FeaturesSpec;
 use scarb_ui::components::Status;
 use scarb_ui::HumanDuration;
+use serde::Serialize;
 use smol_str::{SmolStr, ToSmolStr};
 use std::collections::HashSet;
+use std::num::NonZeroUsize;
+use std::sync::Mutex;
 use std::thread;
 
 use crate::compiler::db::{build_scarb_root_database, has_starknet_plugin, ScarbDatabase};
 use crate::compiler::helpers::{build_compiler_config, collect_main_crate_ids};
 use crate::compiler::plugin::proc_macro;
-use crate::compiler::{CairoCompilationUnit, CompilationUnit, CompilationUnitAttributes};
+use crate::compiler::{
+    collect_future_incompat_notices, CairoCompilationUnit, CompilationUnit,
+    CompilationUnitAttributes, FutureIncompatReport, ProcMacroCompilationUnit, UnitFingerprint,
+    UnitNoticesFile,
+};
+use crate::core::cancellation::check_cancelled;
+use crate::core::errors::Cancelled;
 use crate::core::{
-    FeatureName, PackageId, PackageName, TargetKind, Utf8PathWorkspaceExt, Workspace,
+    CancellationToken, FeatureName, PackageId, PackageName, TargetKind, Utf8PathWorkspaceExt,
+    Workspace,
 };
+use crate::internal::fsx;
+use crate::internal::to_version::ToVersion;
 use crate::ops;
-use crate::ops::{get_test_package_ids, validate_features, CompilationUnitsOpts};
+use crate::ops::{get_test_package_ids, validate_features, CompilationUnitsOpts, ResolveOpts};
+use crate::version;
 
 #[derive(Debug, Clone)]
 pub enum FeaturesSelector {
@@ -60,6 +74,21 @@ pub struct CompileOpts {
     pub include_target_names: Vec<SmolStr>,
     pub features: FeaturesOpts,
     pub ignore_cairo_version: bool,
+    /// Directory to copy final artifacts of the compiled targets to, after a successful build.
+    pub out_dir: Option<Utf8PathBuf>,
+    /// Print a report of code which will be rejected by a future version of Cairo.
+    pub future_incompat_report: bool,
+    /// Write a `<artifact>.meta.json` file describing the provenance of each final artifact of
+    /// the compiled targets next to it.
+    pub emit_metadata: bool,
+    /// Token to stop the build early, checked before each compilation unit.
+    ///
+    /// Units which are already being compiled are finished before the build stops.
+    pub cancellation: Option<CancellationToken>,
+    /// Maximum number of procedural macro plugins to build concurrently.
+    ///
+    /// Defaults to the number of available CPUs.
+    pub jobs: Option<NonZeroUsize>,
 }
 
 impl CompileOpts {
@@ -70,19 +99,18 @@ impl CompileOpts {
         target_names: Vec<String>,
         target_kinds: Vec<String>,
     ) -> Result<Self> {
-        let (include_targets, exclude_targets): (Vec<TargetKind>, Vec<TargetKind>) = if test {
-            (vec![TargetKind::TEST.clone()], Vec::new())
-        } else {
-            (Vec::new(), vec![TargetKind::TEST.clone()])
-        };
-        let include_targets = if !target_kinds.is_empty() {
-            target_kinds
-                .into_iter()
-                .map(TargetKind::try_new)
-                .collect::<Result<Vec<TargetKind>>>()?
-        } else {
-            include_targets
-        };
+        let (include_targets, exclude_targets): (Vec<TargetKind>, Vec<TargetKind>) =
+            if !target_kinds.is_empty() {
+                let include_targets = target_kinds
+                    .into_iter()
+                    .map(TargetKind::try_new)
+                    .collect::<Result<Vec<TargetKind>>>()?;
+                (include_targets, Vec::new())
+            } else if test {
+                (vec![TargetKind::TEST.clone()], Vec::new())
+            } else {
+                (Vec::new(), vec![TargetKind::TEST.clone()])
+            };
         Ok(Self {
             include_target_kinds: include_targets,
             exclude_target_kinds: exclude_targets,
@@ -92,8 +120,22 @@ impl CompileOpts {
                 .collect_vec(),
             features: features.try_into()?,
             ignore_cairo_version,
+            out_dir: None,
+            future_incompat_report: false,
+            emit_metadata: false,
+            cancellation: None,
+            jobs: None,
         })
     }
+
+    fn jobs(&self) -> usize {
+        self.jobs
+            .map_or_else(available_parallelism, NonZeroUsize::get)
+    }
+}
+
+fn available_parallelism() -> usize {
+    thread::available_parallelism().map_or(1, NonZeroUsize::get)
 }
 
 #[tracing::instrument(skip_all, level = "debug")]
@@ -106,18 +148,23 @@ pub fn check(packages: Vec<PackageId>, opts: CompileOpts, ws: &Workspace<'_>) ->
     process(packages, opts, ws, check_units, Some("checking"))
 }
 
-#[tracing::instrument(skip_all, level = "debug")]
-fn process<F>(
+/// Resolve the workspace and generate compilation units of the given packages, which are
+/// selected by the given options.
+///
+/// Procedural macro units are always selected, so that Cairo units can load them.
+pub(crate) fn select_compilation_units(
     packages: Vec<PackageId>,
-    opts: CompileOpts,
+    opts: &CompileOpts,
+    load_prebuilt_macros: bool,
     ws: &Workspace<'_>,
-    mut operation: F,
-    operation_type: Option<&str>,
-) -> Result<()>
-where
-    F: FnMut(Vec<CompilationUnit>, &Workspace<'_>) -> Result<()>,
-{
-    let resolve = ops::resolve_workspace(ws)?;
+) -> Result<Vec<CompilationUnit>> {
+    let resolve = ops::resolve_workspace_with_opts(
+        ws,
+        &ResolveOpts {
+            cancellation: opts.cancellation.clone(),
+            ..Default::default()
+        },
+    )?;
     let packages_to_process = ws
         .members()
         .filter(|p| packages.contains(&p.id))
@@ -131,7 +178,7 @@ where
         ws,
         CompilationUnitsOpts {
             ignore_cairo_version: opts.ignore_cairo_version,
-            load_prebuilt_macros: true,
+            load_prebuilt_macros,
         },
     )?
     .into_iter()
@@ -162,8 +209,48 @@ where
         }
     })
     .collect::<Vec<_>>();
+    Ok(compilation_units)
+}
 
-    operation(compilation_units, ws)?;
+#[tracing::instrument(skip_all, level = "debug")]
+fn process<F>(
+    packages: Vec<PackageId>,
+    opts: CompileOpts,
+    ws: &Workspace<'_>,
+    mut operation: F,
+    operation_type: Option<&str>,
+) -> Result<()>
+where
+    F: FnMut(Vec<CompilationUnit>, &CompileOpts, &Workspace<'_>) -> Result<()>,
+{
+    let compilation_units = select_compilation_units(packages, &opts, true, ws)?;
+    warn_incompatible_scarb_version(&compilation_units, ws);
+
+    let output_targets = compilation_units
+        .iter()
+        .filter_map(|cu| match cu {
+            CompilationUnit::Cairo(cu) => Some(OutputTarget::try_new(cu, opts.emit_metadata, ws)),
+            CompilationUnit::ProcMacro(_) => None,
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let notices_files = compilation_units
+        .iter()
+        .filter_map(|cu| match cu {
+            CompilationUnit::Cairo(cu) => Some(UnitNoticesFile::new(cu, ws)),
+            CompilationUnit::ProcMacro(_) => None,
+        })
+        .collect_vec();
+
+    operation(compilation_units, &opts, ws)?;
+
+    if opts.emit_metadata {
+        emit_artifacts_metadata(&output_targets, ws)?;
+    }
+
+    if let Some(out_dir) = &opts.out_dir {
+        copy_artifacts(&output_targets, opts.emit_metadata, out_dir)?;
+    }
 
     let elapsed_time = HumanDuration(ws.config().elapsed_time());
     let profile = ws.current_profile()?;
@@ -175,70 +262,361 @@ where
         .ui()
         .print(Status::new("Finished", &formatted_message));
 
+    if opts.future_incompat_report {
+        let future_incompat_report = FutureIncompatReport::collect(&notices_files)?;
+        future_incompat_report.save(ws)?;
+        future_incompat_report.print(ws.config());
+    }
+
+    Ok(())
+}
+
+/// Warn about packages requiring a newer version of Scarb than the one running.
+fn warn_incompatible_scarb_version(units: &[CompilationUnit], ws: &Workspace<'_>) {
+    let current_version = version::get().version.to_version().unwrap();
+    let packages = units
+        .iter()
+        .flat_map(|unit| unit.components())
+        .map(|component| &component.package)
+        .unique_by(|package| package.id);
+    for package in packages {
+        let version_req = &package.manifest.metadata.scarb_version;
+        if let Some(version_req) = version_req
+            .as_ref()
+            .filter(|version_req| !version_req.matches(&current_version))
+        {
+            ws.config().ui().warn(format!(
+                "package `{}` requires Scarb version `{version_req}`, \
+                but the current version is `{current_version}`",
+                package.id.name
+            ));
+        }
+    }
+}
+
+/// A compiled target whose final artifacts can be copied to the output directory.
+struct OutputTarget {
+    kind: TargetKind,
+    name: SmolStr,
+    target_dir: Utf8PathBuf,
+    package_id: PackageId,
+    /// Fingerprint of the unit inputs, only computed if artifact metadata is emitted.
+    fingerprint: Option<String>,
+}
+
+impl OutputTarget {
+    fn try_new(
+        unit: &CairoCompilationUnit,
+        emit_metadata: bool,
+        ws: &Workspace<'_>,
+    ) -> Result<Self> {
+        let fingerprint = if emit_metadata {
+            UnitFingerprint::try_new(unit, ws)?.map(|fingerprint| fingerprint.hash().to_string())
+        } else {
+            None
+        };
+        Ok(Self {
+            kind: unit.main_component().target_kind(),
+            name: unit.main_component().target_name(),
+            target_dir: unit.target_dir(ws).path_unchecked().to_path_buf(),
+            package_id: unit.main_package_id(),
+            fingerprint,
+        })
+    }
+
+    /// Names of the artifact files produced for this target, relative to its target directory.
+    fn artifacts(&self) -> Result<Vec<String>> {
+        let name = &self.name;
+        let (mut artifacts, starknet_artifacts) = if self.kind == TargetKind::LIB {
+            (
+                vec![
+                    format!("{name}.sierra.json"),
+                    format!("{name}.sierra"),
+                    format!("{name}.casm"),
+                ],
+                None,
+            )
+        } else if self.kind == TargetKind::EXECUTABLE {
+            (vec![format!("{name}.executable.json")], None)
+        } else if self.kind == TargetKind::STARKNET_CONTRACT {
+            (Vec::new(), Some(format!("{name}.starknet_artifacts.json")))
+        } else if self.kind == TargetKind::TEST {
+            (
+                vec![
+                    format!("{name}.test.json"),
+                    format!("{name}.test.sierra.json"),
+                ],
+                Some(format!("{name}.test.starknet_artifacts.json")),
+            )
+        } else {
+            (Vec::new(), None)
+        };
+
+        if let Some(starknet_artifacts) = starknet_artifacts {
+            let path = self.target_dir.join(&starknet_artifacts);
+            if path.exists() {
+                artifacts.extend(read_starknet_artifacts(&path)?);
+                artifacts.push(starknet_artifacts);
+            }
+        }
+
+        artifacts.retain(|file_name| self.target_dir.join(file_name).is_file());
+        Ok(artifacts)
+    }
+}
+
+/// Read names of contract class files listed in a Starknet artifacts file.
+fn read_starknet_artifacts(path: &Utf8Path) -> Result<Vec<String>> {
+    let artifacts: serde_json::Value = serde_json::from_str(&fsx::read_to_string(path)?)
+        .with_context(|| format!("failed to parse Starknet artifacts file: {path}"))?;
+    let file_names = artifacts["contracts"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .flat_map(|contract| {
+            [
+                &contract["artifacts"]["sierra"],
+                &contract["artifacts"]["casm"],
+            ]
+            .into_iter()
+            .filter_map(|file_name| file_name.as_str())
+            .map(ToString::to_string)
+        })
+        .collect();
+    Ok(file_names)
+}
+
+/// Copy final artifacts of the compiled targets to the output directory, keeping their file names.
+///
+/// If `with_metadata` is set, metadata files written next to the artifacts are copied as well.
+fn copy_artifacts(targets: &[OutputTarget], with_metadata: bool, out_dir: &Utf8Path) -> Result<()> {
+    fsx::create_dir_all(out_dir)?;
+    for target in targets {
+        for file_name in target.artifacts()? {
+            fsx::copy(target.target_dir.join(&file_name), out_dir.join(&file_name))?;
+            if with_metadata {
+                let file_name = artifact_metadata_file_name(&file_name);
+                fsx::copy(target.target_dir.join(&file_name), out_dir.join(&file_name))?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Provenance of a final artifact, written next to it as `<artifact>.meta.json`.
+#[derive(Serialize)]
+struct ArtifactMetadata<'a> {
+    artifact: &'a str,
+    package: String,
+    name: &'a str,
+    version: String,
+    target_kind: &'a str,
+    target_name: &'a str,
+    profile: &'a str,
+    scarb_version: &'static str,
+    cairo_version: &'static str,
+    /// Fingerprint of the sources and configuration the artifact has been compiled from.
+    ///
+    /// Absent for targets loading procedural macros, as these can read arbitrary inputs.
+    fingerprint: Option<&'a str>,
+}
+
+fn artifact_metadata_file_name(file_name: &str) -> String {
+    format!("{file_name}.meta.json")
+}
+
+/// Write a metadata file next to each final artifact of the compiled targets.
+fn emit_artifacts_metadata(targets: &[OutputTarget], ws: &Workspace<'_>) -> Result<()> {
+    let profile = ws.current_profile()?.to_string();
+    let version = version::get();
+    for target in targets {
+        for file_name in target.artifacts()? {
+            let metadata = ArtifactMetadata {
+                artifact: &file_name,
+                package: target.package_id.to_serialized_string(),
+                name: target.package_id.name.as_str(),
+                version: target.package_id.version.to_string(),
+                target_kind: target.kind.as_str(),
+                target_name: &target.name,
+                profile: &profile,
+                scarb_version: version.version,
+                cairo_version: version.cairo.version,
+                fingerprint: target.fingerprint.as_deref(),
+            };
+            let json = serde_json::to_string_pretty(&metadata)?;
+            let path = target
+                .target_dir
+                .join(artifact_metadata_file_name(&file_name));
+            fsx::write(path, json)?;
+        }
+    }
     Ok(())
 }
 
 /// Run compiler in a new thread.
 /// The stack size of created threads can be altered with `RUST_MIN_STACK` env variable.
-pub fn compile_units(units: Vec<CompilationUnit>, ws: &Workspace<'_>) -> Result<()> {
+///
+/// Procedural macro units are built first, concurrently, as they do not depend on each other.
+pub fn compile_units(
+    units: Vec<CompilationUnit>,
+    opts: &CompileOpts,
+    ws: &Workspace<'_>,
+) -> Result<()> {
+    let (proc_macro_units, units): (Vec<_>, Vec<_>) =
+        units.into_iter().partition_map(|unit| match unit {
+            CompilationUnit::ProcMacro(unit) => Either::Left(unit),
+            unit => Either::Right(unit),
+        });
+    compile_proc_macro_units(proc_macro_units, opts, ws)?;
     for unit in units {
+        check_cancelled(opts.cancellation.as_ref())?;
         compile_unit(unit, ws)?;
     }
     Ok(())
 }
 
-pub fn compile_unit(unit: CompilationUnit, ws: &Workspace<'_>) -> Result<()> {
+/// Build procedural macro units, running up to [`CompileOpts::jobs`] builds at a time.
+///
+/// When building concurrently, all units are built even if some of them fail, so that errors of
+/// each failed unit are reported. The error of the first failed unit is returned.
+fn compile_proc_macro_units(
+    units: Vec<ProcMacroCompilationUnit>,
+    opts: &CompileOpts,
+    ws: &Workspace<'_>,
+) -> Result<()> {
+    let units = units
+        .into_iter()
+        .filter(|unit| unit.prebuilt.is_none())
+        .collect_vec();
+    let jobs = opts.jobs().min(units.len());
+    if jobs <= 1 {
+        for unit in units {
+            check_cancelled(opts.cancellation.as_ref())?;
+            compile_proc_macro_unit(unit, None, ws)?;
+        }
+        return Ok(());
+    }
+
+    // Share available CPUs between concurrent Cargo builds, so that together they do not run
+    // more jobs than there are CPUs.
+    let cargo_jobs = (available_parallelism() / jobs).max(1);
+    let queue = Mutex::new(units.into_iter().enumerate());
+    let results = Mutex::new(Vec::new());
     thread::scope(|s| {
-        thread::Builder::new()
-            .name(format!("scarb compile {}", unit.id()))
-            .spawn_scoped(s, || compile_unit_inner(unit, ws))
-            .expect("Failed to spawn compiler thread.")
-            .join()
-            .expect("Compiler thread has panicked.")
-    })
+        for _ in 0..jobs {
+            s.spawn(|| loop {
+                let Some((index, unit)) = queue.lock().unwrap().next() else {
+                    break;
+                };
+                let result = check_cancelled(opts.cancellation.as_ref())
+                    .and_then(|()| compile_proc_macro_unit(unit, Some(cargo_jobs), ws));
+                results.lock().unwrap().push((index, result));
+            });
+        }
+    });
+
+    let mut errors = results
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .sorted_by_key(|(index, _)| *index)
+        .filter_map(|(_, result)| result.err());
+    let Some(error) = errors.next() else {
+        return Ok(());
+    };
+    // Units which have not been started due to cancellation have nothing to report.
+    for error in errors.filter(|error| !error.is::<Cancelled>()) {
+        ws.config().ui().anyhow(&error);
+    }
+    Err(error)
 }
 
-fn compile_unit_inner(unit: CompilationUnit, ws: &Workspace<'_>) -> Result<()> {
-    let package_name = unit.main_package_id().name.clone();
+/// Build a procedural macro unit, limiting Cargo to `cargo_jobs` jobs if it is built
+/// concurrently with other units.
+fn compile_proc_macro_unit(
+    unit: ProcMacroCompilationUnit,
+    cargo_jobs: Option<usize>,
+    ws: &Workspace<'_>,
+) -> Result<()> {
+    let package_name = unit.main_package_id.name.clone();
+    ws.config()
+        .ui()
+        .print(Status::new("Compiling", &unit.name()));
+    let result = match cargo_jobs {
+        Some(jobs) => proc_macro::compile_unit_concurrently(unit, jobs, ws),
+        None => proc_macro::compile_unit(unit, ws),
+    };
+    result.map_err(|err| report_unit_error(err, &package_name, ws))
+}
 
-    let result = match unit {
-        CompilationUnit::ProcMacro(unit) => {
-            if unit.prebuilt.is_some() {
-                Ok(())
-            } else {
-                ws.config()
-                    .ui()
-                    .print(Status::new("Compiling", &unit.name()));
-                proc_macro::compile_unit(unit, ws)
+pub fn compile_unit(unit: CompilationUnit, ws: &Workspace<'_>) -> Result<()> {
+    match unit {
+        CompilationUnit::ProcMacro(unit) if unit.prebuilt.is_some() => Ok(()),
+        CompilationUnit::ProcMacro(unit) => compile_proc_macro_unit(unit, None, ws),
+        CompilationUnit::Cairo(unit) => thread::scope(|s| {
+            thread::Builder::new()
+                .name(format!("scarb compile {}", unit.id()))
+                .spawn_scoped(s, || compile_unit_inner(unit, ws))
+                .expect("Failed to spawn compiler thread.")
+                .join()
+                .expect("Compiler thread has panicked.")
+        }),
+    }
+}
+
+fn compile_unit_inner(unit: CairoCompilationUnit, ws: &Workspace<'_>) -> Result<()> {
+    let package_name = unit.main_package_id.name.clone();
+    let unit_id = unit.id();
+
+    let fingerprint = UnitFingerprint::try_new(&unit, ws)?;
+    let notices_file = UnitNoticesFile::new(&unit, ws);
+    if let Some(fingerprint) = &fingerprint {
+        if let Some(warnings) = fingerprint.check_fresh() {
+            for warning in warnings {
+                warning.print(&ws.config().ui());
             }
+            ws.config().ui().verbose(Status::new("Fresh", &unit.name()));
+            return Ok(());
         }
-        CompilationUnit::Cairo(unit) => {
-            ws.config()
-                .ui()
-                .print(Status::new("Compiling", &unit.name()));
-            let ScarbDatabase {
-                mut db,
-                proc_macro_host,
-            } = build_scarb_root_database(&unit, ws, Default::default())?;
-            check_starknet_dependency(&unit, ws, &db, &package_name);
-            let result = ws.config().compilers().compile(unit, &mut db, ws);
-            proc_macro_host
-                .post_process(db.upcast())
-                .context("procedural macro post processing callback failed")?;
-            result
-        }
+        fingerprint.clear()?;
+    }
+    let output_target = OutputTarget::try_new(&unit, false, ws)?;
+
+    ws.config()
+        .ui()
+        .print(Status::new("Compiling", &unit.name()));
+    let ScarbDatabase {
+        mut db,
+        proc_macro_host,
+    } = build_scarb_root_database(&unit, ws, Default::default())?;
+    check_starknet_dependency(&unit, ws, &db, &package_name);
+    let main_crate_ids = collect_main_crate_ids(&unit, &db);
+    let result = ws.config().compilers().compile(unit, &mut db, ws);
+    proc_macro_host
+        .post_process(db.upcast())
+        .context("procedural macro post processing callback failed")?;
+    let notices = match &result {
+        Ok(()) => collect_future_incompat_notices(&db, &main_crate_ids),
+        Err(_) => Vec::new(),
     };
+    notices_file.save(&notices)?;
+    let warnings = ws.config().unit_warnings().take(&unit_id);
+    if let (Ok(()), Some(fingerprint)) = (&result, &fingerprint) {
+        fingerprint.save(output_target.artifacts()?, warnings)?;
+    }
 
-    result.map_err(|err| {
-        if !suppress_error(&err) {
-            ws.config().ui().anyhow(&err);
-        }
+    result.map_err(|err| report_unit_error(err, &package_name, ws))
+}
 
-        anyhow!("could not compile `{package_name}` due to previous error")
-    })
+fn report_unit_error(err: Error, package_name: &PackageName, ws: &Workspace<'_>) -> Error {
+    if !suppress_error(&err) {
+        ws.config().ui().anyhow(&err);
+    }
+
+    anyhow!("could not compile `{package_name}` due to previous error")
 }
 
-fn check_units(units: Vec<CompilationUnit>, ws: &Workspace<'_>) -> Result<()> {
+fn check_units(units: Vec<CompilationUnit>, opts: &CompileOpts, ws: &Workspace<'_>) -> Result<()> {
     // Select proc macro units that need to be compiled for Cairo compilation units.
     let required_plugins = units
         .iter()
@@ -252,8 +630,16 @@ fn check_units(units: Vec<CompilationUnit>, ws: &Workspace<'_>) -> Result<()> {
         })
         .collect::<HashSet<PackageId>>();
 
-    // We guarantee that proc-macro units are always processed first,
-    // so that all required plugins are compiled before we start checking Cairo units.
+    // We guarantee that required plugins are compiled first,
+    // before we start checking Cairo units.
+    let (plugins, units): (Vec<_>, Vec<_>) = units.into_iter().partition_map(|unit| match unit {
+        CompilationUnit::ProcMacro(unit) if required_plugins.contains(&unit.main_package_id) => {
+            Either::Left(unit)
+        }
+        unit => Either::Right(unit),
+    });
+    compile_proc_macro_units(plugins, opts, ws)?;
+
     let units = units.into_iter().sorted_by_key(|unit| {
         if matches!(unit, CompilationUnit::ProcMacro(_)) {
             0
@@ -261,15 +647,9 @@ fn check_units(units: Vec<CompilationUnit>, ws: &Workspace<'_>) -> Result<()> {
             1
         }
     });
-
     for unit in units {
-        if matches!(unit, CompilationUnit::ProcMacro(_))
-            && required_plugins.contains(&unit.main_package_id())
-        {
-            compile_unit(unit, ws)?;
-        } else {
-            check_unit(unit, ws)?;
-        }
+        check_cancelled(opts.cancellation.as_ref())?;
+        check_unit(unit, ws)?;
     }
     Ok(())
 }
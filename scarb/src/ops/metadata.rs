@@ -1,6 +1,8 @@
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, HashMap, HashSet};
 
 use anyhow::{bail, Result};
+use cairo_lang_filesystem::db::Edition;
+use camino::Utf8PathBuf;
 use itertools::Itertools;
 use semver::{Version, VersionReq};
 use smol_str::SmolStr;
@@ -8,16 +10,18 @@ use smol_str::SmolStr;
 use scarb_metadata as m;
 use scarb_ui::args::PackagesSource;
 
+use crate::compiler::plugin::proc_macro::compilation::SharedLibraryProvider;
 use crate::compiler::{
-    CairoCompilationUnit, CompilationUnit, CompilationUnitAttributes, CompilationUnitComponent,
-    ProcMacroCompilationUnit,
+    CairoCompilationUnit, CompilationUnit, CompilationUnitAttributes, CompilationUnitCairoPlugin,
+    CompilationUnitComponent, CompilerRepository, ProcMacroCompilationUnit,
 };
 use crate::core::{
     edition_variant, DepKind, DependencyVersionReq, ManifestDependency, Package, PackageId,
     SourceId, Target, Workspace,
 };
+use crate::internal::serdex::toml_merge;
 use crate::ops;
-use crate::ops::CompilationUnitsOpts;
+use crate::ops::{CompilationUnitsOpts, WorkspaceResolve};
 use crate::version::CommitInfo;
 
 pub struct MetadataOptions {
@@ -25,10 +29,108 @@ pub struct MetadataOptions {
     pub no_deps: bool,
     pub features: ops::FeaturesOpts,
     pub ignore_cairo_version: bool,
+    /// Restrict metadata to a single compilation unit with this ID, and packages it consists of.
+    pub unit: Option<String>,
+    /// Include raw contents of the manifest file of each package.
+    pub include_manifest_source: bool,
+    /// Include default values of target parameters, as known to compilers of the targets.
+    pub include_target_defaults: bool,
 }
 
+/// Collect metadata of the workspace, in the same structure as printed by `scarb metadata`.
+///
+/// This is the in-process equivalent of running `scarb metadata` in a subprocess.
+/// Unless [`MetadataOptions::no_deps`] is set, this resolves the workspace.
+/// Use [`collect_metadata_with_resolve`] to reuse an already computed resolve.
 #[tracing::instrument(skip_all, level = "debug")]
 pub fn collect_metadata(opts: &MetadataOptions, ws: &Workspace<'_>) -> Result<m::Metadata> {
+    check_metadata_version(opts)?;
+    if opts.no_deps {
+        if let Some(unit) = &opts.unit {
+            bail!("cannot select compilation unit `{unit}` without resolving dependencies");
+        }
+        let target_defaults = target_defaults(opts, ws);
+        let packages = ws
+            .members()
+            .map(|p| collect_package_metadata(&p, opts.include_manifest_source, target_defaults))
+            .collect();
+        build_metadata(packages, Vec::new(), None, ws)
+    } else {
+        let resolve = ops::resolve_workspace(ws)?;
+        collect_metadata_with_resolve(opts, &resolve, ws)
+    }
+}
+
+/// Collect metadata of the workspace from the given resolve.
+///
+/// This allows tools which resolve the workspace on their own to produce metadata without
+/// resolving it again. The [`MetadataOptions::no_deps`] flag is ignored.
+#[tracing::instrument(skip_all, level = "debug")]
+pub fn collect_metadata_with_resolve(
+    opts: &MetadataOptions,
+    resolve: &WorkspaceResolve,
+    ws: &Workspace<'_>,
+) -> Result<m::Metadata> {
+    check_metadata_version(opts)?;
+
+    let units = ops::generate_compilation_units(
+        resolve,
+        &opts.features,
+        ws,
+        CompilationUnitsOpts {
+            ignore_cairo_version: opts.ignore_cairo_version,
+            load_prebuilt_macros: false,
+        },
+    )?;
+
+    let target_defaults = target_defaults(opts, ws);
+    let mut packages: Vec<m::PackageMetadata> = resolve
+        .packages
+        .values()
+        .map(|package| {
+            collect_package_metadata(package, opts.include_manifest_source, target_defaults)
+        })
+        .collect();
+
+    let mut compilation_units: Vec<m::CompilationUnitMetadata> = units
+        .iter()
+        .flat_map(|unit| collect_compilation_unit_metadata(unit, target_defaults, ws))
+        .collect();
+
+    if let Some(unit) = &opts.unit {
+        let Some(unit) = compilation_units.into_iter().find(|cu| &cu.id.repr == unit) else {
+            bail!("compilation unit `{unit}` not found in workspace");
+        };
+        let unit_packages = unit
+            .components
+            .iter()
+            .map(|c| &c.package)
+            .chain(unit.cairo_plugins.iter().map(|p| &p.package))
+            .chain([&unit.package])
+            .cloned()
+            .collect::<HashSet<_>>();
+        packages.retain(|p| unit_packages.contains(&p.id));
+        compilation_units = vec![unit];
+    }
+
+    build_metadata(
+        packages,
+        compilation_units,
+        Some(resolve.lockfile_up_to_date),
+        ws,
+    )
+}
+
+/// Compilers to ask for default values of target parameters, if these are requested.
+fn target_defaults<'a>(
+    opts: &MetadataOptions,
+    ws: &Workspace<'a>,
+) -> Option<&'a CompilerRepository> {
+    opts.include_target_defaults
+        .then(|| ws.config().compilers())
+}
+
+fn check_metadata_version(opts: &MetadataOptions) -> Result<()> {
     if opts.version != m::VersionPin.numeric() {
         bail!(
             "metadata version {} not supported, only {} is currently supported",
@@ -36,34 +138,15 @@ pub fn collect_metadata(opts: &MetadataOptions, ws: &Workspace<'_>) -> Result<m:
             m::VersionPin
         );
     }
+    Ok(())
+}
 
-    let (mut packages, mut compilation_units) = if !opts.no_deps {
-        let resolve = ops::resolve_workspace(ws)?;
-        let packages: Vec<m::PackageMetadata> = resolve
-            .packages
-            .values()
-            .map(collect_package_metadata)
-            .collect();
-
-        let compilation_units: Vec<m::CompilationUnitMetadata> = ops::generate_compilation_units(
-            &resolve,
-            &opts.features,
-            ws,
-            CompilationUnitsOpts {
-                ignore_cairo_version: opts.ignore_cairo_version,
-                load_prebuilt_macros: false,
-            },
-        )?
-        .iter()
-        .flat_map(collect_compilation_unit_metadata)
-        .collect();
-
-        (packages, compilation_units)
-    } else {
-        let packages = ws.members().map(|p| collect_package_metadata(&p)).collect();
-        (packages, Vec::new())
-    };
-
+fn build_metadata(
+    mut packages: Vec<m::PackageMetadata>,
+    mut compilation_units: Vec<m::CompilationUnitMetadata>,
+    lockfile_up_to_date: Option<bool>,
+    ws: &Workspace<'_>,
+) -> Result<m::Metadata> {
     packages.sort_by_key(|p| p.id.clone());
     compilation_units.sort_by_key(|c| c.package.clone());
 
@@ -77,10 +160,21 @@ pub fn collect_metadata(opts: &MetadataOptions, ws: &Workspace<'_>) -> Result<m:
         .compilation_units(compilation_units)
         .current_profile(ws.current_profile()?.to_string())
         .profiles(ws.profile_names())
+        .profile_definition(collect_profile_definition_metadata(ws)?)
+        .lockfile_path(Some(ws.lockfile_path()))
+        .lockfile_up_to_date(lockfile_up_to_date)
         .build()
         .unwrap())
 }
 
+fn collect_profile_definition_metadata(ws: &Workspace<'_>) -> Result<serde_json::Value> {
+    let definition = ws.profile_definition();
+    Ok(serde_json::json!({
+        "cairo": serde_json::to_value(&definition.cairo)?,
+        "tool": serde_json::to_value(&definition.tool)?,
+    }))
+}
+
 fn collect_workspace_metadata(ws: &Workspace<'_>) -> Result<m::WorkspaceMetadata> {
     let mut members: Vec<m::PackageId> = ws.members().map(|it| wrap_package_id(it.id)).collect();
     members.sort();
@@ -93,7 +187,11 @@ fn collect_workspace_metadata(ws: &Workspace<'_>) -> Result<m::WorkspaceMetadata
         .unwrap())
 }
 
-fn collect_package_metadata(package: &Package) -> m::PackageMetadata {
+fn collect_package_metadata(
+    package: &Package,
+    include_manifest_source: bool,
+    target_defaults: Option<&CompilerRepository>,
+) -> m::PackageMetadata {
     let mut dependencies: Vec<m::DependencyMetadata> = package
         .manifest
         .summary
@@ -106,7 +204,7 @@ fn collect_package_metadata(package: &Package) -> m::PackageMetadata {
         .manifest
         .targets
         .iter()
-        .map(collect_target_metadata)
+        .map(|target| collect_target_metadata(target, package.manifest.edition, target_defaults))
         .collect();
     targets.sort_by_key(|t| (t.kind.clone(), t.name.clone()));
 
@@ -157,6 +255,10 @@ fn collect_package_metadata(package: &Package) -> m::PackageMetadata {
         .map(|x| x.to_string())
         .collect();
 
+    let manifest_source = include_manifest_source
+        .then(|| package.manifest.contents.clone())
+        .flatten();
+
     m::PackageMetadataBuilder::default()
         .id(wrap_package_id(package.id))
         .name(package.id.name.clone())
@@ -169,6 +271,9 @@ fn collect_package_metadata(package: &Package) -> m::PackageMetadata {
         .targets(targets)
         .manifest_metadata(manifest_metadata)
         .experimental_features(experimental_features)
+        .publish(package.is_publishable())
+        .minimum_scarb_version(package.manifest.metadata.scarb_version.clone())
+        .manifest_source(manifest_source)
         .build()
         .unwrap()
 }
@@ -202,8 +307,17 @@ fn collect_dependency_kind(kind: &DepKind) -> Option<m::DepKind> {
     }
 }
 
-fn collect_target_metadata(target: &Target) -> m::TargetMetadata {
-    let mut params = toml_to_json(&target.params);
+fn collect_target_metadata(
+    target: &Target,
+    package_edition: Edition,
+    target_defaults: Option<&CompilerRepository>,
+) -> m::TargetMetadata {
+    let params = match target_defaults.and_then(|c| c.default_params(&target.kind)) {
+        Some(defaults) => toml_merge(&defaults, &target.params)
+            .expect("target parameters should always merge with defaults"),
+        None => target.params.clone(),
+    };
+    let mut params = toml_to_json(&params);
     if let Some(group) = target.group_id.as_ref() {
         params.as_object_mut().unwrap().insert(
             "group-id".to_string(),
@@ -214,6 +328,9 @@ fn collect_target_metadata(target: &Target) -> m::TargetMetadata {
         .kind(target.kind.to_string())
         .name(target.name.to_string())
         .source_path(target.source_path.clone())
+        .edition(Some(
+            edition_variant(target.edition.unwrap_or(package_edition)).to_string(),
+        ))
         .params(params)
         .build()
         .unwrap()
@@ -221,19 +338,26 @@ fn collect_target_metadata(target: &Target) -> m::TargetMetadata {
 
 fn collect_compilation_unit_metadata(
     compilation_unit: &CompilationUnit,
+    target_defaults: Option<&CompilerRepository>,
+    ws: &Workspace<'_>,
 ) -> Vec<m::CompilationUnitMetadata> {
     match compilation_unit {
         CompilationUnit::Cairo(cu) => cu
             .rewrite_to_single_source_paths()
             .into_iter()
-            .map(|cu| collect_cairo_compilation_unit_metadata(&cu))
+            .map(|cu| collect_cairo_compilation_unit_metadata(&cu, target_defaults, ws))
             .collect_vec(),
-        CompilationUnit::ProcMacro(cu) => vec![collect_proc_macro_compilation_unit_metadata(cu)],
+        CompilationUnit::ProcMacro(cu) => vec![collect_proc_macro_compilation_unit_metadata(
+            cu,
+            target_defaults,
+        )],
     }
 }
 
 fn collect_cairo_compilation_unit_metadata(
     compilation_unit: &CairoCompilationUnit,
+    target_defaults: Option<&CompilerRepository>,
+    ws: &Workspace<'_>,
 ) -> m::CompilationUnitMetadata {
     let components = collect_compilation_unit_components(compilation_unit.components.iter());
 
@@ -241,9 +365,14 @@ fn collect_cairo_compilation_unit_metadata(
         .cairo_plugins
         .iter()
         .map(|c| {
+            let (artifact_path, prebuilt) = collect_cairo_plugin_artifact(c, ws);
             m::CompilationUnitCairoPluginMetadataBuilder::default()
                 .package(wrap_package_id(c.package.id))
                 .prebuilt_allowed(c.prebuilt_allowed)
+                .version(c.package.id.version.clone())
+                .builtin(c.builtin)
+                .artifact_path(artifact_path)
+                .prebuilt(prebuilt)
                 .build()
                 .unwrap()
         })
@@ -281,6 +410,8 @@ fn collect_cairo_compilation_unit_metadata(
             // We use first_target, as compilation units with multiple targets
             // have already been rewritten to single target ones.
             &compilation_unit.main_component().first_target().clone(),
+            compilation_unit.main_component().package.manifest.edition,
+            target_defaults,
         ))
         .components(components)
         .cairo_plugins(cairo_plugins)
@@ -294,8 +425,32 @@ fn collect_cairo_compilation_unit_metadata(
         .unwrap()
 }
 
+/// Find the shared library which would be loaded for the plugin, if it has already been built
+/// or a prebuilt binary is available.
+fn collect_cairo_plugin_artifact(
+    plugin: &CompilationUnitCairoPlugin,
+    ws: &Workspace<'_>,
+) -> (Option<Utf8PathBuf>, bool) {
+    if plugin.builtin {
+        return (None, false);
+    }
+    if plugin.prebuilt_allowed {
+        // Errors of a declared prebuilt library are reported when it is loaded during the build.
+        if let Ok(Some(path)) = plugin.package.prebuilt_lib_path() {
+            return (Some(path), true);
+        }
+    }
+    let path = plugin
+        .package
+        .shared_lib_path(ws.config())
+        .ok()
+        .filter(|path| path.exists());
+    (path, false)
+}
+
 fn collect_proc_macro_compilation_unit_metadata(
     compilation_unit: &ProcMacroCompilationUnit,
+    target_defaults: Option<&CompilerRepository>,
 ) -> m::CompilationUnitMetadata {
     let components = collect_compilation_unit_components(compilation_unit.components.iter());
     assert_eq!(
@@ -308,6 +463,8 @@ fn collect_proc_macro_compilation_unit_metadata(
         .package(wrap_package_id(compilation_unit.main_package_id()))
         .target(collect_target_metadata(
             &compilation_unit.main_component().first_target().clone(),
+            compilation_unit.main_component().package.manifest.edition,
+            target_defaults,
         ))
         .components(components)
         .cairo_plugins(Vec::new())
@@ -17,6 +17,7 @@ pub use publish::*;
 pub use resolve::*;
 pub use scripts::*;
 pub use subcommands::*;
+pub use unit_graph::*;
 pub use workspace::*;
 
 mod cache;
@@ -35,4 +36,5 @@ mod publish;
 mod resolve;
 mod scripts;
 mod subcommands;
+mod unit_graph;
 mod workspace;
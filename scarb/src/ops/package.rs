@@ -1,4 +1,5 @@
 use std::collections::BTreeMap;
+use std::env;
 use std::fs::File;
 use std::io::{Seek, SeekFrom, Write};
 
@@ -39,6 +40,14 @@ const RESERVED_FILES: &[&str] = &[
 
 const SCARB_PREPACKAGE_SCRIPT_NAME: &str = "package";
 
+/// The `mtime` of archived files, if not overridden by `SOURCE_DATE_EPOCH`.
+///
+/// From `set_metadata_in_mode` implementation in `tar` crate:
+/// > We could in theory set the mtime to zero here, but not all
+/// > tools seem to behave well when ingesting files with a 0
+/// > timestamp.
+const DEFAULT_ARCHIVE_MTIME: u64 = 1;
+
 #[derive(Clone)]
 pub struct PackageOpts {
     pub allow_dirty: bool,
@@ -413,6 +422,11 @@ fn run_verify(
             include_target_names: Vec::new(),
             features,
             ignore_cairo_version,
+            out_dir: None,
+            future_incompat_report: false,
+            emit_metadata: false,
+            cancellation: None,
+            jobs: None,
         },
         &ws,
     )?;
@@ -522,6 +536,7 @@ fn tar(
     let mut ar = tar::Builder::new(encoder);
 
     let base_path = Utf8PathBuf::from(pkg_id.tarball_basename());
+    let mtime = archive_mtime()?;
 
     let mut uncompressed_size = 0;
     for ArchiveFile { path, contents } in recipe {
@@ -547,12 +562,7 @@ fn tar(
                 // https://github.com/alexcrichton/tar-rs/issues/341.
                 // Also, the constant value used there is funky and I do not feel convinced about
                 // its stability. Therefore, we use our own `mtime` value explicitly here.
-                //
-                // From `set_metadata_in_mode` implementation in `tar` crate:
-                // > We could in theory set the mtime to zero here, but not all
-                // > tools seem to behave well when ingesting files with a 0
-                // > timestamp.
-                header.set_mtime(1);
+                header.set_mtime(mtime);
 
                 header.set_cksum();
 
@@ -570,7 +580,7 @@ fn tar(
                 header.set_size(contents.len() as u64);
 
                 // Same as above.
-                header.set_mtime(1);
+                header.set_mtime(mtime);
 
                 header.set_cksum();
 
@@ -587,6 +597,19 @@ fn tar(
     Ok(uncompressed_size)
 }
 
+/// Get the `mtime` to set on all archived files.
+///
+/// Follows the [`SOURCE_DATE_EPOCH`](https://reproducible-builds.org/specs/source-date-epoch/)
+/// specification, falling back to a fixed timestamp if the variable is not set.
+fn archive_mtime() -> Result<u64> {
+    match env::var("SOURCE_DATE_EPOCH") {
+        Ok(epoch) if !epoch.is_empty() => epoch.trim().parse().with_context(|| {
+            format!("failed to parse `SOURCE_DATE_EPOCH` as a UNIX timestamp: {epoch}")
+        }),
+        _ => Ok(DEFAULT_ARCHIVE_MTIME),
+    }
+}
+
 // Checks that the package has some piece of metadata that a human can
 // use to tell what the package is about.
 fn check_metadata(pkg: &Package, config: &Config) -> Result<()> {
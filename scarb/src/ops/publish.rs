@@ -1,19 +1,26 @@
-use anyhow::{ensure, Context, Result};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use anyhow::{bail, ensure, Context, Result};
 use indoc::formatdoc;
 use url::Url;
 
 use scarb_ui::components::Status;
 
-use crate::core::registry::client::RegistryUpload;
+use crate::core::registry::client::{RegistryClient, RegistryResource, RegistryUpload};
 use crate::core::{PackageId, SourceId, Workspace};
 use crate::ops;
 use crate::sources::RegistrySource;
 
 use super::PackageOpts;
 
+const PUBLISH_WAIT_TIMEOUT: Duration = Duration::from_secs(60);
+const PUBLISH_WAIT_INTERVAL: Duration = Duration::from_secs(1);
+
 pub struct PublishOpts {
     pub index_url: Url,
     pub package_opts: PackageOpts,
+    pub dry_run: bool,
 }
 
 #[tracing::instrument(level = "debug", skip(opts, ws))]
@@ -47,6 +54,11 @@ pub fn publish(package_id: PackageId, opts: &PublishOpts, ws: &Workspace<'_>) ->
 
     let dest_package_id = package_id.with_source_id(source_id);
 
+    if opts.dry_run {
+        ws.config().ui().warn("aborting upload due to dry run");
+        return Ok(());
+    }
+
     ws.config()
         .ui()
         .print(Status::new("Uploading", &dest_package_id.to_string()));
@@ -68,3 +80,90 @@ pub fn publish(package_id: PackageId, opts: &PublishOpts, ws: &Workspace<'_>) ->
         // TODO(mkaput): Wait for publish here.
     })
 }
+
+/// Publish multiple workspace members, so that each package is published after all workspace
+/// members it depends on.
+///
+/// Packages with publishing disabled, and packages whose version is already present in the
+/// registry, are skipped.
+/// After each upload, this function waits until the package becomes available in the registry,
+/// so that its dependents can be verified against it.
+/// Publishing stops at the first failure.
+#[tracing::instrument(level = "debug", skip(opts, ws))]
+pub fn publish_many(packages: &[PackageId], opts: &PublishOpts, ws: &Workspace<'_>) -> Result<()> {
+    let source_id = SourceId::for_registry(&opts.index_url)?;
+    let registry_client = RegistrySource::create_client(source_id, ws.config())?;
+
+    let ordered = ws
+        .members_in_topological_order()?
+        .into_iter()
+        .filter(|package| packages.contains(&package.id))
+        .collect::<Vec<_>>();
+
+    for package in ordered {
+        if !package.is_publishable() {
+            ws.config().ui().print(Status::new(
+                "Skipping",
+                &format!("{} (publishing disabled)", package.id),
+            ));
+            continue;
+        }
+
+        let dest_package_id = package.id.with_source_id(source_id);
+        if is_published(registry_client.as_ref(), dest_package_id, ws)? {
+            ws.config().ui().print(Status::new(
+                "Skipping",
+                &format!("{dest_package_id} (already published)"),
+            ));
+            continue;
+        }
+
+        publish(package.id, opts, ws)?;
+
+        if !opts.dry_run {
+            wait_for_publish(registry_client.as_ref(), dest_package_id, ws)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn is_published(
+    registry_client: &dyn RegistryClient,
+    package_id: PackageId,
+    ws: &Workspace<'_>,
+) -> Result<bool> {
+    let records = ws
+        .config()
+        .tokio_handle()
+        .block_on(registry_client.get_records(package_id.name.clone(), None))
+        .with_context(|| format!("failed to query registry for package: {package_id}"))?;
+    Ok(match records {
+        RegistryResource::Download { resource, .. } => resource
+            .iter()
+            .any(|record| record.version == package_id.version),
+        RegistryResource::NotFound | RegistryResource::InCache => false,
+    })
+}
+
+fn wait_for_publish(
+    registry_client: &dyn RegistryClient,
+    package_id: PackageId,
+    ws: &Workspace<'_>,
+) -> Result<()> {
+    let start = Instant::now();
+    while !is_published(registry_client, package_id, ws)? {
+        if start.elapsed() > PUBLISH_WAIT_TIMEOUT {
+            bail!(
+                "timed out waiting for `{package_id}` to be available in registry\n\
+                help: publishing of remaining packages has been stopped"
+            );
+        }
+        ws.config().ui().verbose(Status::new(
+            "Waiting",
+            &format!("for {package_id} to be available"),
+        ));
+        thread::sleep(PUBLISH_WAIT_INTERVAL);
+    }
+    Ok(())
+}
@@ -5,6 +5,7 @@ use crate::compiler::{
     CairoCompilationUnit, CompilationUnit, CompilationUnitAttributes, CompilationUnitCairoPlugin,
     CompilationUnitComponent, CompilationUnitComponentId, ProcMacroCompilationUnit, Profile,
 };
+use crate::core::cancellation::check_cancelled;
 use crate::core::lockfile::Lockfile;
 use crate::core::package::{Package, PackageClass, PackageId};
 use crate::core::registry::cache::RegistryCache;
@@ -15,8 +16,8 @@ use crate::core::registry::Registry;
 use crate::core::resolver::Resolve;
 use crate::core::workspace::Workspace;
 use crate::core::{
-    DepKind, DependencyVersionReq, FeatureName, ManifestCompilerConfig, ManifestDependency,
-    PackageName, SourceId, Target, TargetKind, TestTargetProps, TestTargetType,
+    CancellationToken, DepKind, DependencyVersionReq, FeatureName, ManifestCompilerConfig,
+    ManifestDependency, PackageName, SourceId, Target, TargetKind, TestTargetProps, TestTargetType,
 };
 use crate::internal::to_version::ToVersion;
 use crate::ops::lockfile::{read_lockfile, write_lockfile};
@@ -27,6 +28,7 @@ use cairo_lang_filesystem::cfg::{Cfg, CfgSet};
 use futures::TryFutureExt;
 use indoc::formatdoc;
 use itertools::Itertools;
+use scarb_ui::components::Spinner;
 use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
 use std::iter::zip;
 use std::sync::Arc;
@@ -34,6 +36,8 @@ use std::sync::Arc;
 pub struct WorkspaceResolve {
     pub resolve: Resolve,
     pub packages: HashMap<PackageId, Package>,
+    /// Whether the lockfile read before resolution already matched the resolution result.
+    pub lockfile_up_to_date: bool,
 }
 
 impl WorkspaceResolve {
@@ -99,6 +103,8 @@ impl WorkspaceResolve {
 pub struct ResolveOpts {
     /// Do not use lockfile when resolving.
     pub update: bool,
+    /// Token to stop resolution early, checked while fetching packages.
+    pub cancellation: Option<CancellationToken>,
 }
 
 pub fn resolve_workspace(ws: &Workspace<'_>) -> Result<WorkspaceResolve> {
@@ -162,6 +168,9 @@ pub fn resolve_workspace_with_opts(
                     custom_source_patches.clone(),
                 );
             }
+            for (source_pattern, patches) in ws.patch() {
+                patch_map.insert(source_pattern.clone(), patches.iter().cloned());
+            }
 
             let source_map = SourceMap::preloaded(ws.members(), ws.config());
             let cached = RegistryCache::new(&source_map);
@@ -172,25 +181,48 @@ pub fn resolve_workspace_with_opts(
                 .map(|pkg| pkg.manifest.summary.clone())
                 .collect::<Vec<_>>();
 
+            let existing_lockfile = read_lockfile(ws)?;
             let lockfile: Lockfile = if opts.update {
                 Lockfile::new([])
             } else {
-                read_lockfile(ws)?
+                existing_lockfile.clone()
             };
 
+            let spinner = ws
+                .config()
+                .ui()
+                .widget(Spinner::new("Resolving dependencies"));
+
+            check_cancelled(opts.cancellation.as_ref())?;
             let resolve = resolver::resolve(&members_summaries, &patched, lockfile).await?;
 
-            write_lockfile(Lockfile::from_resolve(&resolve), ws)?;
+            let new_lockfile = Lockfile::from_resolve(&resolve);
+            let lockfile_up_to_date = new_lockfile == existing_lockfile;
+            write_lockfile(new_lockfile, ws)?;
 
-            let packages = collect_packages_from_resolve_graph(&resolve, &patched).await?;
+            if let Some(spinner) = &spinner {
+                spinner.set_message("Fetching packages");
+            }
+            let packages =
+                collect_packages_from_resolve_graph(&resolve, &patched, opts.cancellation.as_ref())
+                    .await?;
+            // Fetching Cairo plugins may spawn processes, which display spinners on their own.
+            drop(spinner);
 
             packages
                 .values()
                 .filter(|p| p.is_cairo_plugin())
-                .map(|p| fetch_cairo_plugin(p, ws))
+                .map(|p| {
+                    check_cancelled(opts.cancellation.as_ref())?;
+                    fetch_cairo_plugin(p, ws)
+                })
                 .collect::<Result<Vec<()>>>()?;
 
-            Ok(WorkspaceResolve { resolve, packages })
+            Ok(WorkspaceResolve {
+                resolve,
+                packages,
+                lockfile_up_to_date,
+            })
         }
         .into_future(),
     )
@@ -205,10 +237,12 @@ pub fn resolve_workspace_with_opts(
 async fn collect_packages_from_resolve_graph(
     resolve: &Resolve,
     registry: &dyn Registry,
+    cancellation: Option<&CancellationToken>,
 ) -> Result<HashMap<PackageId, Package>> {
     let mut packages = HashMap::with_capacity(resolve.package_ids().size_hint().0);
     // TODO(#6): Parallelize this loop.
     for package_id in resolve.package_ids() {
+        check_cancelled(cancellation)?;
         let package = registry.download(package_id).await?;
         packages.insert(package_id, package);
     }
@@ -819,7 +853,7 @@ pub fn generate_cairo_plugin_compilation_units(
     // Whether loading a prebuilt library is both allowed and requested.
     load_prebuilt: bool,
 ) -> Result<ProcMacroCompilationUnit> {
-    let load_prebuilt = load_prebuilt && member.prebuilt_lib_path().is_some();
+    let load_prebuilt = load_prebuilt && member.prebuilt_lib_path()?.is_some();
     let prebuilt = load_prebuilt
         .then_some(
             ProcMacroInstance::try_load_prebuilt(member.clone())
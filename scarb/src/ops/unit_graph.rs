@@ -0,0 +1,123 @@
+use anyhow::Result;
+use itertools::Itertools;
+use serde::Serialize;
+use smol_str::SmolStr;
+
+use crate::compiler::{CompilationUnit, CompilationUnitAttributes, CompilationUnitComponent};
+use crate::core::{PackageId, Workspace};
+use crate::ops::{select_compilation_units, CompileOpts};
+
+/// Version of the unit graph format, bumped on every breaking change.
+pub const UNIT_GRAPH_VERSION: u64 = 1;
+
+/// Graph of compilation units which would be built by `scarb build`, as printed by
+/// `scarb build --unit-graph`.
+#[derive(Debug, Serialize)]
+pub struct UnitGraph {
+    pub version: u64,
+    pub units: Vec<UnitGraphUnit>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct UnitGraphUnit {
+    pub id: String,
+    /// Either `cairo` or `proc-macro`.
+    pub kind: &'static str,
+    pub package: String,
+    pub targets: Vec<UnitGraphTarget>,
+    pub components: Vec<UnitGraphComponent>,
+    /// IDs of units which must be built before this unit, like procedural macros it loads.
+    pub dependencies: Vec<String>,
+    pub compiler_config: serde_json::Value,
+}
+
+#[derive(Debug, Serialize)]
+pub struct UnitGraphTarget {
+    pub kind: String,
+    pub name: SmolStr,
+}
+
+#[derive(Debug, Serialize)]
+pub struct UnitGraphComponent {
+    pub package: String,
+    pub targets: Vec<UnitGraphTarget>,
+    /// Packages of components this component depends on.
+    pub dependencies: Vec<String>,
+}
+
+/// Collect the graph of compilation units of the given packages, without building them.
+#[tracing::instrument(skip_all, level = "debug")]
+pub fn unit_graph(
+    packages: Vec<PackageId>,
+    opts: &CompileOpts,
+    ws: &Workspace<'_>,
+) -> Result<UnitGraph> {
+    let compilation_units = select_compilation_units(packages, opts, false, ws)?;
+
+    let units = compilation_units
+        .iter()
+        .map(|unit| {
+            let (kind, dependencies, compiler_config) = match unit {
+                CompilationUnit::Cairo(cu) => {
+                    let dependencies = cu
+                        .cairo_plugins
+                        .iter()
+                        .filter(|plugin| !plugin.builtin)
+                        .filter_map(|plugin| {
+                            compilation_units.iter().find(|other| {
+                                matches!(other, CompilationUnit::ProcMacro(_))
+                                    && other.main_package_id() == plugin.package.id
+                            })
+                        })
+                        .map(CompilationUnit::id)
+                        .sorted()
+                        .collect();
+                    let compiler_config = serde_json::to_value(&cu.compiler_config)
+                        .expect("Compiler config should always be JSON serializable.");
+                    ("cairo", dependencies, compiler_config)
+                }
+                CompilationUnit::ProcMacro(cu) => {
+                    ("proc-macro", Vec::new(), cu.compiler_config.clone())
+                }
+            };
+            UnitGraphUnit {
+                id: unit.id(),
+                kind,
+                package: unit.main_package_id().to_serialized_string(),
+                targets: collect_targets(unit.main_component()),
+                components: unit
+                    .components()
+                    .iter()
+                    .map(|component| UnitGraphComponent {
+                        package: component.package.id.to_serialized_string(),
+                        targets: collect_targets(component),
+                        dependencies: component
+                            .dependencies
+                            .iter()
+                            .map(|dep| dep.package_id.to_serialized_string())
+                            .collect(),
+                    })
+                    .collect(),
+                dependencies,
+                compiler_config,
+            }
+        })
+        .sorted_by(|a, b| a.id.cmp(&b.id))
+        .collect();
+
+    Ok(UnitGraph {
+        version: UNIT_GRAPH_VERSION,
+        units,
+    })
+}
+
+fn collect_targets(component: &CompilationUnitComponent) -> Vec<UnitGraphTarget> {
+    component
+        .targets
+        .iter()
+        .map(|target| UnitGraphTarget {
+            kind: target.kind.to_string(),
+            name: target.name.clone(),
+        })
+        .collect()
+}
@@ -1,5 +1,6 @@
 use std::collections::hash_map::Entry;
 use std::collections::HashMap;
+use std::fs;
 
 use anyhow::{anyhow, Context, Result};
 use camino::{Utf8Path, Utf8PathBuf};
@@ -12,7 +13,7 @@ use crate::core::config::Config;
 use crate::core::package::Package;
 use crate::core::source::SourceId;
 use crate::core::workspace::Workspace;
-use crate::core::TomlManifest;
+use crate::core::{find_invalid_profile_inheritance, find_unknown_manifest_keys, TomlManifest};
 use crate::internal::fsx;
 use crate::internal::fsx::{is_hidden, PathBufUtf8Ext};
 use crate::ops::find_workspace_manifest_path;
@@ -69,17 +70,55 @@ fn validate_virtual_manifest(manifest_path: &Utf8Path, manifest: &TomlManifest)
     }
 }
 
+/// Read a manifest file, and warn about keys which Scarb does not recognize.
+///
+/// Returns the parsed manifest, along with the contents of the file.
+/// Manifests of packages which are not local are not linted, as users cannot fix them.
+/// Invalid profile inheritance in local manifests is reported as an error pointing at the
+/// offending `inherits` key.
+fn read_toml_manifest(
+    manifest_path: &Utf8Path,
+    source_id: SourceId,
+    config: &Config,
+) -> Result<(TomlManifest, String)> {
+    let contents = fs::read_to_string(manifest_path)
+        .with_context(|| format!("failed to read manifest at: {manifest_path}"))?;
+    let toml_manifest = TomlManifest::read_from_str(&contents)
+        .with_context(|| format!("failed to parse manifest at: {manifest_path}"))?;
+    if source_id.is_path() {
+        for key in find_unknown_manifest_keys(&contents) {
+            config.ui().warn(key.to_warning(manifest_path, &contents));
+        }
+        let invalid_inheritance = find_invalid_profile_inheritance(&contents);
+        if !invalid_inheritance.is_empty() {
+            let errors = invalid_inheritance
+                .iter()
+                .map(|invalid| invalid.to_error(manifest_path, &contents))
+                .collect::<Vec<_>>();
+            return Err(anyhow!(errors.join("\n")))
+                .with_context(|| format!("failed to parse manifest at: {manifest_path}"));
+        }
+    }
+    Ok((toml_manifest, contents))
+}
+
 fn read_workspace_root<'c>(
     manifest_path: &Utf8Path,
     source_id: SourceId,
     config: &'c Config,
 ) -> Result<Workspace<'c>> {
-    let toml_manifest = TomlManifest::read_from_path(manifest_path)?;
+    let (toml_manifest, contents) = read_toml_manifest(manifest_path, source_id, config)?;
     let toml_workspace = toml_manifest.get_workspace();
     let profiles = toml_manifest.collect_profiles()?;
+    let profile_definition = toml_manifest
+        .collect_profile_definition(config.profile())
+        .with_context(|| format!("failed to parse manifest at: {manifest_path}"))?;
+    let patch = toml_manifest
+        .collect_patch(manifest_path)
+        .with_context(|| format!("failed to parse manifest at: {manifest_path}"))?;
 
     let root_package = if toml_manifest.is_package() {
-        let manifest = toml_manifest
+        let mut manifest = toml_manifest
             .to_manifest(
                 manifest_path,
                 manifest_path,
@@ -89,6 +128,7 @@ fn read_workspace_root<'c>(
                 config,
             )
             .with_context(|| format!("failed to parse manifest at: {manifest_path}"))?;
+        manifest.contents = Some(contents);
         let manifest = Box::new(manifest);
         let package = Package::new(manifest.summary.package_id, manifest_path.into(), manifest);
         Some(package)
@@ -111,9 +151,16 @@ fn read_workspace_root<'c>(
             .iter()
             .map(AsRef::as_ref)
             .map(|package_path| {
-                let package_manifest = TomlManifest::read_from_path(package_path)?;
+                let (package_manifest, contents) =
+                    read_toml_manifest(package_path, source_id, config)?;
+                if package_manifest.patch.is_some() {
+                    config.ui().warn(format!(
+                        "[patch] in non-root package manifest will be ignored: {package_path}\n\
+                        help: move [patch] to the workspace root manifest at: {manifest_path}"
+                    ));
+                }
                 // Read the member package.
-                let manifest = package_manifest
+                let mut manifest = package_manifest
                     .to_manifest(
                         package_path,
                         manifest_path,
@@ -123,6 +170,7 @@ fn read_workspace_root<'c>(
                         config,
                     )
                     .with_context(|| format!("failed to parse manifest at: {manifest_path}"))?;
+                manifest.contents = Some(contents);
                 let manifest = Box::new(manifest);
                 let package =
                     Package::new(manifest.summary.package_id, package_path.into(), manifest);
@@ -140,12 +188,14 @@ fn read_workspace_root<'c>(
             root_package,
             config,
             profiles,
+            profile_definition,
             scripts,
+            patch,
         )
     } else {
         // Read single package workspace
         let package = root_package.ok_or_else(|| anyhow!("the [package] section is missing"))?;
-        Workspace::from_single_package(package, config, profiles)
+        Workspace::from_single_package(package, config, profiles, profile_definition, patch)
     }
 }
 
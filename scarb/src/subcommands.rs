@@ -1,6 +1,7 @@
 use crate::core::Config;
 use crate::SCARB_ENV;
 use camino::Utf8PathBuf;
+use scarb_ui::args::{ColorSpec, ToEnvVars};
 use std::collections::HashMap;
 use std::ffi::OsString;
 
@@ -32,10 +33,27 @@ pub fn get_env_vars(
             "SCARB_UI_VERBOSITY".into(),
             config.ui().verbosity().to_string().into(),
         ),
+        (
+            "SCARB_UI_OUTPUT_FORMAT".into(),
+            config.ui().output_format().to_string().into(),
+        ),
         (SCARB_ENV.into(), config.app_exe()?.into()),
     ];
+    let color = ColorSpec {
+        color: config.ui_color(),
+    };
+    vars.extend(
+        color
+            .to_env_vars()
+            .into_iter()
+            .map(|(key, value)| (key.into(), value.into())),
+    );
     if let Some(target_dir) = target_dir {
         vars.push(("SCARB_TARGET_DIR".into(), target_dir.into()));
     }
-    Ok(HashMap::from_iter(vars))
+    let config_env = config
+        .env()
+        .iter()
+        .map(|(key, value)| (key.clone(), value.clone()));
+    Ok(HashMap::from_iter(config_env.chain(vars)))
 }
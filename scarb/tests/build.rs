@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 use std::fs;
+use std::sync::{Arc, Mutex};
 
 use assert_fs::prelude::*;
 use assert_fs::TempDir;
@@ -12,7 +13,7 @@ use scarb_build_metadata::CAIRO_VERSION;
 use scarb_metadata::Metadata;
 use scarb_test_support::command::{CommandExt, Scarb};
 use scarb_test_support::contracts::BALANCE_CONTRACT;
-use scarb_test_support::fsx::ChildPathEx;
+use scarb_test_support::fsx::{AssertFsUtf8Ext, ChildPathEx};
 use scarb_test_support::project_builder::{Dep, DepBuilder, ProjectBuilder};
 use scarb_test_support::workspace_builder::WorkspaceBuilder;
 use serde::Deserialize;
@@ -346,6 +347,47 @@ fn compile_with_compatible_cairo_version() {
         .success();
 }
 
+#[test]
+fn compile_with_newer_required_scarb_version() {
+    let t = TempDir::new().unwrap();
+    ProjectBuilder::start()
+        .name("hello")
+        .version("0.1.0")
+        .manifest_package_extra(r#"scarb-version = ">=33.33.0""#)
+        .build(&t);
+
+    Scarb::quick_snapbox()
+        .args(["build"])
+        .current_dir(&t)
+        .assert()
+        .success()
+        .stdout_matches(indoc! {r#"
+            warn: package `hello` requires Scarb version `>=33.33.0`, but the current version is `[..]`
+            [..] Compiling hello v0.1.0 ([..]Scarb.toml)
+            [..] Finished `dev` profile target(s) in [..]
+        "#});
+}
+
+#[test]
+fn compile_with_compatible_scarb_version() {
+    let t = TempDir::new().unwrap();
+    ProjectBuilder::start()
+        .name("hello")
+        .version("0.1.0")
+        .manifest_package_extra(r#"scarb-version = ">=0.1.0""#)
+        .build(&t);
+
+    Scarb::quick_snapbox()
+        .args(["build"])
+        .current_dir(&t)
+        .assert()
+        .success()
+        .stdout_matches(indoc! {r#"
+            [..] Compiling hello v0.1.0 ([..]Scarb.toml)
+            [..] Finished `dev` profile target(s) in [..]
+        "#});
+}
+
 #[test]
 fn compile_with_invalid_non_numeric_dep_version() {
     let t = TempDir::new().unwrap();
@@ -751,6 +793,251 @@ fn edition_must_exist() {
         "#});
 }
 
+#[test]
+fn target_edition_overrides_package_edition() {
+    let t = TempDir::new().unwrap();
+    ProjectBuilder::start()
+        .lib_cairo(indoc! {r#"
+            fn example() -> Nullable<felt252> { null() }
+        "#})
+        .edition("2023_10")
+        .manifest_extra(indoc! {r#"
+            [lib]
+            edition = "2023_01"
+        "#})
+        .build(&t);
+
+    Scarb::quick_snapbox()
+        .arg("build")
+        .current_dir(&t)
+        .assert()
+        .success();
+}
+
+#[test]
+fn target_edition_must_exist() {
+    let t = TempDir::new().unwrap();
+    ProjectBuilder::start()
+        .manifest_extra(indoc! {r#"
+            [lib]
+            edition = "2021"
+        "#})
+        .build(&t);
+
+    Scarb::quick_snapbox()
+        .arg("fetch")
+        .current_dir(&t)
+        .assert()
+        .failure()
+        .stdout_matches(indoc! {r#"
+             error: failed to parse manifest at: [..]/Scarb.toml
+
+             Caused by:
+                 TOML parse error at line [..], column 11
+                   |
+                 [..] | edition = "2021"
+                   |           ^^^^^^
+                 unknown variant `2021`, expected one of `2023_01`, `2023_10`, `2023_11`, `2024_07`
+        "#});
+}
+
+#[test]
+fn unchanged_package_is_not_recompiled() {
+    let t = TempDir::new().unwrap();
+    ProjectBuilder::start()
+        .name("hello")
+        .version("1.0.0")
+        .lib_cairo("fn f() -> felt252 { 42 }")
+        .build(&t);
+
+    Scarb::quick_snapbox()
+        .arg("build")
+        .current_dir(&t)
+        .assert()
+        .success()
+        .stdout_matches(indoc! {r#"
+               Compiling hello v1.0.0 ([..]Scarb.toml)
+                Finished `dev` profile target(s) in [..]
+        "#});
+
+    Scarb::quick_snapbox()
+        .arg("build")
+        .current_dir(&t)
+        .assert()
+        .success()
+        .stdout_matches(indoc! {r#"
+                Finished `dev` profile target(s) in [..]
+        "#});
+
+    t.child("target/dev/hello.sierra.json")
+        .assert(predicates::path::exists());
+}
+
+#[test]
+fn removed_artifact_triggers_recompilation() {
+    let t = TempDir::new().unwrap();
+    ProjectBuilder::start()
+        .name("hello")
+        .version("1.0.0")
+        .lib_cairo("fn f() -> felt252 { 42 }")
+        .build(&t);
+
+    Scarb::quick_snapbox()
+        .arg("build")
+        .current_dir(&t)
+        .assert()
+        .success();
+
+    fs::remove_file(t.child("target/dev/hello.sierra.json")).unwrap();
+
+    Scarb::quick_snapbox()
+        .arg("build")
+        .current_dir(&t)
+        .assert()
+        .success()
+        .stdout_matches(indoc! {r#"
+               Compiling hello v1.0.0 ([..]Scarb.toml)
+                Finished `dev` profile target(s) in [..]
+        "#});
+
+    t.child("target/dev/hello.sierra.json")
+        .assert(predicates::path::exists());
+}
+
+#[test]
+fn warnings_are_reported_when_compilation_is_skipped() {
+    let t = TempDir::new().unwrap();
+    ProjectBuilder::start()
+        .name("hello")
+        .version("1.0.0")
+        .lib_cairo(indoc! {r#"
+            fn f() -> felt252 {
+                let a = 41;
+                42
+            }
+        "#})
+        .build(&t);
+
+    for _ in 0..2 {
+        Scarb::quick_snapbox()
+            .arg("build")
+            .current_dir(&t)
+            .assert()
+            .success()
+            .stdout_matches(indoc! {r#"
+                ...
+                warn[E0001]: Unused variable. Consider ignoring by prefixing with `_`.
+                 --> [..]lib.cairo:2:9
+                    let a = 41;
+                        ^
+
+                    Finished `dev` profile target(s) in [..]
+            "#});
+    }
+}
+
+#[test]
+fn changed_source_file_triggers_recompilation() {
+    let t = TempDir::new().unwrap();
+    ProjectBuilder::start()
+        .name("hello")
+        .version("1.0.0")
+        .lib_cairo("mod a;")
+        .src("src/a.cairo", "fn f() -> felt252 { 42 }")
+        .build(&t);
+
+    Scarb::quick_snapbox()
+        .arg("build")
+        .current_dir(&t)
+        .assert()
+        .success();
+
+    t.child("src/a.cairo")
+        .write_str("fn f() -> felt252 { 43 }")
+        .unwrap();
+
+    Scarb::quick_snapbox()
+        .arg("build")
+        .current_dir(&t)
+        .assert()
+        .success()
+        .stdout_matches(indoc! {r#"
+               Compiling hello v1.0.0 ([..]Scarb.toml)
+                Finished `dev` profile target(s) in [..]
+        "#});
+}
+
+#[test]
+fn changed_compiler_config_triggers_recompilation() {
+    let t = TempDir::new().unwrap();
+    ProjectBuilder::start()
+        .name("hello")
+        .version("1.0.0")
+        .lib_cairo("fn f() -> felt252 { 42 }")
+        .build(&t);
+
+    Scarb::quick_snapbox()
+        .arg("build")
+        .current_dir(&t)
+        .assert()
+        .success();
+
+    ProjectBuilder::start()
+        .name("hello")
+        .version("1.0.0")
+        .lib_cairo("fn f() -> felt252 { 42 }")
+        .manifest_extra(indoc! {r#"
+            [cairo]
+            sierra-replace-ids = false
+        "#})
+        .build(&t);
+
+    Scarb::quick_snapbox()
+        .arg("build")
+        .current_dir(&t)
+        .assert()
+        .success()
+        .stdout_matches(indoc! {r#"
+               Compiling hello v1.0.0 ([..]Scarb.toml)
+                Finished `dev` profile target(s) in [..]
+        "#});
+}
+
+#[test]
+fn misspelled_manifest_keys_warn() {
+    let t = TempDir::new().unwrap();
+    t.child("Scarb.toml")
+        .write_str(indoc! {r#"
+            [package]
+            name = "hello"
+            version = "0.1.0"
+            editon = "2024_07"
+
+            [dependancies]
+
+            [tool.something]
+            editon = "2024_07"
+        "#})
+        .unwrap();
+    t.child("src/lib.cairo").write_str("fn f() {}").unwrap();
+
+    Scarb::quick_snapbox()
+        .arg("build")
+        .current_dir(&t)
+        .assert()
+        .success()
+        .stdout_matches(indoc! {r#"
+            warn: unused manifest key: package.editon
+             --> [..]Scarb.toml:4:1
+            help: did you mean `edition`?
+            warn: unused manifest key: dependancies
+             --> [..]Scarb.toml:6:2
+            help: did you mean `dependencies`?
+               Compiling hello v0.1.0 ([..]Scarb.toml)
+                Finished `dev` profile target(s) in [..]
+        "#});
+}
+
 #[test]
 fn dev_dep_used_outside_tests() {
     let t = TempDir::new().unwrap();
@@ -1027,6 +1314,88 @@ fn does_not_show_warnings_from_deps() {
         .success();
 }
 
+#[test]
+fn future_incompat_report() {
+    let t = TempDir::new().unwrap();
+    let first = t.child("first");
+    let second = t.child("second");
+    ProjectBuilder::start()
+        .name("first")
+        .lib_cairo(indoc! {r#"
+            #[deprecated(feature: "deprecated-hello", note: "Use `hi` instead.", since: "1.0.0")]
+            pub fn hello() -> felt252 { 42 }
+        "#})
+        .build(&first);
+    ProjectBuilder::start()
+        .name("second")
+        .lib_cairo(indoc! {r#"
+            fn main() -> felt252 { first::hello() }
+        "#})
+        .dep("first", &first)
+        .build(&second);
+
+    // The report is only saved when requested.
+    Scarb::quick_snapbox()
+        .arg("build")
+        .current_dir(&second)
+        .assert()
+        .success()
+        .stdout_matches(indoc! {r#"
+            [..]Compiling second v1.0.0 ([..]Scarb.toml)
+            warn: Usage of deprecated feature `"deprecated-hello"` [..]
+            ...
+                Finished `dev` profile target(s) in [..]
+        "#});
+    t.child("second/target/future-incompat-report.json")
+        .assert(predicates::path::missing());
+
+    Scarb::quick_snapbox()
+        .args(["build", "--future-incompat-report"])
+        .current_dir(&second)
+        .assert()
+        .success()
+        .stdout_matches(indoc! {r#"
+            ...
+                Finished `dev` profile target(s) in [..]
+            warn: the following packages contain code that will be rejected by a future version of Cairo: second v1.0.0 ([..])
+            The package `second v1.0.0 ([..])` currently triggers the following future incompatibility notices:
+            > Usage of deprecated feature `"deprecated-hello"` [..]
+            ...
+        "#});
+
+    // The report is persisted and still complete when the unit is not recompiled.
+    t.child("second/target/future-incompat-report.json")
+        .assert(predicates::str::contains("deprecated-hello"));
+    Scarb::quick_snapbox()
+        .args(["build", "--future-incompat-report"])
+        .current_dir(&second)
+        .assert()
+        .success()
+        .stdout_matches(indoc! {r#"
+            ...
+                Finished `dev` profile target(s) in [..]
+            warn: the following packages contain code that will be rejected by a future version of Cairo: second v1.0.0 ([..])
+            ...
+        "#});
+}
+
+#[test]
+fn future_incompat_report_without_notices() {
+    let t = TempDir::new().unwrap();
+    ProjectBuilder::start().name("hello").build(&t);
+
+    Scarb::quick_snapbox()
+        .args(["build", "--future-incompat-report"])
+        .current_dir(&t)
+        .assert()
+        .success()
+        .stdout_matches(indoc! {r#"
+            [..]Compiling hello v1.0.0 ([..]Scarb.toml)
+            [..]Finished `dev` profile target(s) in [..]
+            note: no packages contain code that will be rejected by a future version of Cairo
+        "#});
+}
+
 #[test]
 fn error_codes_shown_in_json_output() {
     let t = TempDir::new().unwrap();
@@ -1642,3 +2011,334 @@ fn can_build_with_add_redeposit_gas() {
         .assert()
         .success();
 }
+
+#[test]
+fn copies_artifacts_to_out_dir() {
+    let t = TempDir::new().unwrap();
+    ProjectBuilder::start()
+        .name("hello")
+        .dep_starknet()
+        .lib_cairo(BALANCE_CONTRACT)
+        .manifest_extra(indoc! {r#"
+            [lib]
+            casm = true
+
+            [[target.starknet-contract]]
+        "#})
+        .build(&t);
+
+    Scarb::quick_snapbox()
+        .arg("build")
+        .arg("--out-dir")
+        .arg("out")
+        .current_dir(&t)
+        .assert()
+        .success();
+
+    assert_eq!(
+        t.child("out").files(),
+        vec![
+            "hello.casm",
+            "hello.sierra.json",
+            "hello.starknet_artifacts.json",
+            "hello_Balance.contract_class.json",
+        ]
+    );
+    assert_eq!(
+        t.child("target/dev").files(),
+        vec![
+            "hello.casm",
+            "hello.sierra.json",
+            "hello.starknet_artifacts.json",
+            "hello_Balance.contract_class.json",
+        ]
+    );
+}
+
+#[test]
+fn out_dir_only_includes_selected_targets() {
+    let t = TempDir::new().unwrap();
+    ProjectBuilder::start()
+        .name("hello")
+        .dep_starknet()
+        .lib_cairo(BALANCE_CONTRACT)
+        .manifest_extra("[[target.starknet-contract]]")
+        .build(&t);
+
+    Scarb::quick_snapbox()
+        .arg("build")
+        .arg("--target-kinds")
+        .arg("starknet-contract")
+        .arg("--out-dir")
+        .arg("out")
+        .current_dir(&t)
+        .assert()
+        .success();
+
+    assert_eq!(
+        t.child("out").files(),
+        vec![
+            "hello.starknet_artifacts.json",
+            "hello_Balance.contract_class.json",
+        ]
+    );
+}
+
+#[test]
+fn emits_artifact_metadata() {
+    let t = TempDir::new().unwrap();
+    ProjectBuilder::start()
+        .name("hello")
+        .version("0.1.0")
+        .build(&t);
+
+    Scarb::quick_snapbox()
+        .arg("build")
+        .arg("--emit-metadata")
+        .arg("--out-dir")
+        .arg("out")
+        .current_dir(&t)
+        .assert()
+        .success();
+
+    let metadata = t
+        .child("target/dev/hello.sierra.json.meta.json")
+        .assert_is_json::<serde_json::Value>();
+    assert_eq!(metadata["artifact"], "hello.sierra.json");
+    assert!(metadata["package"]
+        .as_str()
+        .unwrap()
+        .starts_with("hello 0.1.0 "));
+    assert_eq!(metadata["name"], "hello");
+    assert_eq!(metadata["version"], "0.1.0");
+    assert_eq!(metadata["target_kind"], "lib");
+    assert_eq!(metadata["target_name"], "hello");
+    assert_eq!(metadata["profile"], "dev");
+    assert!(metadata["scarb_version"].is_string());
+    assert_eq!(metadata["cairo_version"], CAIRO_VERSION);
+    assert!(metadata["fingerprint"].is_string());
+
+    assert_eq!(
+        t.child("out").files(),
+        vec!["hello.sierra.json", "hello.sierra.json.meta.json"]
+    );
+}
+
+#[test]
+fn check_rejects_out_dir() {
+    let t = TempDir::new().unwrap();
+    ProjectBuilder::start().name("hello").build(&t);
+
+    Scarb::quick_snapbox()
+        .arg("check")
+        .arg("--out-dir")
+        .arg("out")
+        .current_dir(&t)
+        .assert()
+        .failure()
+        .stdout_matches(indoc! {r#"
+            error: `--out-dir` is not supported by `scarb check`, as it does not produce any artifacts
+        "#});
+}
+
+#[test]
+fn artifacts_are_reproducible() {
+    let t = TempDir::new().unwrap();
+    ProjectBuilder::start()
+        .name("hello")
+        .dep_starknet()
+        .lib_cairo(BALANCE_CONTRACT)
+        .manifest_extra(indoc! {r#"
+            [lib]
+            casm = true
+            sierra-text = true
+
+            [[target.starknet-contract]]
+            casm = true
+        "#})
+        .build(&t);
+
+    let build = || {
+        Scarb::quick_snapbox()
+            .arg("build")
+            .current_dir(&t)
+            .assert()
+            .success();
+        let target_dir = t.child("target/dev");
+        let artifacts = target_dir
+            .files()
+            .into_iter()
+            .map(|file| {
+                let content = fs::read(target_dir.child(&file)).unwrap();
+                (file, content)
+            })
+            .collect::<HashMap<_, _>>();
+        fs::remove_dir_all(t.child("target")).unwrap();
+        artifacts
+    };
+
+    let first = build();
+    let second = build();
+    assert!(!first.is_empty());
+    assert_eq!(first, second);
+}
+
+#[test]
+fn color_flag() {
+    let t = TempDir::new().unwrap();
+    ProjectBuilder::start().name("hello").build(&t);
+
+    let build = |color: &str| {
+        let cmd = Scarb::quick_snapbox()
+            .args(["--color", color, "build"])
+            .env_remove("CLICOLOR")
+            .env_remove("CLICOLOR_FORCE")
+            .current_dir(&t)
+            .assert()
+            .success();
+        String::from_utf8(cmd.get_output().stdout.clone()).unwrap()
+    };
+
+    assert!(build("always").contains("\u{1b}["));
+    assert!(!build("never").contains("\u{1b}["));
+    // Output of tests is not a terminal.
+    assert!(!build("auto").contains("\u{1b}["));
+}
+
+#[test]
+fn color_flag_is_passed_to_scripts() {
+    let t = TempDir::new().unwrap();
+    ProjectBuilder::start()
+        .name("hello")
+        .manifest_extra(indoc! {r#"
+            [scripts]
+            color = "echo $SCARB_COLOR"
+        "#})
+        .build(&t);
+
+    Scarb::quick_snapbox()
+        .args(["--color", "never", "run", "color"])
+        .env_remove("SCARB_COLOR")
+        .current_dir(&t)
+        .assert()
+        .success()
+        .stdout_eq("never\n");
+}
+
+#[test]
+fn cancelled_build_stops_before_compiling_units() {
+    let t = TempDir::new().unwrap();
+    ProjectBuilder::start().name("hello").build(&t);
+
+    let config = Scarb::test_config(t.child("Scarb.toml"));
+    let ws = scarb::ops::read_workspace(config.manifest_path(), &config).unwrap();
+    let token = scarb::core::CancellationToken::new();
+    token.cancel();
+    let opts = scarb::ops::CompileOpts {
+        include_target_kinds: Vec::new(),
+        exclude_target_kinds: Vec::new(),
+        include_target_names: Vec::new(),
+        features: scarb::ops::FeaturesOpts {
+            features: scarb::ops::FeaturesSelector::Features(Vec::new()),
+            no_default_features: false,
+        },
+        ignore_cairo_version: false,
+        out_dir: None,
+        future_incompat_report: false,
+        emit_metadata: false,
+        cancellation: Some(token),
+        jobs: None,
+    };
+    let packages = ws.members().map(|p| p.id).collect();
+    let err = scarb::ops::compile(packages, opts, &ws).unwrap_err();
+
+    assert!(err.is::<scarb::core::errors::Cancelled>());
+    assert_eq!(err.to_string(), "operation has been cancelled");
+    t.child("target/dev/hello.sierra.json")
+        .assert(predicates::path::missing());
+}
+
+/// Compiler of `lib` targets, which cancels the build once it has compiled a unit.
+struct CancellingCompiler {
+    token: scarb::core::CancellationToken,
+    compiled: Arc<Mutex<Vec<String>>>,
+}
+
+impl scarb::compiler::Compiler for CancellingCompiler {
+    fn target_kind(&self) -> scarb::core::TargetKind {
+        scarb::core::TargetKind::LIB
+    }
+
+    fn default_params(&self) -> toml::Value {
+        toml::Value::Table(Default::default())
+    }
+
+    fn compile(
+        &self,
+        unit: scarb::compiler::CairoCompilationUnit,
+        _db: &mut cairo_lang_compiler::db::RootDatabase,
+        _ws: &scarb::core::Workspace<'_>,
+    ) -> anyhow::Result<()> {
+        self.compiled
+            .lock()
+            .unwrap()
+            .push(unit.main_package_id.name.to_string());
+        self.token.cancel();
+        Ok(())
+    }
+}
+
+#[test]
+fn cancelled_build_stops_between_units() {
+    let t = TempDir::new().unwrap();
+    let first = t.child("first");
+    let second = t.child("second");
+    ProjectBuilder::start().name("first").build(&first);
+    ProjectBuilder::start().name("second").build(&second);
+    WorkspaceBuilder::start()
+        .add_member("first")
+        .add_member("second")
+        .build(&t);
+
+    let token = scarb::core::CancellationToken::new();
+    let compiled = Arc::new(Mutex::new(Vec::new()));
+    let mut compilers = scarb::compiler::CompilerRepository::empty();
+    compilers
+        .add(Box::new(CancellingCompiler {
+            token: token.clone(),
+            compiled: compiled.clone(),
+        }))
+        .unwrap();
+
+    let cache_dir = TempDir::new().unwrap();
+    let config_dir = TempDir::new().unwrap();
+    let config = scarb::core::Config::builder(t.child("Scarb.toml").utf8_path())
+        .global_cache_dir_override(Some(cache_dir.utf8_path()))
+        .global_config_dir_override(Some(config_dir.utf8_path()))
+        .path_env_override(Some(std::iter::empty::<std::path::PathBuf>()))
+        .compilers(compilers)
+        .build()
+        .unwrap();
+    let ws = scarb::ops::read_workspace(config.manifest_path(), &config).unwrap();
+    let opts = scarb::ops::CompileOpts {
+        include_target_kinds: Vec::new(),
+        exclude_target_kinds: Vec::new(),
+        include_target_names: Vec::new(),
+        features: scarb::ops::FeaturesOpts {
+            features: scarb::ops::FeaturesSelector::Features(Vec::new()),
+            no_default_features: false,
+        },
+        ignore_cairo_version: false,
+        out_dir: None,
+        future_incompat_report: false,
+        emit_metadata: false,
+        cancellation: Some(token),
+        jobs: None,
+    };
+    let packages = ws.members().map(|p| p.id).collect();
+    let err = scarb::ops::compile(packages, opts, &ws).unwrap_err();
+
+    assert!(err.is::<scarb::core::errors::Cancelled>());
+    // Only the first unit has been compiled, the second one was skipped after cancellation.
+    assert_eq!(compiled.lock().unwrap().len(), 1);
+}
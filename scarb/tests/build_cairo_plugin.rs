@@ -3,7 +3,7 @@ use assert_fs::TempDir;
 use cairo_lang_sierra::program::VersionedProgram;
 use indoc::indoc;
 use scarb_test_support::cairo_plugin_project_builder::CairoPluginProjectBuilder;
-use scarb_test_support::command::Scarb;
+use scarb_test_support::command::{CommandExt, Scarb};
 use scarb_test_support::fsx::ChildPathEx;
 use scarb_test_support::project_builder::ProjectBuilder;
 use scarb_test_support::workspace_builder::WorkspaceBuilder;
@@ -655,8 +655,8 @@ fn can_define_multiple_macros() {
         .stdout_matches(indoc! {r#"
             warn: `scarb cairo-run` will be deprecated soon
             help: use `scarb execute` instead
-            [..]Compiling other v1.0.0 ([..]Scarb.toml)
-            [..]Compiling some v1.0.0 ([..]Scarb.toml)
+            [..]Compiling [..] v1.0.0 ([..]Scarb.toml)
+            [..]Compiling [..] v1.0.0 ([..]Scarb.toml)
             [..]Compiling hello v1.0.0 ([..]Scarb.toml)
             [..]Finished `dev` profile target(s) in [..]
             [..]Running hello
@@ -759,8 +759,8 @@ fn cannot_duplicate_macros_across_packages() {
         .assert()
         .failure()
         .stdout_matches(indoc! {r#"
-            [..]Compiling other v1.0.0 ([..]Scarb.toml)
-            [..]Compiling some v1.0.0 ([..]Scarb.toml)
+            [..]Compiling [..] v1.0.0 ([..]Scarb.toml)
+            [..]Compiling [..] v1.0.0 ([..]Scarb.toml)
             [..]Compiling hello v1.0.0 ([..]Scarb.toml)
             error: duplicate expansions defined for procedural macros: hello (some v1.0.0 ([..]Scarb.toml) and other v1.0.0 ([..]Scarb.toml))
         "#});
@@ -1262,6 +1262,56 @@ fn executable_name_cannot_clash_attr() {
         "#});
 }
 
+#[test]
+fn cannot_duplicate_executable_attributes_across_packages() {
+    let temp = TempDir::new().unwrap();
+    let t = temp.child("some");
+    CairoPluginProjectBuilder::default()
+        .lib_rs(indoc! {r##"
+        use cairo_lang_macro::executable_attribute;
+
+        executable_attribute!("some");
+        "##})
+        .build(&t);
+
+    let w = temp.child("other");
+    CairoPluginProjectBuilder::default()
+        .name("other")
+        .lib_rs(indoc! {r##"
+        use cairo_lang_macro::executable_attribute;
+
+        executable_attribute!("some");
+        "##})
+        .build(&w);
+
+    let project = temp.child("hello");
+    ProjectBuilder::start()
+        .name("hello")
+        .version("1.0.0")
+        .dep_starknet()
+        .dep("some", &t)
+        .dep("other", &w)
+        .lib_cairo(indoc! {r#"
+            #[some]
+            fn main() -> felt252 { 12 }
+        "#})
+        .build(&project);
+
+    Scarb::quick_snapbox()
+        .arg("build")
+        // Disable output from Cargo.
+        .env("CARGO_TERM_QUIET", "true")
+        .current_dir(&project)
+        .assert()
+        .failure()
+        .stdout_matches(indoc! {r#"
+            [..]Compiling [..] v1.0.0 ([..]Scarb.toml)
+            [..]Compiling [..] v1.0.0 ([..]Scarb.toml)
+            [..]Compiling hello v1.0.0 ([..]Scarb.toml)
+            error: duplicate executable attributes defined for procedural macros: some ([..]v1.0.0 ([..]Scarb.toml) and [..]v1.0.0 ([..]Scarb.toml))
+        "#});
+}
+
 #[test]
 fn can_be_expanded() {
     let temp = TempDir::new().unwrap();
@@ -1544,3 +1594,132 @@ fn can_expand_impl_inner_func_attrr() {
 
         "#});
 }
+
+#[test]
+fn unit_graph() {
+    let t = TempDir::new().unwrap();
+    let some = t.child("some");
+    CairoPluginProjectBuilder::default().build(&some);
+    ProjectBuilder::start()
+        .name("hello")
+        .version("1.0.0")
+        .dep("some", &some)
+        .build(&t.child("hello"));
+    WorkspaceBuilder::start()
+        .add_member("some")
+        .add_member("hello")
+        .build(&t);
+
+    let graph = Scarb::quick_snapbox()
+        .args(["--json", "build", "--unit-graph"])
+        .current_dir(&t)
+        .stdout_json::<serde_json::Value>();
+
+    assert_eq!(graph["version"], 1);
+    let units = graph["units"].as_array().unwrap();
+    let unit = |kind: &str, package: &str| {
+        units
+            .iter()
+            .find(|unit| {
+                unit["kind"] == kind
+                    && unit["package"]
+                        .as_str()
+                        .unwrap()
+                        .starts_with(&format!("{package} "))
+            })
+            .unwrap_or_else(|| panic!("missing {kind} unit of {package}"))
+    };
+    let hello = unit("cairo", "hello");
+    let some = unit("proc-macro", "some");
+    assert_eq!(units.len(), 2);
+
+    // Cairo unit depends on the unit of the procedural macro it loads.
+    assert_eq!(hello["dependencies"], serde_json::json!([some["id"]]));
+    assert_eq!(some["dependencies"], serde_json::json!([]));
+    assert_eq!(
+        hello["targets"],
+        serde_json::json!([{"kind": "lib", "name": "hello"}])
+    );
+    assert!(hello["compiler_config"].is_object());
+
+    let components = hello["components"].as_array().unwrap();
+    let main_component = components
+        .iter()
+        .find(|c| c["package"] == hello["package"])
+        .unwrap();
+    assert!(main_component["dependencies"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|dep| dep.as_str().unwrap().starts_with("core ")));
+
+    // Nothing is built.
+    assert!(!t.child("target/dev/hello.sierra.json").exists());
+}
+
+#[test]
+fn builds_independent_plugins_in_parallel() {
+    let temp = TempDir::new().unwrap();
+    let some = temp.child("some");
+    CairoPluginProjectBuilder::default().build(&some);
+    let other = temp.child("other");
+    CairoPluginProjectBuilder::default()
+        .name("other")
+        .build(&other);
+    let project = temp.child("hello");
+    ProjectBuilder::start()
+        .name("hello")
+        .version("1.0.0")
+        .dep("some", &some)
+        .dep("other", &other)
+        .build(&project);
+
+    Scarb::quick_snapbox()
+        .args(["build", "--jobs", "2"])
+        // Disable output from Cargo.
+        .env("CARGO_TERM_QUIET", "true")
+        .current_dir(&project)
+        .assert()
+        .success()
+        .stdout_matches(indoc! {r#"
+            [..]Compiling [..] v1.0.0 ([..]Scarb.toml)
+            [..]Compiling [..] v1.0.0 ([..]Scarb.toml)
+            [..]Compiling hello v1.0.0 ([..]Scarb.toml)
+            [..]Finished `dev` profile target(s) in [..]
+        "#});
+}
+
+#[test]
+fn parallel_plugin_build_failure_is_attributed_to_plugin() {
+    let temp = TempDir::new().unwrap();
+    let some = temp.child("some");
+    CairoPluginProjectBuilder::default().build(&some);
+    let broken = temp.child("broken");
+    CairoPluginProjectBuilder::default()
+        .name("broken")
+        .lib_rs("fn this_does_not_compile() -> u32 { \"not a number\" }")
+        .build(&broken);
+    let project = temp.child("hello");
+    ProjectBuilder::start()
+        .name("hello")
+        .version("1.0.0")
+        .dep("some", &some)
+        .dep("broken", &broken)
+        .build(&project);
+
+    Scarb::quick_snapbox()
+        .args(["build", "--jobs", "2"])
+        // Disable output from Cargo.
+        .env("CARGO_TERM_QUIET", "true")
+        .current_dir(&project)
+        .assert()
+        .failure()
+        .stdout_matches(indoc! {r#"
+            [..]Compiling [..] v1.0.0 ([..]Scarb.toml)
+            [..]Compiling [..] v1.0.0 ([..]Scarb.toml)
+            ...
+            [broken] error[E0308]: mismatched types
+            ...
+            error: could not compile `broken` due to previous error
+        "#});
+}
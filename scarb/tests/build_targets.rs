@@ -757,6 +757,66 @@ fn can_choose_target_by_name() {
     );
 }
 
+#[test]
+fn can_choose_targets_with_target_flag() {
+    let t = TempDir::new().unwrap();
+    ProjectBuilder::start()
+        .name("hello")
+        .dep_starknet()
+        .manifest_extra(indoc! {r#"
+            [lib]
+            [[target.starknet-contract]]
+            name = "contracts"
+        "#})
+        .lib_cairo(r#"fn f() -> felt252 { 42 }"#)
+        .build(&t);
+
+    Scarb::quick_snapbox()
+        .arg("build")
+        .arg("--target")
+        .arg("contracts")
+        .current_dir(&t)
+        .assert()
+        .success();
+    assert_eq!(
+        t.child("target/dev").files(),
+        vec!["contracts.starknet_artifacts.json"]
+    );
+
+    Scarb::quick_snapbox()
+        .arg("build")
+        .arg("--target")
+        .arg("contracts")
+        .arg("--target")
+        .arg("hello")
+        .current_dir(&t)
+        .assert()
+        .success();
+    assert_eq!(
+        t.child("target/dev").files(),
+        vec!["contracts.starknet_artifacts.json", "hello.sierra.json"]
+    );
+}
+
+#[test]
+fn target_name_must_exist() {
+    let t = TempDir::new().unwrap();
+    ProjectBuilder::start()
+        .name("hello")
+        .lib_cairo(r#"fn f() -> felt252 { 42 }"#)
+        .build(&t);
+
+    Scarb::quick_snapbox()
+        .arg("build")
+        .arg("--target=hello,world,foo")
+        .current_dir(&t)
+        .assert()
+        .failure()
+        .stdout_matches(indoc! {r#"
+            error: no targets named `world`, `foo` found in selected packages
+        "#});
+}
+
 #[test]
 fn can_choose_target_by_kind() {
     let t = TempDir::new().unwrap();
@@ -795,6 +855,50 @@ fn can_choose_target_by_kind() {
     assert_eq!(t.child("target/dev").files(), vec!["hello.sierra.json"]);
 }
 
+#[test]
+fn can_choose_test_targets_by_kind() {
+    let t = TempDir::new().unwrap();
+    ProjectBuilder::start()
+        .name("hello")
+        .dep_cairo_test()
+        .manifest_extra("[lib]")
+        .lib_cairo(r#"fn f() -> felt252 { 42 }"#)
+        .build(&t);
+
+    Scarb::quick_snapbox()
+        .arg("build")
+        .arg("--target-kinds=test")
+        .current_dir(&t)
+        .assert()
+        .success();
+
+    let files = t.child("target/dev").files();
+    assert!(!files.is_empty());
+    assert!(files
+        .iter()
+        .all(|file| file.starts_with("hello_unittest.test.")));
+}
+
+#[test]
+fn target_kind_must_match_some_target() {
+    let t = TempDir::new().unwrap();
+    ProjectBuilder::start()
+        .name("hello")
+        .manifest_extra("[lib]")
+        .lib_cairo(r#"fn f() -> felt252 { 42 }"#)
+        .build(&t);
+
+    Scarb::quick_snapbox()
+        .arg("build")
+        .arg("--target-kinds=starknet-contract,executable")
+        .current_dir(&t)
+        .assert()
+        .failure()
+        .stdout_matches(indoc! {r#"
+            error: no targets of kind `starknet-contract`, `executable` found in selected packages
+        "#});
+}
+
 #[test]
 fn cannot_use_both_test_and_target_kind() {
     let t = TempDir::new().unwrap();
@@ -1,6 +1,8 @@
 use assert_fs::{prelude::*, TempDir};
+use indoc::indoc;
 
 use scarb_test_support::command::Scarb;
+use scarb_test_support::gitx;
 use scarb_test_support::project_builder::ProjectBuilder;
 
 #[test]
@@ -43,3 +45,50 @@ fn path_print() {
         .success();
     cache_dir.assert(predicates::path::is_dir());
 }
+
+#[test]
+fn fetch_into_overridden_cache_dir() {
+    let dep = gitx::new("dep", |t| ProjectBuilder::start().name("dep").build(&t));
+    let t = TempDir::new().unwrap();
+    ProjectBuilder::start().dep("dep", &dep).build(&t);
+    let default_cache_dir = TempDir::new().unwrap().child("default");
+    let cache_dir = TempDir::new().unwrap().child("override");
+
+    Scarb::quick_snapbox()
+        .arg("--global-cache-dir")
+        .arg(cache_dir.path())
+        .arg("fetch")
+        .env("SCARB_CACHE", default_cache_dir.path())
+        .current_dir(&t)
+        .assert()
+        .success();
+
+    cache_dir
+        .child("registry/git/db")
+        .assert(predicates::path::is_dir());
+    cache_dir
+        .child("registry/git/checkouts")
+        .assert(predicates::path::is_dir());
+    default_cache_dir.assert(predicates::path::missing());
+}
+
+#[test]
+fn cache_dir_must_be_a_directory() {
+    let t = TempDir::new().unwrap();
+    ProjectBuilder::start().build(&t);
+    let cache_file = t.child("cache");
+    cache_file.write_str("").unwrap();
+
+    Scarb::quick_snapbox()
+        .arg("fetch")
+        .env("SCARB_CACHE", cache_file.path())
+        .current_dir(&t)
+        .assert()
+        .failure()
+        .stdout_matches(indoc! {r#"
+            error: invalid cache directory: [..]cache
+
+            Caused by:
+                path is not a directory
+        "#});
+}
@@ -0,0 +1,318 @@
+use assert_fs::prelude::*;
+use assert_fs::TempDir;
+use indoc::{formatdoc, indoc};
+use url::Url;
+
+use scarb_test_support::cairo_plugin_project_builder::CairoPluginProjectBuilder;
+use scarb_test_support::command::Scarb;
+use scarb_test_support::fsx::ChildPathEx;
+use scarb_test_support::gitx;
+use scarb_test_support::project_builder::ProjectBuilder;
+
+fn env_printing_plugin(t: &impl PathChild) {
+    CairoPluginProjectBuilder::default()
+        .lib_rs(indoc! {r#"
+        use cairo_lang_macro::{ProcMacroResult, TokenStream, attribute_macro, Diagnostic};
+
+        #[attribute_macro]
+        pub fn some(_attr: TokenStream, token_stream: TokenStream) -> ProcMacroResult {
+            let first = option_env!("SCARB_TEST_FIRST").unwrap_or_default();
+            let second = option_env!("SCARB_TEST_SECOND").unwrap_or_default();
+            let diag = Diagnostic::warn(format!("first={first} second={second}"));
+            ProcMacroResult::new(token_stream)
+                .with_diagnostics(diag.into())
+        }
+        "#})
+        .build(&t.child("some"));
+    ProjectBuilder::start()
+        .name("hello")
+        .version("1.0.0")
+        .dep("some", t.child("some"))
+        .lib_cairo(indoc! {r#"
+            #[some]
+            fn f() -> felt252 { 12 }
+        "#})
+        .build(&t.child("hello"));
+}
+
+fn registry_index(t: &TempDir) -> (assert_fs::fixture::ChildPath, String) {
+    let index = t.child("index");
+    index.create_dir_all().unwrap();
+    let url = Url::from_directory_path(&index).unwrap().to_string();
+    (index, url)
+}
+
+#[test]
+fn global_config_is_used() {
+    let config_dir = TempDir::new().unwrap();
+    let registry_t = TempDir::new().unwrap();
+    let (index, url) = registry_index(&registry_t);
+    config_dir
+        .child("config.toml")
+        .write_str(&formatdoc! {r#"
+            [registry]
+            index = "{url}"
+        "#})
+        .unwrap();
+
+    let t = TempDir::new().unwrap();
+    ProjectBuilder::start()
+        .name("foo")
+        .version("1.0.0")
+        .build(&t);
+
+    Scarb::quick_snapbox()
+        .env("SCARB_CONFIG", config_dir.path())
+        .arg("publish")
+        .arg("--no-verify")
+        .current_dir(&t)
+        .assert()
+        .success();
+
+    index
+        .child("index/3/f/foo.json")
+        .assert(predicates::path::is_file());
+}
+
+#[test]
+fn local_config_overrides_global_config() {
+    let config_dir = TempDir::new().unwrap();
+    let global_registry_t = TempDir::new().unwrap();
+    let (global_index, global_url) = registry_index(&global_registry_t);
+    let local_registry_t = TempDir::new().unwrap();
+    let (local_index, local_url) = registry_index(&local_registry_t);
+    config_dir
+        .child("config.toml")
+        .write_str(&formatdoc! {r#"
+            [registry]
+            index = "{global_url}"
+        "#})
+        .unwrap();
+
+    let t = TempDir::new().unwrap();
+    ProjectBuilder::start()
+        .name("foo")
+        .version("1.0.0")
+        .build(&t);
+    t.child(".scarb/config.toml")
+        .write_str(&formatdoc! {r#"
+            [registry]
+            index = "{local_url}"
+        "#})
+        .unwrap();
+
+    Scarb::quick_snapbox()
+        .env("SCARB_CONFIG", config_dir.path())
+        .arg("publish")
+        .arg("--no-verify")
+        .current_dir(&t)
+        .assert()
+        .success();
+
+    local_index
+        .child("index/3/f/foo.json")
+        .assert(predicates::path::is_file());
+    assert_eq!(global_index.tree(), "");
+}
+
+#[test]
+fn offline_from_global_config() {
+    let config_dir = TempDir::new().unwrap();
+    config_dir
+        .child("config.toml")
+        .write_str("[net]\noffline = true\n")
+        .unwrap();
+
+    let dep = gitx::new("dep", |t| ProjectBuilder::start().name("dep").build(&t));
+    let t = TempDir::new().unwrap();
+    ProjectBuilder::start()
+        .name("hello")
+        .dep("dep", &dep)
+        .build(&t);
+
+    Scarb::quick_snapbox()
+        .env("SCARB_CONFIG", config_dir.path())
+        .arg("fetch")
+        .current_dir(&t)
+        .assert()
+        .failure()
+        .stdout_matches(indoc! {r#"
+            ...
+            [..]offline mode[..]
+            ...
+        "#});
+}
+
+#[test]
+fn invalid_config_file() {
+    let config_dir = TempDir::new().unwrap();
+    config_dir.child("config.toml").write_str("[net\n").unwrap();
+
+    let t = TempDir::new().unwrap();
+    ProjectBuilder::start().name("hello").build(&t);
+
+    Scarb::quick_snapbox()
+        .env("SCARB_CONFIG", config_dir.path())
+        .arg("fetch")
+        .current_dir(&t)
+        .assert()
+        .failure()
+        .stdout_matches(indoc! {r#"
+            error: failed to parse config file at: [..]config.toml
+            ...
+        "#});
+}
+
+#[test]
+fn env_is_visible_to_proc_macro() {
+    let t = TempDir::new().unwrap();
+    env_printing_plugin(&t);
+    t.child("hello/.scarb/config.toml")
+        .write_str(indoc! {r#"
+            [env]
+            SCARB_TEST_FIRST = "one"
+            SCARB_TEST_SECOND = { value = "two", relative = true }
+        "#})
+        .unwrap();
+
+    Scarb::quick_snapbox()
+        .arg("build")
+        // Disable output from Cargo.
+        .env("CARGO_TERM_QUIET", "true")
+        .current_dir(t.child("hello"))
+        .assert()
+        .success()
+        .stdout_matches(indoc! {r#"
+            [..] Compiling some v1.0.0 ([..]Scarb.toml)
+            [..] Compiling hello v1.0.0 ([..]Scarb.toml)
+            warn: Plugin diagnostic: first=one second=[..]hello[..]two
+             --> [..]lib.cairo:1:1
+            #[some]
+            ^^^^^^^
+
+            [..]Finished `dev` profile target(s) in [..]
+        "#});
+}
+
+#[test]
+fn env_force_overrides_existing_value() {
+    let t = TempDir::new().unwrap();
+    env_printing_plugin(&t);
+    t.child("hello/.scarb/config.toml")
+        .write_str(indoc! {r#"
+            [env]
+            SCARB_TEST_FIRST = "config"
+            SCARB_TEST_SECOND = { value = "config", force = true }
+        "#})
+        .unwrap();
+
+    Scarb::quick_snapbox()
+        .arg("build")
+        // Disable output from Cargo.
+        .env("CARGO_TERM_QUIET", "true")
+        .env("SCARB_TEST_FIRST", "environment")
+        .env("SCARB_TEST_SECOND", "environment")
+        .current_dir(t.child("hello"))
+        .assert()
+        .success()
+        .stdout_matches(indoc! {r#"
+            [..] Compiling some v1.0.0 ([..]Scarb.toml)
+            [..] Compiling hello v1.0.0 ([..]Scarb.toml)
+            warn: Plugin diagnostic: first=environment second=config
+             --> [..]lib.cairo:1:1
+            #[some]
+            ^^^^^^^
+
+            [..]Finished `dev` profile target(s) in [..]
+        "#});
+}
+
+#[test]
+fn env_is_visible_to_scripts() {
+    let t = TempDir::new().unwrap();
+    ProjectBuilder::start()
+        .manifest_extra(indoc! {r#"
+            [scripts]
+            some_script = "echo $SCARB_TEST_FIRST $SCARB_TEST_SECOND"
+        "#})
+        .build(&t);
+    t.child(".scarb/config.toml")
+        .write_str(indoc! {r#"
+            [env]
+            SCARB_TEST_FIRST = "config"
+            SCARB_TEST_SECOND = "config"
+        "#})
+        .unwrap();
+
+    Scarb::quick_snapbox()
+        .env("SCARB_TEST_FIRST", "environment")
+        .env_remove("SCARB_TEST_SECOND")
+        .args(["run", "some_script"])
+        .current_dir(&t)
+        .assert()
+        .success()
+        .stdout_eq("environment config\n");
+}
+
+fn profile_env_project(t: &TempDir) {
+    env_printing_plugin(t);
+    t.child("hello/.scarb/config.toml")
+        .write_str(indoc! {r#"
+            [env]
+            SCARB_TEST_FIRST = "base"
+            SCARB_TEST_SECOND = "base"
+
+            [profile.release.env]
+            SCARB_TEST_SECOND = "release"
+        "#})
+        .unwrap();
+}
+
+#[test]
+fn profile_env_overrides_base_env() {
+    let t = TempDir::new().unwrap();
+    profile_env_project(&t);
+
+    Scarb::quick_snapbox()
+        .arg("--release")
+        .arg("build")
+        // Disable output from Cargo.
+        .env("CARGO_TERM_QUIET", "true")
+        .current_dir(t.child("hello"))
+        .assert()
+        .success()
+        .stdout_matches(indoc! {r#"
+            [..] Compiling some v1.0.0 ([..]Scarb.toml)
+            [..] Compiling hello v1.0.0 ([..]Scarb.toml)
+            warn: Plugin diagnostic: first=base second=release
+             --> [..]lib.cairo:1:1
+            #[some]
+            ^^^^^^^
+
+            [..]Finished `release` profile target(s) in [..]
+        "#});
+}
+
+#[test]
+fn profile_env_does_not_affect_other_profiles() {
+    let t = TempDir::new().unwrap();
+    profile_env_project(&t);
+
+    Scarb::quick_snapbox()
+        .arg("build")
+        // Disable output from Cargo.
+        .env("CARGO_TERM_QUIET", "true")
+        .current_dir(t.child("hello"))
+        .assert()
+        .success()
+        .stdout_matches(indoc! {r#"
+            [..] Compiling some v1.0.0 ([..]Scarb.toml)
+            [..] Compiling hello v1.0.0 ([..]Scarb.toml)
+            warn: Plugin diagnostic: first=base second=base
+             --> [..]lib.cairo:1:1
+            #[some]
+            ^^^^^^^
+
+            [..]Finished `dev` profile target(s) in [..]
+        "#});
+}
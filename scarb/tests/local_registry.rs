@@ -4,10 +4,12 @@ use indoc::{formatdoc, indoc};
 use serde_json::json;
 use url::Url;
 
+use scarb_metadata::Metadata;
 use scarb_test_support::command::Scarb;
 use scarb_test_support::fsx::ChildPathEx;
 use scarb_test_support::project_builder::{Dep, DepBuilder, ProjectBuilder};
 use scarb_test_support::registry::local::LocalRegistry;
+use scarb_test_support::workspace_builder::WorkspaceBuilder;
 
 #[test]
 fn usage() {
@@ -314,3 +316,316 @@ fn publish_overwrites_existing() {
 // TODO(mkaput): Test errors properly when package is in index, but tarball is missing.
 // TODO(mkaput): Test publishing with target-specific dependencies.
 // TODO(mkaput): Test offline mode.
+
+fn publishable_workspace(t: &TempDir) {
+    let first = t.child("first");
+    let second = t.child("second");
+    let third = t.child("third");
+    ProjectBuilder::start()
+        .name("first")
+        .version("1.0.0")
+        .dep("third", third.version("1.0.0"))
+        .lib_cairo("fn main() -> felt252 { third::main() }")
+        .build(&first);
+    ProjectBuilder::start()
+        .name("second")
+        .version("1.0.0")
+        .dep("first", first.version("1.0.0"))
+        .lib_cairo("fn main() -> felt252 { first::main() }")
+        .build(&second);
+    ProjectBuilder::start()
+        .name("third")
+        .version("1.0.0")
+        .lib_cairo("fn main() -> felt252 { 0 }")
+        .build(&third);
+    WorkspaceBuilder::start()
+        .add_member("first")
+        .add_member("second")
+        .add_member("third")
+        .build(t);
+}
+
+#[test]
+fn publish_workspace_in_dependency_order() {
+    let t = TempDir::new().unwrap();
+    publishable_workspace(&t);
+    let registry_t = TempDir::new().unwrap();
+    let index = registry_t.child("index");
+    index.create_dir_all().unwrap();
+
+    Scarb::quick_snapbox()
+        .arg("publish")
+        .arg("--workspace")
+        .arg("--no-verify")
+        .arg("--index")
+        .arg(Url::from_directory_path(&index).unwrap().to_string())
+        .current_dir(&t)
+        .assert()
+        .success()
+        .stdout_matches(indoc! {r#"
+            [..] Packaging third v1.0.0 ([..])
+            ...
+            [..] Uploading third v1.0.0 (registry+file://[..])
+            [..] Published third v1.0.0 (registry+file://[..])
+            [..] Packaging first v1.0.0 ([..])
+            ...
+            [..] Uploading first v1.0.0 (registry+file://[..])
+            [..] Published first v1.0.0 (registry+file://[..])
+            [..] Packaging second v1.0.0 ([..])
+            ...
+            [..] Uploading second v1.0.0 (registry+file://[..])
+            [..] Published second v1.0.0 (registry+file://[..])
+        "#});
+
+    assert_eq!(
+        index.tree(),
+        indoc! {r#"
+            first-1.0.0.tar.zst
+            index/
+            . fi/
+            . . rs/
+            . . . first.json
+            . se/
+            . . co/
+            . . . second.json
+            . th/
+            . . ir/
+            . . . third.json
+            second-1.0.0.tar.zst
+            third-1.0.0.tar.zst
+        "#}
+    );
+}
+
+#[test]
+fn publish_workspace_skips_published_packages() {
+    let t = TempDir::new().unwrap();
+    publishable_workspace(&t);
+    let registry_t = TempDir::new().unwrap();
+    let index = registry_t.child("index");
+    index.create_dir_all().unwrap();
+
+    Scarb::quick_snapbox()
+        .arg("publish")
+        .arg("--package")
+        .arg("third")
+        .arg("--no-verify")
+        .arg("--index")
+        .arg(Url::from_directory_path(&index).unwrap().to_string())
+        .current_dir(&t)
+        .assert()
+        .success();
+
+    Scarb::quick_snapbox()
+        .arg("publish")
+        .arg("--workspace")
+        .arg("--no-verify")
+        .arg("--index")
+        .arg(Url::from_directory_path(&index).unwrap().to_string())
+        .current_dir(&t)
+        .assert()
+        .success()
+        .stdout_matches(indoc! {r#"
+            [..] Skipping third v1.0.0 (registry+file://[..]) (already published)
+            [..] Packaging first v1.0.0 ([..])
+            ...
+            [..] Published first v1.0.0 (registry+file://[..])
+            [..] Packaging second v1.0.0 ([..])
+            ...
+            [..] Published second v1.0.0 (registry+file://[..])
+        "#});
+}
+
+#[test]
+fn publish_workspace_skips_unpublishable_packages() {
+    let t = TempDir::new().unwrap();
+    publishable_workspace(&t);
+    ProjectBuilder::start()
+        .name("second")
+        .version("1.0.0")
+        .dep("first", t.child("first").version("1.0.0"))
+        .manifest_package_extra("publish = false")
+        .lib_cairo("fn main() -> felt252 { first::main() }")
+        .build(&t.child("second"));
+    let registry_t = TempDir::new().unwrap();
+    let index = registry_t.child("index");
+    index.create_dir_all().unwrap();
+
+    Scarb::quick_snapbox()
+        .arg("publish")
+        .arg("--workspace")
+        .arg("--no-verify")
+        .arg("--index")
+        .arg(Url::from_directory_path(&index).unwrap().to_string())
+        .current_dir(&t)
+        .assert()
+        .success()
+        .stdout_matches(indoc! {r#"
+            [..] Packaging third v1.0.0 ([..])
+            ...
+            [..] Published third v1.0.0 (registry+file://[..])
+            [..] Packaging first v1.0.0 ([..])
+            ...
+            [..] Published first v1.0.0 (registry+file://[..])
+            [..] Skipping second v1.0.0 ([..]) (publishing disabled)
+        "#});
+
+    assert_eq!(
+        index.tree(),
+        indoc! {r#"
+            first-1.0.0.tar.zst
+            index/
+            . fi/
+            . . rs/
+            . . . first.json
+            . th/
+            . . ir/
+            . . . third.json
+            third-1.0.0.tar.zst
+        "#}
+    );
+}
+
+#[test]
+fn publish_workspace_dry_run() {
+    let t = TempDir::new().unwrap();
+    publishable_workspace(&t);
+    let registry_t = TempDir::new().unwrap();
+    let index = registry_t.child("index");
+    index.create_dir_all().unwrap();
+
+    Scarb::quick_snapbox()
+        .arg("publish")
+        .arg("--workspace")
+        .arg("--no-verify")
+        .arg("--dry-run")
+        .arg("--index")
+        .arg(Url::from_directory_path(&index).unwrap().to_string())
+        .current_dir(&t)
+        .assert()
+        .success()
+        .stdout_matches(indoc! {r#"
+            [..] Packaging third v1.0.0 ([..])
+            ...
+            warn: aborting upload due to dry run
+            [..] Packaging first v1.0.0 ([..])
+            ...
+            warn: aborting upload due to dry run
+            [..] Packaging second v1.0.0 ([..])
+            ...
+            warn: aborting upload due to dry run
+        "#});
+
+    assert_eq!(index.tree(), "");
+}
+
+#[test]
+fn patch_registry_dependency_with_path() {
+    let mut registry = LocalRegistry::create();
+    registry.publish(|t| {
+        ProjectBuilder::start()
+            .name("bar")
+            .version("1.0.0")
+            .lib_cairo(r#"fn f() -> felt252 { 0 }"#)
+            .build(t);
+    });
+
+    let t = TempDir::new().unwrap();
+    let bar = t.child("bar");
+    ProjectBuilder::start()
+        .name("bar")
+        .version("1.0.1")
+        .lib_cairo(r#"fn f() -> felt252 { 1 }"#)
+        .build(&bar);
+    let foo = t.child("foo");
+    ProjectBuilder::start()
+        .name("foo")
+        .version("0.1.0")
+        .dep("bar", Dep.version("1").registry(&registry))
+        .lib_cairo(r#"fn f() -> felt252 { bar::f() }"#)
+        .manifest_extra(formatdoc! {r#"
+            [patch."{registry}"]
+            bar = {{ path = "../bar" }}
+        "#})
+        .build(&foo);
+
+    let metadata = Scarb::quick_snapbox()
+        .args(["--json", "metadata", "--format-version", "1"])
+        .current_dir(&foo)
+        .stdout_json::<Metadata>();
+    let bar = metadata.packages.iter().find(|p| p.name == "bar").unwrap();
+    assert_eq!(bar.version.to_string(), "1.0.1");
+    assert!(bar.source.repr.starts_with("path+"));
+
+    Scarb::quick_snapbox()
+        .arg("build")
+        .current_dir(&foo)
+        .assert()
+        .success();
+}
+
+#[test]
+fn patch_with_mismatched_version() {
+    let mut registry = LocalRegistry::create();
+    registry.publish(|t| {
+        ProjectBuilder::start()
+            .name("bar")
+            .version("1.0.0")
+            .lib_cairo(r#"fn f() -> felt252 { 0 }"#)
+            .build(t);
+    });
+
+    let t = TempDir::new().unwrap();
+    ProjectBuilder::start()
+        .name("bar")
+        .version("2.0.0")
+        .build(&t.child("bar"));
+    let foo = t.child("foo");
+    ProjectBuilder::start()
+        .name("foo")
+        .version("0.1.0")
+        .dep("bar", Dep.version("1").registry(&registry))
+        .manifest_extra(formatdoc! {r#"
+            [patch."{registry}"]
+            bar = {{ path = "../bar" }}
+        "#})
+        .build(&foo);
+
+    Scarb::quick_snapbox()
+        .arg("fetch")
+        .current_dir(&foo)
+        .assert()
+        .failure()
+        .stdout_matches(indoc! {r#"
+            ...
+            [..]patch for `bar` points to version 2.0.0, which does not match the version requirement `^1`
+            ...
+        "#});
+}
+
+#[test]
+fn patch_with_missing_path() {
+    let registry = LocalRegistry::create();
+    let t = TempDir::new().unwrap();
+    ProjectBuilder::start()
+        .name("foo")
+        .version("0.1.0")
+        .dep("bar", Dep.version("1").registry(&registry))
+        .manifest_extra(formatdoc! {r#"
+            [patch."{registry}"]
+            bar = {{ path = "bar" }}
+        "#})
+        .build(&t);
+
+    Scarb::quick_snapbox()
+        .arg("fetch")
+        .current_dir(&t)
+        .assert()
+        .failure()
+        .stdout_matches(indoc! {r#"
+            error: failed to parse manifest at: [..]Scarb.toml
+
+            Caused by:
+                patch for `bar` points to `[..]bar`, which does not contain a manifest file
+        "#});
+}
@@ -1,14 +1,22 @@
 use std::collections::BTreeMap;
+use std::io::{BufRead, BufReader};
+use std::process::Stdio;
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
 
 use assert_fs::prelude::*;
 use indoc::indoc;
 use itertools::Itertools;
+use semver::{Version, VersionReq};
 use serde_json::json;
 
+use scarb_build_metadata::CAIRO_VERSION;
 use scarb_metadata::{Cfg, DepKind, ManifestMetadataBuilder, Metadata, PackageMetadata};
 use scarb_test_support::cairo_plugin_project_builder::CairoPluginProjectBuilder;
 use scarb_test_support::command::{CommandExt, Scarb};
 use scarb_test_support::fsx;
+use scarb_test_support::fsx::ChildPathEx;
 use scarb_test_support::project_builder::{Dep, DepBuilder, ProjectBuilder};
 use scarb_test_support::workspace_builder::WorkspaceBuilder;
 
@@ -422,6 +430,66 @@ fn no_dep() {
     );
 }
 
+#[test]
+fn lockfile_path_and_status() {
+    let t = assert_fs::TempDir::new().unwrap();
+    create_local_dependencies_setup(&t);
+    let metadata = || {
+        Scarb::quick_snapbox()
+            .arg("--json")
+            .arg("metadata")
+            .arg("--format-version")
+            .arg("1")
+            .current_dir(&t)
+            .stdout_json::<Metadata>()
+    };
+
+    // No lockfile yet, so resolution has to write it.
+    let meta = metadata();
+    assert_eq!(
+        meta.lockfile_path.unwrap(),
+        fsx::canonicalize_utf8(&t).unwrap().join("Scarb.lock")
+    );
+    assert_eq!(meta.lockfile_up_to_date, Some(false));
+
+    let meta = metadata();
+    assert_eq!(meta.lockfile_up_to_date, Some(true));
+
+    // A lockfile missing a package is stale.
+    t.child("Scarb.lock")
+        .write_str(indoc! {r#"
+            # Code generated by scarb DO NOT EDIT.
+            version = 1
+
+            [[package]]
+            name = "x"
+            version = "1.0.0"
+        "#})
+        .unwrap();
+    let meta = metadata();
+    assert_eq!(meta.lockfile_up_to_date, Some(false));
+}
+
+#[test]
+fn lockfile_status_unknown_without_deps() {
+    let t = assert_fs::TempDir::new().unwrap();
+    create_local_dependencies_setup(&t);
+    let meta = Scarb::quick_snapbox()
+        .arg("--json")
+        .arg("metadata")
+        .arg("--format-version")
+        .arg("1")
+        .arg("--no-deps")
+        .current_dir(&t)
+        .stdout_json::<Metadata>();
+
+    assert_eq!(
+        meta.lockfile_path.unwrap(),
+        fsx::canonicalize_utf8(&t).unwrap().join("Scarb.lock")
+    );
+    assert_eq!(meta.lockfile_up_to_date, None);
+}
+
 #[test]
 fn manifest_targets_and_metadata() {
     let t = assert_fs::TempDir::new().unwrap();
@@ -529,6 +597,48 @@ fn manifest_targets_and_metadata() {
     );
 }
 
+#[test]
+fn target_edition_is_resolved() {
+    let t = assert_fs::TempDir::new().unwrap();
+    ProjectBuilder::start()
+        .name("hello")
+        .edition("2023_10")
+        .manifest_extra(indoc! {r#"
+            [lib]
+            edition = "2023_01"
+
+            [[target.starknet-contract]]
+        "#})
+        .build(&t);
+
+    let meta = Scarb::quick_snapbox()
+        .args(["--json", "metadata", "--format-version=1"])
+        .current_dir(&t)
+        .stdout_json::<Metadata>();
+
+    let package = meta.packages.iter().find(|p| p.name == "hello").unwrap();
+    let editions = package
+        .targets
+        .iter()
+        .map(|t| (t.kind.as_str(), t.edition.as_deref().unwrap()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        editions,
+        vec![
+            ("lib", "2023_01"),
+            ("starknet-contract", "2023_10"),
+            ("test", "2023_01"),
+        ]
+    );
+
+    let unit = meta
+        .compilation_units
+        .iter()
+        .find(|cu| cu.target.kind == "lib")
+        .unwrap();
+    assert_eq!(unit.target.edition.as_deref(), Some("2023_01"));
+}
+
 #[test]
 fn tool_metadata_is_packaged_contained() {
     let t = assert_fs::TempDir::new().unwrap();
@@ -1387,6 +1497,36 @@ fn includes_experimental_features() {
         .contains(&String::from("associated_item_constraints")));
 }
 
+#[test]
+fn includes_publish() {
+    let t = assert_fs::TempDir::new().unwrap();
+    ProjectBuilder::start()
+        .name("hello")
+        .version("0.1.0")
+        .build(&t.child("hello"));
+    ProjectBuilder::start()
+        .name("private")
+        .version("0.1.0")
+        .manifest_package_extra("publish = false")
+        .build(&t.child("private"));
+    WorkspaceBuilder::start()
+        .add_member("hello")
+        .add_member("private")
+        .build(&t);
+
+    let metadata = Scarb::quick_snapbox()
+        .arg("--json")
+        .arg("metadata")
+        .arg("--format-version")
+        .arg("1")
+        .current_dir(&t)
+        .stdout_json::<Metadata>();
+
+    let packages = packages_by_name(metadata);
+    assert!(packages.get("hello").unwrap().publish);
+    assert!(!packages.get("private").unwrap().publish);
+}
+
 #[test]
 fn add_redeposit_gas_disabled_by_default() {
     let t = assert_fs::TempDir::new().unwrap();
@@ -1552,3 +1692,451 @@ fn can_allow_prebuilt_plugins_for_subtree() {
     assert!(cu.cairo_plugins[0].package.repr.starts_with("q"));
     assert!(cu.cairo_plugins[0].prebuilt_allowed.unwrap());
 }
+
+#[test]
+fn cairo_plugins_report_version_and_builtin() {
+    let t = assert_fs::TempDir::new().unwrap();
+
+    CairoPluginProjectBuilder::default()
+        .name("q")
+        .scarb_project(|builder| {
+            builder
+                .name("q")
+                .version("1.2.3")
+                .manifest_extra("[cairo-plugin]")
+        })
+        .build(&t.child("q"));
+
+    ProjectBuilder::start()
+        .name("x")
+        .version("1.0.0")
+        .lib_cairo(r"fn f() -> felt252 { 42 }")
+        .dep_cairo_test()
+        .dep("q", Dep.path("q"))
+        .build(&t);
+
+    let meta = Scarb::quick_snapbox()
+        .arg("--json")
+        .arg("metadata")
+        .arg("--format-version")
+        .arg("1")
+        .current_dir(&t)
+        .stdout_json::<Metadata>();
+    let cu = meta
+        .compilation_units
+        .iter()
+        .find(|cu| cu.target.kind == "test")
+        .unwrap();
+
+    let plugin = |name: &str| {
+        cu.cairo_plugins
+            .iter()
+            .find(|plugin| plugin.package.repr.starts_with(name))
+            .unwrap()
+    };
+
+    let q = plugin("q");
+    assert_eq!(q.version, Some(Version::new(1, 2, 3)));
+    assert!(!q.builtin);
+
+    let cairo_test = plugin("cairo_test");
+    assert_eq!(cairo_test.version, Some(CAIRO_VERSION.parse().unwrap()));
+    assert!(cairo_test.builtin);
+}
+
+#[test]
+fn cairo_plugins_report_built_artifact() {
+    let t = assert_fs::TempDir::new().unwrap();
+    let cache_dir = assert_fs::TempDir::new().unwrap();
+
+    CairoPluginProjectBuilder::default()
+        .name("q")
+        .build(&t.child("q"));
+    ProjectBuilder::start()
+        .name("x")
+        .version("1.0.0")
+        .lib_cairo(r"fn f() -> felt252 { 42 }")
+        .dep("q", Dep.path("q"))
+        .build(&t);
+
+    let plugin = || {
+        let meta = Scarb::quick_snapbox()
+            .env("SCARB_CACHE", cache_dir.path())
+            .arg("--json")
+            .arg("metadata")
+            .arg("--format-version")
+            .arg("1")
+            .current_dir(&t)
+            .stdout_json::<Metadata>();
+        meta.compilation_units
+            .into_iter()
+            .find(|cu| cu.target.kind == "lib")
+            .unwrap()
+            .cairo_plugins
+            .into_iter()
+            .find(|plugin| plugin.package.repr.starts_with("q "))
+            .unwrap()
+    };
+
+    let q = plugin();
+    assert_eq!(q.artifact_path, None);
+    assert!(!q.prebuilt);
+
+    Scarb::quick_snapbox()
+        .env("SCARB_CACHE", cache_dir.path())
+        // Disable output from Cargo.
+        .env("CARGO_TERM_QUIET", "true")
+        .arg("build")
+        .current_dir(&t)
+        .assert()
+        .success();
+
+    let q = plugin();
+    let artifact_path = q.artifact_path.unwrap();
+    assert!(artifact_path.exists());
+    assert!(!q.prebuilt);
+}
+
+#[test]
+fn watch_emits_metadata_on_manifest_change() {
+    let t = assert_fs::TempDir::new().unwrap();
+    ProjectBuilder::start()
+        .name("hello")
+        .version("0.1.0")
+        .build(&t);
+
+    let mut child = Scarb::new()
+        .std()
+        .args(["--json", "metadata", "--format-version", "1", "--watch"])
+        .current_dir(&t)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .unwrap();
+
+    let (tx, rx) = mpsc::channel();
+    let stdout = child.stdout.take().unwrap();
+    thread::spawn(move || {
+        for line in BufReader::new(stdout).lines() {
+            let Ok(line) = line else { break };
+            if tx.send(line).is_err() {
+                break;
+            }
+        }
+    });
+    let next_metadata = || {
+        let line = rx.recv_timeout(Duration::from_secs(60)).unwrap();
+        serde_json::from_str::<Metadata>(&line).unwrap()
+    };
+    let hello_version = |meta: Metadata| packages_by_name(meta)["hello"].version.to_string();
+
+    assert_eq!(hello_version(next_metadata()), "0.1.0");
+
+    ProjectBuilder::start()
+        .name("hello")
+        .version("0.2.0")
+        .just_manifest(&t);
+    assert_eq!(hello_version(next_metadata()), "0.2.0");
+
+    child.kill().unwrap();
+    child.wait().unwrap();
+}
+
+#[test]
+fn in_process_metadata_matches_subprocess() {
+    let t = assert_fs::TempDir::new().unwrap();
+    let dep = t.child("dep");
+    ProjectBuilder::start()
+        .name("dep")
+        .version("0.1.0")
+        .build(&dep);
+    ProjectBuilder::start()
+        .name("hello")
+        .version("0.1.0")
+        .dep("dep", &dep)
+        .build(&t);
+
+    let config = Scarb::test_config(t.child("Scarb.toml"));
+    let ws = scarb::ops::read_workspace(config.manifest_path(), &config).unwrap();
+    let opts = scarb::ops::MetadataOptions {
+        version: 1,
+        no_deps: false,
+        features: scarb::ops::FeaturesOpts {
+            features: scarb::ops::FeaturesSelector::Features(Vec::new()),
+            no_default_features: false,
+        },
+        ignore_cairo_version: false,
+        unit: None,
+        include_manifest_source: false,
+        include_target_defaults: false,
+    };
+    let resolve = scarb::ops::resolve_workspace(&ws).unwrap();
+    let in_process = scarb::ops::collect_metadata_with_resolve(&opts, &resolve, &ws).unwrap();
+
+    let subprocess = Scarb::from_config(&config)
+        .snapbox()
+        .arg("--json")
+        .arg("metadata")
+        .arg("--format-version")
+        .arg("1")
+        .current_dir(&t)
+        .stdout_json::<Metadata>();
+
+    // Path to the executable differs, because the test binary is not Scarb itself.
+    let to_json = |metadata: Metadata| {
+        let mut value = serde_json::to_value(metadata).unwrap();
+        value.as_object_mut().unwrap().remove("app_exe");
+        value
+    };
+    assert_eq!(to_json(in_process), to_json(subprocess));
+}
+
+#[test]
+fn diff_against_baseline() {
+    let t = assert_fs::TempDir::new().unwrap();
+    let dep = t.child("dep");
+    ProjectBuilder::start()
+        .name("dep")
+        .version("0.1.0")
+        .build(&dep);
+    let hello = t.child("hello");
+    ProjectBuilder::start()
+        .name("hello")
+        .version("0.1.0")
+        .dep("dep", &dep)
+        .build(&hello);
+
+    let output = Scarb::quick_snapbox()
+        .arg("metadata")
+        .arg("--format-version")
+        .arg("1")
+        .current_dir(&hello)
+        .assert()
+        .success();
+    let baseline = t.child("baseline.json");
+    baseline.write_binary(&output.get_output().stdout).unwrap();
+
+    let diff = || {
+        Scarb::quick_snapbox()
+            .arg("metadata")
+            .arg("--format-version")
+            .arg("1")
+            .arg("--diff")
+            .arg(baseline.path())
+            .current_dir(&hello)
+    };
+
+    diff()
+        .assert()
+        .success()
+        .stdout_matches("no differences found\n");
+
+    ProjectBuilder::start()
+        .name("dep")
+        .version("0.2.0")
+        .just_manifest(&dep);
+    ProjectBuilder::start()
+        .name("hello")
+        .version("0.2.0")
+        .dep("dep", &dep)
+        .just_manifest(&hello);
+
+    diff().assert().failure().stdout_matches(indoc! {r#"
+        changed dep v0.1.0 -> v0.2.0
+        changed hello v0.1.0 -> v0.2.0
+    "#});
+
+    Scarb::quick_snapbox()
+        .arg("--json")
+        .arg("metadata")
+        .arg("--format-version")
+        .arg("1")
+        .arg("--diff")
+        .arg(baseline.path())
+        .arg("--only-dependencies")
+        .current_dir(&hello)
+        .assert()
+        .failure()
+        .stdout_matches(indoc! {r#"
+            {"added":[],"removed":[],"changed":[{"name":"dep","old":{"name":"dep","version":"0.1.0","source":"path+[..]"},"new":{"name":"dep","version":"0.2.0","source":"path+[..]"}}]}
+        "#});
+}
+
+#[test]
+fn diff_keys_packages_by_id() {
+    let t = assert_fs::TempDir::new().unwrap();
+    let dep = t.child("dep");
+    ProjectBuilder::start()
+        .name("dep")
+        .version("0.1.0")
+        .build(&dep);
+    let hello = t.child("hello");
+    ProjectBuilder::start()
+        .name("hello")
+        .version("0.1.0")
+        .dep("dep", &dep)
+        .build(&hello);
+
+    let output = Scarb::quick_snapbox()
+        .arg("metadata")
+        .arg("--format-version")
+        .arg("1")
+        .current_dir(&hello)
+        .assert()
+        .success();
+    let mut metadata: serde_json::Value =
+        serde_json::from_slice(&output.get_output().stdout).unwrap();
+    // Add a package with the same name as the path dependency, but from a different source.
+    let packages = metadata["packages"].as_array_mut().unwrap();
+    let mut registry_dep = packages
+        .iter()
+        .find(|package| package["name"] == "dep")
+        .unwrap()
+        .clone();
+    registry_dep["id"] = json!("dep 0.1.0 (registry+https://example.com/)");
+    registry_dep["source"] = json!("registry+https://example.com/");
+    packages.push(registry_dep);
+    let baseline = t.child("baseline.json");
+    baseline
+        .write_str(&serde_json::to_string(&metadata).unwrap())
+        .unwrap();
+
+    Scarb::quick_snapbox()
+        .arg("metadata")
+        .arg("--format-version")
+        .arg("1")
+        .arg("--diff")
+        .arg(baseline.path())
+        .current_dir(&hello)
+        .assert()
+        .failure()
+        .stdout_matches(indoc! {r#"
+            removed dep v0.1.0 (registry+https://example.com/)
+        "#});
+}
+
+#[test]
+fn metadata_of_single_unit() {
+    let t = assert_fs::TempDir::new().unwrap();
+    ProjectBuilder::start()
+        .name("first")
+        .build(&t.child("first"));
+    ProjectBuilder::start()
+        .name("second")
+        .dep("first", Dep.path("../first"))
+        .build(&t.child("second"));
+    WorkspaceBuilder::start()
+        .add_member("first")
+        .add_member("second")
+        .build(&t);
+
+    let full = Scarb::quick_snapbox()
+        .args(["--json", "metadata", "--format-version=1"])
+        .current_dir(&t)
+        .stdout_json::<Metadata>();
+    let unit = full
+        .compilation_units
+        .iter()
+        .find(|cu| cu.package.repr.starts_with("first ") && cu.target.kind == "lib")
+        .unwrap();
+
+    let trimmed = Scarb::quick_snapbox()
+        .args(["--json", "metadata", "--format-version=1", "--unit"])
+        .arg(&unit.id.repr)
+        .current_dir(&t)
+        .stdout_json::<Metadata>();
+
+    assert_eq!(trimmed.compilation_units, vec![unit.clone()]);
+    assert_eq!(
+        trimmed
+            .packages
+            .iter()
+            .map(|p| p.name.as_str())
+            .sorted()
+            .collect_vec(),
+        vec!["core", "first"]
+    );
+}
+
+#[test]
+fn minimum_scarb_version() {
+    let t = assert_fs::TempDir::new().unwrap();
+    ProjectBuilder::start()
+        .name("hello")
+        .manifest_package_extra(r#"scarb-version = ">=2.8.0""#)
+        .build(&t);
+
+    let metadata = Scarb::quick_snapbox()
+        .args(["--json", "metadata", "--format-version=1", "--no-deps"])
+        .current_dir(&t)
+        .stdout_json::<Metadata>();
+    let package = metadata.find_package_by_name("hello").unwrap();
+    assert_eq!(
+        package.minimum_scarb_version,
+        Some(VersionReq::parse(">=2.8.0").unwrap())
+    );
+}
+
+#[test]
+fn metadata_of_missing_unit_fails() {
+    let t = assert_fs::TempDir::new().unwrap();
+    ProjectBuilder::start().name("hello").build(&t);
+
+    Scarb::quick_snapbox()
+        .args(["metadata", "--format-version=1", "--unit", "missing"])
+        .current_dir(&t)
+        .assert()
+        .failure()
+        .stdout_matches(indoc! {r#"
+            error: compilation unit `missing` not found in workspace
+        "#});
+}
+
+#[test]
+fn include_manifest_source() {
+    let t = assert_fs::TempDir::new().unwrap();
+    ProjectBuilder::start().name("hello").build(&t);
+    let manifest = t.child("Scarb.toml").read_to_string();
+
+    let metadata = Scarb::quick_snapbox()
+        .args(["--json", "metadata", "--format-version=1", "--no-deps"])
+        .current_dir(&t)
+        .stdout_json::<Metadata>();
+    assert!(metadata
+        .packages
+        .iter()
+        .all(|p| p.manifest_source.is_none()));
+
+    let metadata = Scarb::quick_snapbox()
+        .args([
+            "--json",
+            "metadata",
+            "--format-version=1",
+            "--no-deps",
+            "--include-manifest-source",
+        ])
+        .current_dir(&t)
+        .stdout_json::<Metadata>();
+    let package = metadata.find_package_by_name("hello").unwrap();
+    assert_eq!(package.manifest_source.as_deref(), Some(manifest.as_str()));
+}
+
+#[test]
+fn include_target_defaults() {
+    let t = assert_fs::TempDir::new().unwrap();
+    ProjectBuilder::start().name("hello").build(&t);
+
+    let lib_params = |include: bool| {
+        let metadata = Scarb::quick_snapbox()
+            .args(["--json", "metadata", "--format-version=1", "--no-deps"])
+            .args(include.then_some("--include-target-defaults"))
+            .current_dir(&t)
+            .stdout_json::<Metadata>();
+        let package = metadata.find_package_by_name("hello").unwrap();
+        let target = package.targets.iter().find(|t| t.kind == "lib").unwrap();
+        target.params.clone()
+    };
+
+    assert!(lib_params(false).get("sierra").is_none());
+    assert_eq!(lib_params(true)["sierra"], true);
+}
@@ -1769,3 +1769,83 @@ fn package_proc_macro_with_package_script() {
             "src/lib.rs",
         ]);
 }
+
+fn archive_mtimes(path: &Path) -> Vec<u64> {
+    PackageChecker::open(path)
+        .entries()
+        .expect("failed to get archive entries")
+        .map(|entry| {
+            entry
+                .expect("failed to get archive entry")
+                .header()
+                .mtime()
+                .expect("failed to get archive entry mtime")
+        })
+        .collect()
+}
+
+#[test]
+fn default_archive_mtime() {
+    let t = TempDir::new().unwrap();
+    simple_project().build(&t);
+
+    Scarb::quick_snapbox()
+        .arg("package")
+        .arg("--no-metadata")
+        .arg("--no-verify")
+        .current_dir(&t)
+        .assert()
+        .success();
+
+    let mtimes = archive_mtimes(&t.child("target/package/foo-1.0.0.tar.zst"));
+    assert!(!mtimes.is_empty());
+    assert!(mtimes.iter().all(|&mtime| mtime == 1));
+}
+
+#[test]
+fn source_date_epoch() {
+    let t = TempDir::new().unwrap();
+    simple_project().build(&t);
+
+    let package = || {
+        Scarb::quick_snapbox()
+            .env("SOURCE_DATE_EPOCH", "1700000000")
+            .arg("package")
+            .arg("--no-metadata")
+            .arg("--no-verify")
+            .current_dir(&t)
+            .assert()
+            .success();
+        let path = t.child("target/package/foo-1.0.0.tar.zst");
+        let bytes = std::fs::read(&path).unwrap();
+        (bytes, archive_mtimes(&path))
+    };
+
+    let (first, mtimes) = package();
+    t.child("src/foo.cairo").touch().unwrap();
+    let (second, _) = package();
+
+    assert_eq!(first, second);
+    assert!(!mtimes.is_empty());
+    assert!(mtimes.iter().all(|&mtime| mtime == 1700000000));
+}
+
+#[test]
+fn invalid_source_date_epoch() {
+    let t = TempDir::new().unwrap();
+    simple_project().build(&t);
+
+    Scarb::quick_snapbox()
+        .env("SOURCE_DATE_EPOCH", "yesterday")
+        .arg("package")
+        .arg("--no-metadata")
+        .arg("--no-verify")
+        .current_dir(&t)
+        .assert()
+        .failure()
+        .stdout_matches(indoc! {r#"
+        [..] Packaging foo v1.0.0 [..]
+        error: failed to parse `SOURCE_DATE_EPOCH` as a UNIX timestamp: yesterday
+        ...
+        "#});
+}
@@ -2,8 +2,9 @@ use assert_fs::fixture::{ChildPath, FileWriteStr, PathCreateDir};
 use assert_fs::prelude::PathChild;
 use assert_fs::TempDir;
 use cairo_lang_macro::TokenStream;
-use indoc::indoc;
+use indoc::{formatdoc, indoc};
 use libloading::library_filename;
+use scarb::core::Digest;
 use scarb_proc_macro_server_types::methods::expand::{ExpandInline, ExpandInlineMacroParams};
 use scarb_test_support::cairo_plugin_project_builder::CairoPluginProjectBuilder;
 use scarb_test_support::command::Scarb;
@@ -143,6 +144,188 @@ fn compile_with_prebuilt_plugins_only_one_allows() {
         "#});
 }
 
+/// Build `proc_macro_example` and declare the resulting library as prebuilt for given targets.
+fn proc_macro_example_with_declared_prebuilt(
+    t: &ChildPath,
+    targets: &[&str],
+    checksum: Option<&str>,
+) {
+    let name = "proc_macro_example";
+    CairoPluginProjectBuilder::default()
+        .name(name)
+        .version("0.1.0")
+        .lib_rs(indoc! {r#"
+            use cairo_lang_macro::{ProcMacroResult, TokenStream, inline_macro};
+            #[inline_macro]
+            pub fn some(token_stream: TokenStream) -> ProcMacroResult {
+                ProcMacroResult::new(token_stream)
+            }
+        "#})
+        .build(t);
+    let dll_filename = library_filename(name);
+    let dll_filename = dll_filename.to_string_lossy().to_string();
+    let build_dir = t.child("cargo_build_dir");
+    Command::new("cargo")
+        .arg("build")
+        .arg("--release")
+        .env("CARGO_TARGET_DIR", build_dir.path())
+        .current_dir(t)
+        .assert()
+        .success();
+    t.child("prebuilt").create_dir_all().unwrap();
+    fs::copy(
+        build_dir.child("release").child(&dll_filename),
+        t.child("prebuilt").child(&dll_filename),
+    )
+    .unwrap();
+    let checksum = checksum.map(ToString::to_string).unwrap_or_else(|| {
+        let bytes = fs::read(t.child("prebuilt").child(&dll_filename)).unwrap();
+        Digest::recommended().update(&bytes).finish().to_string()
+    });
+    let mut manifest = fs::read_to_string(t.child("Scarb.toml")).unwrap();
+    for target in targets {
+        manifest.push_str(&formatdoc! {r#"
+
+            [cairo-plugin.prebuilt."{target}"]
+            path = "prebuilt/{dll_filename}"
+            checksum = "{checksum}"
+        "#});
+    }
+    t.child("Scarb.toml").write_str(&manifest).unwrap();
+}
+
+fn prebuilt_user_project(t: &ChildPath, dep: &ChildPath) {
+    ProjectBuilder::start()
+        .name("hello")
+        .lib_cairo(indoc! {r#"
+            fn main() -> u32 {
+                let x = some!(42);
+                x
+            }
+        "#})
+        .dep("proc_macro_example", dep)
+        .manifest_extra(indoc! {r#"
+            [tool.scarb]
+            allow-prebuilt-plugins = ["proc_macro_example"]
+        "#})
+        .build(t);
+}
+
+#[test]
+#[ignore = "run this test by name"]
+fn compile_with_declared_prebuilt_plugin() {
+    let t = TempDir::new().unwrap();
+    let targets = TRIPLETS.map(|(target, _)| target);
+    proc_macro_example_with_declared_prebuilt(&t.child("dep"), &targets, None);
+    prebuilt_user_project(&t.child("hello"), &t.child("dep"));
+    Scarb::quick_snapbox()
+        .arg("build")
+        // Disable Cargo and Rust compiler.
+        .env("CARGO", "/bin/false")
+        .env("RUSTC", "/bin/false")
+        .current_dir(t.child("hello"))
+        .assert()
+        .success()
+        .stdout_matches(indoc! {r#"
+            [..]Compiling hello v1.0.0 ([..]Scarb.toml)
+            [..] Finished `dev` profile target(s) in [..]
+        "#});
+}
+
+#[test]
+#[ignore = "run this test by name"]
+fn declared_prebuilt_plugin_for_other_platform_is_compiled() {
+    let t = TempDir::new().unwrap();
+    proc_macro_example_with_declared_prebuilt(&t.child("dep"), &["wasm32-unknown-unknown"], None);
+    prebuilt_user_project(&t.child("hello"), &t.child("dep"));
+    Scarb::quick_snapbox()
+        .arg("build")
+        // Disable output from Cargo.
+        .env("CARGO_TERM_QUIET", "true")
+        .current_dir(t.child("hello"))
+        .assert()
+        .success()
+        .stdout_matches(indoc! {r#"
+            [..]Compiling proc_macro_example v0.1.0 ([..])
+            [..]Compiling hello v1.0.0 ([..]Scarb.toml)
+            [..] Finished `dev` profile target(s) in [..]
+        "#});
+}
+
+#[test]
+#[ignore = "run this test by name"]
+fn declared_prebuilt_plugin_with_invalid_checksum_fails() {
+    let t = TempDir::new().unwrap();
+    let targets = TRIPLETS.map(|(target, _)| target);
+    proc_macro_example_with_declared_prebuilt(
+        &t.child("dep"),
+        &targets,
+        Some("sha256:0000000000000000000000000000000000000000000000000000000000000000"),
+    );
+    prebuilt_user_project(&t.child("hello"), &t.child("dep"));
+    Scarb::quick_snapbox()
+        .arg("build")
+        // Disable output from Cargo.
+        .env("CARGO_TERM_QUIET", "true")
+        .current_dir(t.child("hello"))
+        .assert()
+        .failure()
+        .stdout_matches(indoc! {r#"
+            ...
+            [..]checksum of prebuilt library `[..]` does not match, expected `sha256:0000000000000000000000000000000000000000000000000000000000000000`, got `sha256:[..]`
+            ...
+        "#});
+}
+
+#[test]
+#[ignore = "run this test by name"]
+fn declared_prebuilt_plugin_outside_package_root_fails() {
+    let t = TempDir::new().unwrap();
+    CairoPluginProjectBuilder::default()
+        .name("proc_macro_example")
+        .scarb_project(|b| {
+            b.name("proc_macro_example")
+                .version("0.1.0")
+                .manifest_extra(indoc! {r#"
+                    [cairo-plugin]
+
+                    [cairo-plugin.prebuilt."x86_64-unknown-linux-gnu"]
+                    path = "../libproc_macro_example.so"
+                    checksum = "sha256:0000000000000000000000000000000000000000000000000000000000000000"
+
+                    [cairo-plugin.prebuilt."aarch64-unknown-linux-gnu"]
+                    path = "../libproc_macro_example.so"
+                    checksum = "sha256:0000000000000000000000000000000000000000000000000000000000000000"
+
+                    [cairo-plugin.prebuilt."x86_64-apple-darwin"]
+                    path = "../libproc_macro_example.dylib"
+                    checksum = "sha256:0000000000000000000000000000000000000000000000000000000000000000"
+
+                    [cairo-plugin.prebuilt."aarch64-apple-darwin"]
+                    path = "../libproc_macro_example.dylib"
+                    checksum = "sha256:0000000000000000000000000000000000000000000000000000000000000000"
+
+                    [cairo-plugin.prebuilt."x86_64-pc-windows-msvc"]
+                    path = "../proc_macro_example.dll"
+                    checksum = "sha256:0000000000000000000000000000000000000000000000000000000000000000"
+                "#})
+        })
+        .build(&t.child("dep"));
+    prebuilt_user_project(&t.child("hello"), &t.child("dep"));
+    Scarb::quick_snapbox()
+        .arg("build")
+        // Disable output from Cargo.
+        .env("CARGO_TERM_QUIET", "true")
+        .current_dir(t.child("hello"))
+        .assert()
+        .failure()
+        .stdout_matches(indoc! {r#"
+            ...
+            [..]path of prebuilt library `../[..]` must be relative to the package root and stay within it
+            ...
+        "#});
+}
+
 fn invalid_prebuilt_project(t: &ChildPath) {
     let name = "invalid_prebuilt_example";
     let version = "0.1.0";
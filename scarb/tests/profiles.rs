@@ -6,6 +6,7 @@ use scarb_metadata::Metadata;
 use scarb_test_support::command::{CommandExt, Scarb};
 use scarb_test_support::fsx::ChildPathEx;
 use scarb_test_support::project_builder::ProjectBuilder;
+use scarb_test_support::workspace_builder::WorkspaceBuilder;
 
 #[test]
 fn build_defaults_to_dev() {
@@ -616,28 +617,157 @@ fn custom_profiles_can_inherit_by_name() {
 }
 
 #[test]
-fn custom_profiles_can_inherit_dev_and_release_only() {
+fn custom_profiles_can_inherit_custom_profiles() {
     let t = TempDir::new().unwrap();
     ProjectBuilder::start()
         .name("hello")
         .manifest_extra(indoc! {r#"
             [profile.some-profile]
+            inherits = "release"
+
+            [profile.some-profile.cairo]
+            inlining-strategy = "avoid"
 
             [profile.custom]
             inherits = "some-profile"
         "#})
         .build(&t);
 
+    let metadata = Scarb::quick_snapbox()
+        .args([
+            "--json",
+            "--profile",
+            "custom",
+            "metadata",
+            "--format-version",
+            "1",
+        ])
+        .current_dir(&t)
+        .stdout_json::<Metadata>();
+
+    assert_eq!(metadata.current_profile, "custom".to_string());
+    assert!(!metadata.compilation_units.is_empty());
+    for cu in metadata.compilation_units {
+        let compiler_config = cu.compiler_config;
+        // Inherited from `release`.
+        assert!(!compiler_config
+            .get("sierra_replace_ids")
+            .unwrap()
+            .as_bool()
+            .unwrap());
+        // Inherited from `some-profile`.
+        assert_eq!(
+            compiler_config
+                .get("inlining_strategy")
+                .unwrap()
+                .as_str()
+                .unwrap(),
+            "avoid"
+        );
+    }
+}
+
+#[test]
+fn metadata_contains_resolved_profile_definition() {
+    let t = TempDir::new().unwrap();
+    ProjectBuilder::start()
+        .name("first")
+        .build(&t.child("first"));
+    WorkspaceBuilder::start()
+        .add_member("first")
+        .manifest_extra(indoc! {r#"
+            [profile.custom]
+            inherits = "release"
+
+            [profile.custom.cairo]
+            inlining-strategy = "avoid"
+
+            [profile.custom.tool.some-tool]
+            some-key = "some-value"
+        "#})
+        .build(&t);
+
+    let metadata = Scarb::quick_snapbox()
+        .args([
+            "--json",
+            "--profile",
+            "custom",
+            "metadata",
+            "--format-version",
+            "1",
+        ])
+        .current_dir(&t)
+        .stdout_json::<Metadata>();
+
+    assert_eq!(metadata.current_profile, "custom".to_string());
+    let definition = metadata.profile_definition;
+    // Inherited from `release`.
+    assert_eq!(definition["cairo"]["sierra-replace-ids"], false);
+    assert_eq!(definition["cairo"]["enable-gas"], true);
+    // Overridden by `custom`.
+    assert_eq!(definition["cairo"]["inlining-strategy"], "avoid");
+    assert_eq!(definition["tool"]["some-tool"]["some-key"], "some-value");
+}
+
+#[test]
+fn custom_profiles_cannot_inherit_undefined_profiles() {
+    let t = TempDir::new().unwrap();
+    ProjectBuilder::start()
+        .name("hello")
+        .manifest_extra(indoc! {r#"
+            [profile.some-profile]
+
+            [profile.custom]
+            inherits = "some-profle"
+        "#})
+        .build(&t);
+
     Scarb::quick_snapbox()
-        .args(["--profile", "custom", "metadata", "--format-version", "1"])
+        .args([
+            "--profile",
+            "some-profile",
+            "metadata",
+            "--format-version",
+            "1",
+        ])
+        .current_dir(&t)
+        .assert()
+        .failure()
+        .stdout_matches(indoc! {r#"
+            error: failed to parse manifest at: [..]Scarb.toml
+
+            Caused by:
+                profile `custom` inherits from undefined profile `some-profle`
+                 --> [..]Scarb.toml:[..]:1
+                help: did you mean `some-profile`?
+        "#});
+}
+
+#[test]
+fn profile_inheritance_cycle() {
+    let t = TempDir::new().unwrap();
+    ProjectBuilder::start()
+        .name("hello")
+        .manifest_extra(indoc! {r#"
+            [profile.a]
+            inherits = "b"
+
+            [profile.b]
+            inherits = "a"
+        "#})
+        .build(&t);
+
+    Scarb::quick_snapbox()
+        .args(["--profile", "a", "metadata", "--format-version", "1"])
         .current_dir(&t)
         .assert()
         .failure()
         .stdout_matches(indoc! {r#"
-            error: failed to parse manifest at: [..]
+            error: failed to parse manifest at: [..]Scarb.toml
 
             Caused by:
-                profile can inherit from `dev` or `release` only, found `some-profile`
+                profile inheritance cycle: `a` -> `b` -> `a`
+                 --> [..]Scarb.toml:[..]:1
         "#});
 }
 
@@ -1,4 +1,3 @@
-use assert_fs::prelude::*;
 use assert_fs::TempDir;
 use indoc::indoc;
 
@@ -17,15 +16,7 @@ fn checksum_mismatch() {
             .build(t);
     });
 
-    registry
-        .t
-        .child("bar-1.0.0.tar.zst")
-        .write_str(
-            "This is a sequence of bytes that is definitely not a valid tar nor zst. \
-            This way, we verify that Scarb is not even attempting to read/interpret \
-            archives before verifying checksums.",
-        )
-        .unwrap();
+    registry.corrupt_archive("bar", "1.0.0");
 
     let t = TempDir::new().unwrap();
     ProjectBuilder::start()
@@ -1,13 +1,15 @@
 use std::time::Duration;
 
+use assert_fs::prelude::*;
 use assert_fs::TempDir;
 use expect_test::expect;
 use indoc::indoc;
 
 use scarb_test_support::command::Scarb;
-use scarb_test_support::project_builder::ProjectBuilder;
+use scarb_test_support::project_builder::{DepBuilder, ProjectBuilder};
 use scarb_test_support::registry::http::HttpRegistry;
 use scarb_test_support::simple_http_server::HttpPostResponse;
+use scarb_test_support::workspace_builder::WorkspaceBuilder;
 
 #[test]
 fn publish() {
@@ -192,3 +194,54 @@ fn error_from_registry() {
     "]];
     expected.assert_eq(&registry.logs());
 }
+
+#[test]
+fn publish_workspace_stops_on_failure() {
+    // 400 -> StatusCode::BAD_REQUEST
+    let registry = HttpRegistry::serve(Some(HttpPostResponse {
+        code: 400,
+        message: "Version '1.0.0' of package 'bar' already exists.".to_string(),
+    }));
+
+    let t = TempDir::new().unwrap();
+    let bar = t.child("bar");
+    ProjectBuilder::start()
+        .name("bar")
+        .version("1.0.0")
+        .lib_cairo(r#"fn f() -> felt252 { 0 }"#)
+        .build(&bar);
+    ProjectBuilder::start()
+        .name("foo")
+        .version("1.0.0")
+        .dep("bar", bar.version("1.0.0"))
+        .lib_cairo(r#"fn f() -> felt252 { bar::f() }"#)
+        .build(&t.child("foo"));
+    WorkspaceBuilder::start()
+        .add_member("foo")
+        .add_member("bar")
+        .build(&t);
+
+    Scarb::quick_snapbox()
+        .arg("publish")
+        .arg("--workspace")
+        .arg("--index")
+        .arg(&registry.url)
+        .arg("--no-verify")
+        .env("SCARB_REGISTRY_AUTH_TOKEN", "scrb_supersecrettoken")
+        .current_dir(&t)
+        .timeout(Duration::from_secs(60))
+        .assert()
+        .failure()
+        .stdout_matches(indoc! {r#"
+        [..] Packaging bar v1.0.0 ([..])
+        warn: manifest has no readme
+        warn: manifest has no description
+        warn: manifest has no license or license-file
+        warn: manifest has no documentation or homepage or repository
+        see [..]
+        [..]
+        [..] Packaged [..]
+        [..] Uploading bar v1.0.0 (registry+http[..])
+        error: upload failed with status code: `400 Bad Request`, `Version '1.0.0' of package 'bar' already exists.`
+        "#});
+}
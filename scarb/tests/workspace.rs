@@ -126,3 +126,126 @@ fn target_name_duplicate() {
             help: use different target names to resolve the conflict
         "#});
 }
+
+#[test]
+fn package_name_duplicate() {
+    let t = TempDir::new().unwrap();
+    ProjectBuilder::start()
+        .name("hello")
+        .build(&t.child("first"));
+    ProjectBuilder::start()
+        .name("hello")
+        .build(&t.child("second"));
+    WorkspaceBuilder::start()
+        .add_member("first")
+        .add_member("second")
+        .build(&t);
+
+    Scarb::quick_snapbox()
+        .arg("fetch")
+        .current_dir(&t)
+        .assert()
+        .failure()
+        .stdout_matches(indoc! {r#"
+            warn: workspace contains multiple packages named `hello`
+             --> [..]first[..]Scarb.toml
+             --> [..]second[..]Scarb.toml
+            help: use different package names to resolve the conflict
+            error: workspace contains duplicate target definitions `lib (hello)`
+            help: use different target names to resolve the conflict
+        "#});
+}
+
+#[test]
+fn members_in_topological_order() {
+    let t = TempDir::new().unwrap();
+    let first = t.child("first");
+    let second = t.child("second");
+    let third = t.child("third");
+    ProjectBuilder::start()
+        .name("first")
+        .dep("third", &third)
+        .build(&first);
+    ProjectBuilder::start()
+        .name("second")
+        .dep("first", &first)
+        .build(&second);
+    ProjectBuilder::start().name("third").build(&third);
+    WorkspaceBuilder::start()
+        .add_member("first")
+        .add_member("second")
+        .add_member("third")
+        .build(&t);
+
+    let config = Scarb::test_config(t.child("Scarb.toml"));
+    let ws = scarb::ops::read_workspace(config.manifest_path(), &config).unwrap();
+    let names = ws
+        .members_in_topological_order()
+        .unwrap()
+        .iter()
+        .map(|p| p.id.name.to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(names, vec!["third", "first", "second"]);
+}
+
+#[test]
+fn members_in_topological_order_ignores_dev_dependencies() {
+    let t = TempDir::new().unwrap();
+    let first = t.child("first");
+    let second = t.child("second");
+    ProjectBuilder::start()
+        .name("first")
+        .dev_dep("second", &second)
+        .build(&first);
+    ProjectBuilder::start()
+        .name("second")
+        .dep("first", &first)
+        .build(&second);
+    WorkspaceBuilder::start()
+        .add_member("first")
+        .add_member("second")
+        .build(&t);
+
+    let config = Scarb::test_config(t.child("Scarb.toml"));
+    let ws = scarb::ops::read_workspace(config.manifest_path(), &config).unwrap();
+    let names = ws
+        .members_in_topological_order()
+        .unwrap()
+        .iter()
+        .map(|p| p.id.name.to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(names, vec!["first", "second"]);
+}
+
+#[test]
+fn members_in_topological_order_detects_cycle() {
+    let t = TempDir::new().unwrap();
+    let first = t.child("first");
+    let second = t.child("second");
+    let third = t.child("third");
+    ProjectBuilder::start()
+        .name("first")
+        .dep("second", &second)
+        .build(&first);
+    ProjectBuilder::start()
+        .name("second")
+        .dep("first", &first)
+        .build(&second);
+    ProjectBuilder::start()
+        .name("third")
+        .dep("first", &first)
+        .build(&third);
+    WorkspaceBuilder::start()
+        .add_member("first")
+        .add_member("second")
+        .add_member("third")
+        .build(&t);
+
+    let config = Scarb::test_config(t.child("Scarb.toml"));
+    let ws = scarb::ops::read_workspace(config.manifest_path(), &config).unwrap();
+    let err = ws.members_in_topological_order().unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "workspace members form a dependency cycle: `first` -> `second` -> `first`"
+    );
+}
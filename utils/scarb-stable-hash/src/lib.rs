@@ -1,4 +1,5 @@
 use std::hash::{Hash, Hasher};
+use std::path::Path;
 
 use data_encoding::BASE32_DNSSEC;
 use xxhash_rust::xxh3::Xxh3;
@@ -37,13 +38,64 @@ pub fn short_hash(hashable: impl Hash) -> String {
     hasher.finish_as_short_hash()
 }
 
+/// Compute a short hash of a path, which is the same across platforms.
+///
+/// Backslashes are treated as path separators, so equivalent paths produce identical hashes
+/// regardless of whether they have been written with `/` or `\` separators.
+pub fn stable_hash_path(path: impl AsRef<Path>) -> String {
+    let path = path.as_ref().to_string_lossy().replace('\\', "/");
+    short_hash(path)
+}
+
+/// Compute a short hash of manifest file contents, which is the same across platforms.
+///
+/// Line endings are normalized, and trailing whitespace is ignored, so that checkouts of
+/// the same manifest on different platforms produce identical hashes.
+pub fn stable_hash_manifest(contents: &str) -> String {
+    let contents = contents
+        .lines()
+        .map(str::trim_end)
+        .collect::<Vec<_>>()
+        .join("\n");
+    short_hash(contents.trim_end())
+}
+
 #[cfg(test)]
 mod tests {
-    use super::short_hash;
+    use super::{short_hash, stable_hash_manifest, stable_hash_path};
 
     #[test]
     fn short_hash_is_stable() {
         assert_eq!(short_hash("abcd"), "e1p6jp2ak1nmk");
         assert_eq!(short_hash(123), "8fupdqgl2ulsq");
     }
+
+    #[test]
+    fn path_hash_is_stable_across_separators() {
+        assert_eq!(
+            stable_hash_path("src/foo/lib.cairo"),
+            stable_hash_path("src\\foo\\lib.cairo")
+        );
+        assert_eq!(
+            stable_hash_path("src/foo/lib.cairo"),
+            short_hash("src/foo/lib.cairo")
+        );
+        assert_ne!(
+            stable_hash_path("src/foo/lib.cairo"),
+            stable_hash_path("src/bar/lib.cairo")
+        );
+    }
+
+    #[test]
+    fn manifest_hash_is_stable_across_line_endings() {
+        let manifest = "[package]\nname = \"hello\"\nversion = \"0.1.0\"\n";
+        assert_eq!(
+            stable_hash_manifest(manifest),
+            stable_hash_manifest("[package]\r\nname = \"hello\"  \r\nversion = \"0.1.0\"\r\n\r\n")
+        );
+        assert_ne!(
+            stable_hash_manifest(manifest),
+            stable_hash_manifest("[package]\nname = \"hello\"\nversion = \"0.2.0\"\n")
+        );
+    }
 }
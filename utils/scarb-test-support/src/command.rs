@@ -14,6 +14,7 @@ use scarb::core::Config;
 use scarb_ui::Verbosity;
 
 use crate::fsx::{AssertFsUtf8Ext, PathUtf8Ext};
+use crate::json_output::JsonOutput;
 
 pub struct Scarb {
     cache: EnvPath,
@@ -45,6 +46,13 @@ impl Scarb {
         Self::new().snapbox()
     }
 
+    /// Like [`Scarb::quick_snapbox`], but with JSON-NL output enabled.
+    ///
+    /// Use [`CommandExt::json_output`] to parse the output.
+    pub fn quick_json_snapbox() -> SnapboxCommand {
+        Self::quick_snapbox().arg("--json")
+    }
+
     pub fn snapbox(self) -> SnapboxCommand {
         SnapboxCommand::from_std(self.std())
     }
@@ -55,6 +63,8 @@ impl Scarb {
         cmd.env("SCARB_CACHE", self.cache.path());
         cmd.env("SCARB_CONFIG", self.config.path());
         cmd.env("SCARB_INIT_TEST_RUNNER", "cairo-test");
+        // Keep package archives stable, regardless of the environment tests are run in.
+        cmd.env_remove("SOURCE_DATE_EPOCH");
         cmd
     }
 
@@ -119,6 +129,11 @@ impl EnvPath {
 
 pub trait CommandExt {
     fn stdout_json<T: DeserializeOwned>(self) -> T;
+
+    /// Run the command and parse each line of its standard output as a JSON message.
+    ///
+    /// This does not check the exit status of the command.
+    fn json_output(self) -> JsonOutput;
 }
 
 impl CommandExt for SnapboxCommand {
@@ -139,4 +154,9 @@ impl CommandExt for SnapboxCommand {
         // help: make sure that the command outputs NDJSON (`--json` flag).
         panic!("Failed to deserialize stdout to JSON");
     }
+
+    fn json_output(self) -> JsonOutput {
+        let output = self.output().expect("Failed to spawn command");
+        JsonOutput::parse(&output.stdout)
+    }
 }
@@ -0,0 +1,81 @@
+use std::fmt;
+use std::io::BufRead;
+
+use serde_json::Value;
+
+/// Messages printed to standard output by a command run with `--json` flag, one per line.
+pub struct JsonOutput {
+    messages: Vec<Value>,
+}
+
+impl JsonOutput {
+    /// Parse each non-empty line of the output as a JSON value.
+    ///
+    /// Panics if any line is not valid JSON.
+    pub fn parse(stdout: &[u8]) -> Self {
+        let messages = BufRead::lines(stdout)
+            .map(|line| line.expect("Failed to read line from stdout"))
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| {
+                serde_json::from_str(&line)
+                    .unwrap_or_else(|err| panic!("Line is not valid JSON ({err}): {line}"))
+            })
+            .collect();
+        Self { messages }
+    }
+
+    pub fn messages(&self) -> &[Value] {
+        &self.messages
+    }
+
+    /// Iterate over messages with the given `type` field, like `warn` or `error`.
+    pub fn of_type<'a>(&'a self, ty: &'a str) -> impl Iterator<Item = &'a Value> + 'a {
+        self.messages.iter().filter(move |m| m["type"] == ty)
+    }
+
+    /// Iterate over status messages with the given `status` field, like `compiling`.
+    pub fn of_status<'a>(&'a self, status: &'a str) -> impl Iterator<Item = &'a Value> + 'a {
+        self.messages.iter().filter(move |m| m["status"] == status)
+    }
+
+    /// Assert that there is a message of the given type, whose text contains the given fragment.
+    #[track_caller]
+    pub fn assert_contains_message(&self, ty: &str, fragment: &str) -> &Self {
+        assert!(
+            self.of_type(ty).any(|m| m["message"]
+                .as_str()
+                .is_some_and(|message| message.contains(fragment))),
+            "No `{ty}` message containing `{fragment}` in output:\n{self}"
+        );
+        self
+    }
+
+    /// Assert that there is a message of the given type with the given code.
+    #[track_caller]
+    pub fn assert_contains_code(&self, ty: &str, code: &str) -> &Self {
+        assert!(
+            self.of_type(ty).any(|m| m["code"] == code),
+            "No `{ty}` message with code `{code}` in output:\n{self}"
+        );
+        self
+    }
+
+    /// Assert that there is a status message with the given status and message text.
+    #[track_caller]
+    pub fn assert_contains_status(&self, status: &str, message: &str) -> &Self {
+        assert!(
+            self.of_status(status).any(|m| m["message"] == message),
+            "No `{status}` status with message `{message}` in output:\n{self}"
+        );
+        self
+    }
+}
+
+impl fmt::Display for JsonOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for message in &self.messages {
+            writeln!(f, "{message}")?;
+        }
+        Ok(())
+    }
+}
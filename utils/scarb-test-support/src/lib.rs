@@ -5,6 +5,7 @@ pub mod contracts;
 pub mod filesystem;
 pub mod fsx;
 pub mod gitx;
+pub mod json_output;
 pub mod manifest_edit;
 pub mod predicates;
 pub mod proc_macro_server;
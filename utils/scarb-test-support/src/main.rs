@@ -5,6 +5,8 @@ use std::{env, io};
 use anyhow::Result;
 use clap::{Parser, Subcommand};
 use scarb_test_support::simple_http_server::SimpleHttpServer;
+use scarb_ui::components::Status;
+use scarb_ui::{JsonDiagnosticsStream, OutputFormat, Ui, Verbosity};
 
 #[derive(Parser, Clone, Debug)]
 struct Args {
@@ -17,6 +19,7 @@ struct Args {
 pub enum Command {
     HangOnTcp(HangOnTcpArgs),
     HttpServer,
+    EmitJson(EmitJsonArgs),
 }
 
 #[derive(Parser, Clone, Debug)]
@@ -25,11 +28,19 @@ pub struct HangOnTcpArgs {
     address: String,
 }
 
+#[derive(Parser, Clone, Debug)]
+pub struct EmitJsonArgs {
+    /// Print warnings and errors to standard error.
+    #[arg(long)]
+    diagnostics_to_stderr: bool,
+}
+
 fn main() -> Result<()> {
     let args: Args = Args::parse();
     match args.command {
         Command::HangOnTcp(args) => hang_on_tcp(args),
         Command::HttpServer => http_server(),
+        Command::EmitJson(args) => emit_json(args),
     }
 }
 
@@ -51,3 +62,17 @@ async fn http_server() -> Result<()> {
     drop(http);
     Ok(())
 }
+
+/// Print a known set of messages in JSON-NL format.
+fn emit_json(args: EmitJsonArgs) -> Result<()> {
+    let json_diagnostics = if args.diagnostics_to_stderr {
+        JsonDiagnosticsStream::Stderr
+    } else {
+        JsonDiagnosticsStream::Stdout
+    };
+    let ui = Ui::new(Verbosity::Normal, OutputFormat::Json).with_json_diagnostics(json_diagnostics);
+    ui.print(Status::new("Compiling", "hello v1.0.0"));
+    ui.warn_with_code("E0001", "Unused variable.");
+    ui.error("could not compile `hello`");
+    Ok(())
+}
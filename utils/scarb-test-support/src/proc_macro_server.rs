@@ -8,13 +8,15 @@ use std::collections::HashMap;
 use std::io::BufRead;
 use std::io::BufReader;
 use std::io::Lines;
+use std::io::Read;
 use std::io::Write;
 use std::marker::PhantomData;
+use std::net::TcpListener;
+use std::net::TcpStream;
 use std::path::Path;
 use std::process::Child;
-use std::process::ChildStdin;
-use std::process::ChildStdout;
 use std::process::Stdio;
+use std::thread;
 
 pub const SIMPLE_MACROS: &str = r#"
 use cairo_lang_macro::{
@@ -59,9 +61,9 @@ impl<M: Method> PendingRequest<M> {
 }
 
 pub struct ProcMacroClient {
-    requester: ChildStdin,
-    responder: Lines<BufReader<ChildStdout>>,
-    server_process: Child,
+    requester: Box<dyn Write>,
+    responder: Lines<BufReader<Box<dyn Read>>>,
+    server_process: Option<Child>,
     id_counter: RequestId,
     responses: HashMap<RequestId, RpcResponse>,
 }
@@ -80,16 +82,10 @@ impl ProcMacroClient {
             .unwrap();
 
         let requester = server_process.stdin.take().unwrap();
-        let responder = BufReader::new(server_process.stdout.take().unwrap()).lines();
-
-        Self {
-            requester,
-            responder,
-            server_process,
-            id_counter: Default::default(),
-            responses: Default::default(),
-        }
+        let responder = server_process.stdout.take().unwrap();
+        Self::from_streams(requester, responder, Some(server_process))
     }
+
     pub fn new_without_cargo<P: AsRef<Path>>(path: P) -> Self {
         let mut server_process = Scarb::new()
             .std()
@@ -105,11 +101,43 @@ impl ProcMacroClient {
             .unwrap();
 
         let requester = server_process.stdin.take().unwrap();
-        let responder = BufReader::new(server_process.stdout.take().unwrap()).lines();
+        let responder = server_process.stdout.take().unwrap();
+        Self::from_streams(requester, responder, Some(server_process))
+    }
 
+    /// Connect to a stub server, which answers each request with the given handler.
+    ///
+    /// This does not spawn Scarb, and is meant for testing the client itself.
+    /// Use [`respond`] to implement the handler with typed parameters and responses.
+    pub fn stub(mut handler: impl FnMut(RpcRequest) -> RpcResponse + Send + 'static) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut writer = stream.try_clone().unwrap();
+            for line in BufReader::new(stream).lines() {
+                let Ok(line) = line else { break };
+                let request: RpcRequest = serde_json::from_str(&line).unwrap();
+                let mut response = serde_json::to_vec(&handler(request)).unwrap();
+                response.push(b'\n');
+                if writer.write_all(&response).is_err() {
+                    break;
+                }
+            }
+        });
+
+        let stream = TcpStream::connect(addr).unwrap();
+        Self::from_streams(stream.try_clone().unwrap(), stream, None)
+    }
+
+    fn from_streams(
+        requester: impl Write + 'static,
+        responder: impl Read + 'static,
+        server_process: Option<Child>,
+    ) -> Self {
         Self {
-            requester,
-            responder,
+            requester: Box::new(requester),
+            responder: BufReader::new(Box::new(responder) as Box<dyn Read>).lines(),
             server_process,
             id_counter: Default::default(),
             responses: Default::default(),
@@ -143,6 +171,26 @@ impl ProcMacroClient {
         self.wait_for_response::<M>(request)
     }
 
+    /// Send a request and wait for its response, panicking if the server responds with an error.
+    #[track_caller]
+    pub fn request_ok<M: Method>(&mut self, params: M::Params) -> M::Response {
+        self.request_and_wait::<M>(params)
+            .unwrap_or_else(|err| panic!("`{}` request failed: {}", M::METHOD, err.message))
+    }
+
+    /// Send a request and wait for its response, panicking if the server does not respond with
+    /// an error.
+    #[track_caller]
+    pub fn request_err<M: Method>(&mut self, params: M::Params) -> ResponseError {
+        match self.request_and_wait::<M>(params) {
+            Ok(_) => panic!(
+                "`{}` request succeeded, but was expected to fail",
+                M::METHOD
+            ),
+            Err(err) => err,
+        }
+    }
+
     pub fn wait_for_response<M: Method>(
         &mut self,
         request: PendingRequest<M>,
@@ -172,7 +220,29 @@ impl ProcMacroClient {
 
 impl Drop for ProcMacroClient {
     fn drop(&mut self) {
-        self.server_process.kill().unwrap();
-        self.server_process.wait().unwrap();
+        if let Some(server_process) = &mut self.server_process {
+            server_process.kill().unwrap();
+            server_process.wait().unwrap();
+        }
+    }
+}
+
+/// Answer a request of method `M` received by a [`ProcMacroClient::stub`] server.
+///
+/// Panics if the request is of a different method.
+pub fn respond<M: Method>(
+    request: RpcRequest,
+    handler: impl FnOnce(M::Params) -> Result<M::Response, ResponseError>,
+) -> RpcResponse {
+    assert_eq!(request.method, M::METHOD, "unexpected request method");
+    let params = serde_json::from_value(request.value).unwrap();
+    let (result, error) = match handler(params) {
+        Ok(response) => (Some(serde_json::to_value(response).unwrap()), None),
+        Err(error) => (None, Some(error)),
+    };
+    RpcResponse {
+        id: request.id,
+        result,
+        error,
     }
 }
@@ -1,6 +1,7 @@
 use assert_fs::fixture::ChildPath;
 use assert_fs::prelude::*;
 use assert_fs::TempDir;
+use axum::http::{HeaderName, Method};
 use serde_json::json;
 use std::fmt;
 use std::path::Path;
@@ -61,6 +62,38 @@ impl HttpRegistry {
         self
     }
 
+    /// Overwrite the archive of a published package with garbage, so that downloading it fails
+    /// checksum verification.
+    pub fn corrupt_archive(&mut self, name: &str, version: &str) -> &mut Self {
+        self.local.corrupt_archive(name, version);
+        self
+    }
+
+    /// Reject package uploads which do not send exactly this `Authorization` header value,
+    /// for example `Bearer <token>`.
+    pub fn require_authorization(&mut self, value: impl Into<String>) -> &mut Self {
+        self.server.require_authorization(Some(value.into()));
+        self
+    }
+
+    /// Assert that every request with the given method and path sent the given header value,
+    /// and that there was at least one such request.
+    #[track_caller]
+    pub fn assert_sent_header(&self, method: Method, path: &str, name: HeaderName, value: &str) {
+        let headers = {
+            let _guard = RUNTIME.enter();
+            RUNTIME.block_on(async { self.server.request_headers(&method, path).await })
+        };
+        assert!(!headers.is_empty(), "no {method} {path} requests were made");
+        for headers in headers {
+            assert_eq!(
+                headers.get(&name).and_then(|v| v.to_str().ok()),
+                Some(value),
+                "{method} {path} request sent unexpected `{name}` header"
+            );
+        }
+    }
+
     /// Enable this when writing tests to see what requests are being made in the test.
     pub fn print_logs(&self) {
         self.server.print_logs(true);
@@ -1,5 +1,6 @@
 use std::fmt;
 
+use assert_fs::prelude::*;
 use assert_fs::TempDir;
 use url::Url;
 
@@ -45,6 +46,22 @@ impl LocalRegistry {
     }
 }
 
+impl LocalRegistry {
+    /// Overwrite the archive of a published package with garbage, so that downloading it fails
+    /// checksum verification.
+    pub fn corrupt_archive(&self, name: &str, version: &str) -> &Self {
+        self.t
+            .child(format!("{name}-{version}.tar.zst"))
+            .write_str(
+                "This is a sequence of bytes that is definitely not a valid tar nor zst. \
+                This way, we verify that Scarb is not even attempting to read/interpret \
+                archives before verifying checksums.",
+            )
+            .unwrap();
+        self
+    }
+}
+
 impl fmt::Display for LocalRegistry {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         fmt::Display::fmt(&self.url, f)
@@ -7,7 +7,7 @@ use std::sync::Arc;
 
 use axum::body::Body;
 use axum::extract::State;
-use axum::http::header::{ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH};
+use axum::http::header::{AUTHORIZATION, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH};
 use axum::http::Method;
 use axum::http::Request;
 use axum::http::StatusCode;
@@ -31,10 +31,14 @@ type LogsStore = Arc<Mutex<BTreeMap<u32, HttpLog>>>;
 
 type LoggerState = (LogsStore, Arc<AtomicBool>);
 
+/// Value of the `Authorization` header required from package upload requests, if any.
+type RequiredAuthorization = Arc<std::sync::Mutex<Option<String>>>;
+
 pub struct SimpleHttpServer {
     addr: SocketAddr,
     print_logs: Arc<AtomicBool>,
     logs: LogsStore,
+    required_authorization: RequiredAuthorization,
     ct: Option<tokio::sync::oneshot::Sender<()>>,
 }
 
@@ -58,12 +62,22 @@ impl SimpleHttpServer {
 
         let print_logs = Arc::new(AtomicBool::new(false));
         let logs: LogsStore = Default::default();
+        let required_authorization: RequiredAuthorization = Default::default();
 
         let app = Router::new()
             .fallback_service(ServeDir::new(dir))
             .route(
                 "/api/v1/packages/new",
-                post(move || post_handler(post_response.clone())),
+                post({
+                    let required_authorization = required_authorization.clone();
+                    move |headers: HeaderMap| {
+                        post_handler(
+                            headers,
+                            required_authorization.clone(),
+                            post_response.clone(),
+                        )
+                    }
+                }),
             )
             .layer(middleware::from_fn(set_etag))
             .layer(middleware::from_fn_with_state(
@@ -89,6 +103,7 @@ impl SimpleHttpServer {
             addr,
             print_logs,
             logs,
+            required_authorization,
             ct: Some(ct),
         }
     }
@@ -106,6 +121,22 @@ impl SimpleHttpServer {
         let logs = self.logs.lock().await;
         logs.values().map(ToString::to_string).join("\n###\n\n")
     }
+
+    /// Reject package uploads which do not send exactly this `Authorization` header value.
+    ///
+    /// Pass `None` to accept all uploads again.
+    pub fn require_authorization(&self, value: Option<String>) {
+        *self.required_authorization.lock().unwrap() = value;
+    }
+
+    /// Headers of all requests made so far with the given method and path, in request order.
+    pub async fn request_headers(&self, method: &Method, path: &str) -> Vec<HeaderMap> {
+        let logs = self.logs.lock().await;
+        logs.values()
+            .filter(|log| log.req_method == method && log.req_uri == path)
+            .map(|log| log.req_headers.clone())
+            .collect()
+    }
 }
 
 impl Drop for SimpleHttpServer {
@@ -114,8 +145,22 @@ impl Drop for SimpleHttpServer {
     }
 }
 
-async fn post_handler(post_response: Option<HttpPostResponse>) -> impl IntoResponse {
+async fn post_handler(
+    headers: HeaderMap,
+    required_authorization: RequiredAuthorization,
+    post_response: Option<HttpPostResponse>,
+) -> impl IntoResponse {
+    let required_authorization = required_authorization.lock().unwrap().clone();
+    let is_authorized = required_authorization.is_none_or(|required| {
+        headers
+            .get(AUTHORIZATION)
+            .is_some_and(|value| value.as_bytes() == required.as_bytes())
+    });
     let (status_code, message) = match post_response {
+        _ if !is_authorized => (
+            StatusCode::UNAUTHORIZED,
+            "invalid authentication token".to_string(),
+        ),
         Some(response) => (
             StatusCode::from_u16(response.code).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR),
             response.message,
@@ -0,0 +1,72 @@
+use snapbox::cmd::Command;
+
+use scarb_test_support::cargo::cargo_bin;
+use scarb_test_support::command::CommandExt;
+use scarb_test_support::json_output::JsonOutput;
+
+fn emit_json() -> JsonOutput {
+    Command::new(cargo_bin("scarb-test-support"))
+        .arg("emit-json")
+        .json_output()
+}
+
+#[test]
+fn parses_each_line() {
+    let output = emit_json();
+    assert_eq!(output.messages().len(), 3);
+    assert_eq!(output.of_type("warn").count(), 1);
+    assert_eq!(output.of_status("compiling").count(), 1);
+}
+
+#[test]
+fn matches_messages() {
+    emit_json()
+        .assert_contains_status("compiling", "hello v1.0.0")
+        .assert_contains_message("warn", "Unused variable")
+        .assert_contains_code("warn", "E0001")
+        .assert_contains_message("error", "could not compile");
+}
+
+#[test]
+#[should_panic(expected = "No `error` message with code `E0001`")]
+fn fails_on_missing_code() {
+    emit_json().assert_contains_code("error", "E0001");
+}
+
+#[test]
+#[should_panic(expected = "Line is not valid JSON")]
+fn fails_on_non_json_line() {
+    JsonOutput::parse(b"{\"type\":\"warn\"}\nnot json\n");
+}
+
+#[test]
+fn diagnostics_to_stderr() {
+    let output = Command::new(cargo_bin("scarb-test-support"))
+        .args(["emit-json", "--diagnostics-to-stderr"])
+        .output()
+        .expect("Failed to spawn command");
+
+    let stdout = JsonOutput::parse(&output.stdout);
+    assert_eq!(stdout.messages().len(), 1);
+    stdout.assert_contains_status("compiling", "hello v1.0.0");
+
+    let stderr = JsonOutput::parse(&output.stderr);
+    assert_eq!(stderr.messages().len(), 2);
+    stderr
+        .assert_contains_code("warn", "E0001")
+        .assert_contains_message("error", "could not compile");
+    assert_eq!(
+        stderr.of_type("warn").next().unwrap()["severity"],
+        "warning"
+    );
+    assert_eq!(stderr.of_type("error").next().unwrap()["severity"], "error");
+}
+
+#[test]
+fn diagnostics_on_stdout_have_no_severity() {
+    let output = emit_json();
+    assert!(output
+        .messages()
+        .iter()
+        .all(|m| m.get("severity").is_none()));
+}
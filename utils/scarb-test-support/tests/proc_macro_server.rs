@@ -0,0 +1,74 @@
+use scarb_proc_macro_server_types::jsonrpc::ResponseError;
+use scarb_proc_macro_server_types::methods::defined_macros::{
+    DefinedMacros, DefinedMacrosParams, DefinedMacrosResponse,
+};
+use scarb_test_support::proc_macro_server::{respond, ProcMacroClient};
+
+fn defined_macros() -> DefinedMacrosResponse {
+    DefinedMacrosResponse {
+        attributes: vec!["some".to_string()],
+        inline_macros: vec!["inline_some".to_string()],
+        derives: vec!["some_derive".to_string()],
+        executables: vec![],
+    }
+}
+
+#[test]
+fn round_trips_typed_request() {
+    let mut client = ProcMacroClient::stub(|request| {
+        respond::<DefinedMacros>(request, |_params| Ok(defined_macros()))
+    });
+
+    let response = client.request_ok::<DefinedMacros>(DefinedMacrosParams {});
+    assert_eq!(response.attributes, vec!["some".to_string()]);
+    assert_eq!(response.inline_macros, vec!["inline_some".to_string()]);
+    assert_eq!(response.derives, vec!["some_derive".to_string()]);
+    assert!(response.executables.is_empty());
+}
+
+#[test]
+fn round_trips_error_response() {
+    let mut client = ProcMacroClient::stub(|request| {
+        respond::<DefinedMacros>(request, |_params| {
+            Err(ResponseError {
+                message: "no macros".to_string(),
+            })
+        })
+    });
+
+    let error = client.request_err::<DefinedMacros>(DefinedMacrosParams {});
+    assert_eq!(error.message, "no macros");
+}
+
+#[test]
+fn matches_responses_to_pending_requests() {
+    let mut client = ProcMacroClient::stub(|request| {
+        let id = request.id;
+        respond::<DefinedMacros>(request, |_params| {
+            let mut response = defined_macros();
+            response.executables = vec![format!("executable_{id}")];
+            Ok(response)
+        })
+    });
+
+    let first = client.request::<DefinedMacros>(DefinedMacrosParams {});
+    let second = client.request::<DefinedMacros>(DefinedMacrosParams {});
+    let second = client.wait_for_response(second).unwrap();
+    let first = client.wait_for_response(first).unwrap();
+    assert_eq!(first.executables, vec!["executable_0".to_string()]);
+    assert_eq!(second.executables, vec!["executable_1".to_string()]);
+}
+
+#[test]
+#[should_panic(expected = "`definedMacros` request failed: no macros")]
+fn request_ok_panics_on_error_response() {
+    let mut client = ProcMacroClient::stub(|request| {
+        respond::<DefinedMacros>(request, |_params| {
+            Err(ResponseError {
+                message: "no macros".to_string(),
+            })
+        })
+    });
+
+    client.request_ok::<DefinedMacros>(DefinedMacrosParams {});
+}
@@ -0,0 +1,114 @@
+use std::time::Duration;
+
+use assert_fs::TempDir;
+use axum::http::header::AUTHORIZATION;
+use axum::http::Method;
+use indoc::indoc;
+
+use scarb_test_support::command::Scarb;
+use scarb_test_support::project_builder::{Dep, DepBuilder, ProjectBuilder};
+use scarb_test_support::registry::http::HttpRegistry;
+use scarb_test_support::simple_http_server::HttpPostResponse;
+
+fn publish(registry: &HttpRegistry, token: &str) -> snapbox::cmd::OutputAssert {
+    let t = TempDir::new().unwrap();
+    ProjectBuilder::start()
+        .name("bar")
+        .version("1.0.0")
+        .build(&t);
+    Scarb::quick_snapbox()
+        .arg("publish")
+        .arg("--index")
+        .arg(&registry.url)
+        .arg("--no-verify")
+        .env("SCARB_REGISTRY_AUTH_TOKEN", token)
+        .current_dir(&t)
+        .timeout(Duration::from_secs(60))
+        .assert()
+}
+
+#[test]
+fn required_authorization_accepts_matching_header() {
+    let mut registry = HttpRegistry::serve(Some(HttpPostResponse {
+        code: 200,
+        message: "published".to_string(),
+    }));
+    registry.require_authorization("Bearer scrb_secret");
+
+    publish(&registry, "scrb_secret").success();
+
+    registry.assert_sent_header(
+        Method::POST,
+        "/api/v1/packages/new",
+        AUTHORIZATION,
+        "Bearer scrb_secret",
+    );
+}
+
+#[test]
+fn required_authorization_rejects_other_header() {
+    let mut registry = HttpRegistry::serve(Some(HttpPostResponse {
+        code: 200,
+        message: "published".to_string(),
+    }));
+    registry.require_authorization("Bearer scrb_secret");
+
+    publish(&registry, "scrb_other")
+        .failure()
+        .stdout_matches(indoc! {r#"
+        ...
+        error: upload failed with status code: `401 Unauthorized`, `invalid authentication token`
+        "#});
+}
+
+#[test]
+#[should_panic(expected = "request sent unexpected `authorization` header")]
+fn assert_sent_header_detects_mismatch() {
+    let registry = HttpRegistry::serve(Some(HttpPostResponse {
+        code: 200,
+        message: "published".to_string(),
+    }));
+
+    publish(&registry, "scrb_secret").success();
+
+    registry.assert_sent_header(
+        Method::POST,
+        "/api/v1/packages/new",
+        AUTHORIZATION,
+        "Bearer scrb_other",
+    );
+}
+
+#[test]
+fn corrupted_archive_fails_checksum_verification() {
+    let mut registry = HttpRegistry::serve(None);
+    registry.publish(|t| {
+        ProjectBuilder::start()
+            .name("bar")
+            .version("1.0.0")
+            .lib_cairo(r#"fn f() -> felt252 { 0 }"#)
+            .build(t);
+    });
+    registry.corrupt_archive("bar", "1.0.0");
+
+    let t = TempDir::new().unwrap();
+    ProjectBuilder::start()
+        .name("foo")
+        .version("0.1.0")
+        .dep("bar", Dep.version("1").registry(&registry))
+        .build(&t);
+
+    Scarb::quick_snapbox()
+        .arg("fetch")
+        .current_dir(&t)
+        .timeout(Duration::from_secs(10))
+        .assert()
+        .failure()
+        .stdout_matches(indoc! {r#"
+        ...
+        error: failed to download package: bar v1.0.0 (registry+http://[..])
+
+        Caused by:
+            failed to verify the checksum of downloaded archive
+        "#});
+}
@@ -0,0 +1,111 @@
+use clap::ValueEnum;
+
+use crate::Ui;
+
+/// When to use colors in output.
+#[derive(ValueEnum, Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum ColorChoice {
+    /// Detect color support from the terminal and `CLICOLOR`/`CLICOLOR_FORCE` env vars.
+    #[default]
+    Auto,
+    /// Always use colors.
+    Always,
+    /// Never use colors.
+    Never,
+}
+
+impl ColorChoice {
+    /// Whether colors are forced on or off, or `None` if they should be detected.
+    pub fn enabled(self) -> Option<bool> {
+        match self {
+            Self::Auto => None,
+            Self::Always => Some(true),
+            Self::Never => Some(false),
+        }
+    }
+}
+
+/// [`clap`] structured arguments that provide Scarb UI color selection.
+#[derive(clap::Args, Debug, Clone, Default)]
+#[command(about = None, long_about = None)]
+pub struct ColorSpec {
+    /// Coloring of the output.
+    #[arg(
+        long,
+        value_enum,
+        default_value_t,
+        global = true,
+        value_name = "WHEN",
+        env = "SCARB_COLOR"
+    )]
+    pub color: ColorChoice,
+}
+
+impl ColorSpec {
+    /// Force colorization of stdout and stderr of the given [`Ui`] according to this
+    /// specification.
+    ///
+    /// Does nothing for [`ColorChoice::Auto`], leaving default detection in place.
+    pub fn apply(&self, ui: &Ui) {
+        if let Some(enable) = self.color.enabled() {
+            ui.force_colors_enabled(enable);
+            ui.force_colors_enabled_stderr(enable);
+        }
+    }
+}
+
+impl super::ToEnvVars for ColorSpec {
+    fn to_env_vars(self) -> Vec<(String, String)> {
+        let value = self
+            .color
+            .to_possible_value()
+            .expect("color choice must have a value")
+            .get_name()
+            .to_string();
+        vec![("SCARB_COLOR".to_string(), value)]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::args::{ColorChoice, ColorSpec, ToEnvVars};
+    use crate::{OutputFormat, Ui, Verbosity};
+
+    // NOTE: Color state is global for the process, so all cases are checked sequentially
+    //   in a single test.
+    #[test]
+    fn color_choice_sets_color_state() {
+        let ui = Ui::new(Verbosity::Normal, OutputFormat::Text);
+        let apply = |color| ColorSpec { color }.apply(&ui);
+
+        apply(ColorChoice::Always);
+        assert!(ui.has_colors_enabled());
+        assert!(ui.has_colors_enabled_stderr());
+
+        apply(ColorChoice::Auto);
+        assert!(ui.has_colors_enabled());
+        assert!(ui.has_colors_enabled_stderr());
+
+        apply(ColorChoice::Never);
+        assert!(!ui.has_colors_enabled());
+        assert!(!ui.has_colors_enabled_stderr());
+
+        apply(ColorChoice::Auto);
+        assert!(!ui.has_colors_enabled());
+        assert!(!ui.has_colors_enabled_stderr());
+    }
+
+    #[test]
+    fn color_spec_env_vars() {
+        for (color, value) in [
+            (ColorChoice::Auto, "auto"),
+            (ColorChoice::Always, "always"),
+            (ColorChoice::Never, "never"),
+        ] {
+            assert_eq!(
+                ColorSpec { color }.to_env_vars(),
+                vec![("SCARB_COLOR".to_string(), value.to_string())]
+            );
+        }
+    }
+}
@@ -1,9 +1,11 @@
 //! This module provides reusable [`clap`] arguments for common tasks in Scarb ecosystem.
 
+pub use color::*;
 pub use features::*;
 pub use packages_filter::*;
 pub use verbosity::*;
 
+mod color;
 mod features;
 mod packages_filter;
 mod verbosity;
@@ -23,8 +23,9 @@ const PACKAGES_FILTER_DELIMITER: char = ',';
 /// ```
 #[derive(clap::Parser, Clone, Debug)]
 pub struct PackagesFilter {
-    /// Packages to run this command on, can be a concrete package name (`foobar`) or
-    /// a prefix glob (`foo*`).
+    /// Packages to run this command on, can be a concrete package name (`foobar`),
+    /// a name with version (`foobar@1.0.0`), a package ID (`foobar 1.0.0 (registry+...)`),
+    /// or a prefix glob (`foo*`).
     #[arg(
         short,
         long,
@@ -54,8 +55,9 @@ pub struct PackagesFilter {
 /// ```
 #[derive(clap::Parser, Clone, Debug)]
 pub struct PackagesFilterLong {
-    /// Packages to run this command on, can be a concrete package name (`foobar`) or
-    /// a prefix glob (`foo*`).
+    /// Packages to run this command on, can be a concrete package name (`foobar`),
+    /// a name with version (`foobar@1.0.0`), a package ID (`foobar 1.0.0 (registry+...)`),
+    /// or a prefix glob (`foo*`).
     #[arg(
         long,
         default_value = "*",
@@ -129,6 +131,11 @@ impl PackagesFilter {
         Self::do_match_all::<S>(specs, self.workspace, members)
     }
 
+    /// Whether all packages in the workspace have been explicitly requested with `--workspace`.
+    pub fn is_workspace(&self) -> bool {
+        self.workspace
+    }
+
     /// Generate a new [`PackagesFilter`] for the given slice  of packages.
     ///
     /// This is useful when you want to build an env filter from matched packages.
@@ -195,7 +202,7 @@ impl PackagesFilter {
                 members.clone().into_iter(),
             )?);
         }
-        packages.dedup_by_key(|p| S::package_name_of(p).to_string());
+        packages.dedup_by_key(|p| S::package_id_of(p));
         Ok(packages)
     }
 
@@ -212,13 +219,15 @@ impl PackagesFilter {
             members.collect::<Vec<_>>()
         } else {
             members
-                .filter(|pkg| spec.matches(S::package_name_of(pkg)))
+                .filter(|pkg| spec.matches::<S>(pkg))
                 .collect::<Vec<_>>()
         };
 
         if matches.is_empty() {
             match spec {
-                Spec::One(package_name) => bail!("package `{package_name}` not found in workspace"),
+                Spec::One(_) | Spec::Version(..) | Spec::Id(_) => {
+                    bail!("package `{spec}` not found in workspace")
+                }
                 Spec::All | Spec::Glob(_) => bail!("no workspace members match `{spec}`"),
             }
         }
@@ -248,12 +257,28 @@ enum Spec<'a> {
     All,
     One(&'a str),
     Glob(&'a str),
+    /// Package name with exact version, `name@version`.
+    Version(&'a str, &'a str),
+    /// Serialized package ID, `name version (source)`.
+    Id(&'a str),
 }
 
 impl<'a> Spec<'a> {
     fn parse(string: &'a str) -> Result<Self> {
         let string = string.trim();
 
+        if string.contains(" (") && string.ends_with(')') {
+            return Ok(Self::Id(string));
+        }
+
+        if let Some((name, version)) = string.split_once('@') {
+            ensure!(
+                !name.is_empty() && !version.is_empty() && !string.contains('*'),
+                "invalid package spec: expected `name@version`, got `{string}`"
+            );
+            return Ok(Self::Version(name, version));
+        }
+
         if !string.contains('*') {
             return Ok(Self::One(string));
         }
@@ -276,11 +301,16 @@ impl<'a> Spec<'a> {
         }
     }
 
-    fn matches(&self, name: &str) -> bool {
+    fn matches<S: PackagesSource>(&self, package: &S::Package) -> bool {
+        let name = S::package_name_of(package);
         match self {
             Spec::All => true,
             Spec::One(pat) => name == *pat,
             Spec::Glob(pat) => name.starts_with(pat),
+            Spec::Version(pat, version) => {
+                name == *pat && S::package_version_of(package) == *version
+            }
+            Spec::Id(id) => S::package_id_of(package) == *id,
         }
     }
 }
@@ -291,6 +321,8 @@ impl fmt::Display for Spec<'_> {
             Spec::All => write!(f, "*"),
             Spec::One(name) => write!(f, "{name}"),
             Spec::Glob(pat) => write!(f, "{pat}*"),
+            Spec::Version(name, version) => write!(f, "{name}@{version}"),
+            Spec::Id(id) => write!(f, "{id}"),
         }
     }
 }
@@ -321,6 +353,12 @@ pub trait PackagesSource {
     #[doc(hidden)]
     fn package_name_of(package: &Self::Package) -> &str;
 
+    #[doc(hidden)]
+    fn package_version_of(package: &Self::Package) -> String;
+
+    #[doc(hidden)]
+    fn package_id_of(package: &Self::Package) -> String;
+
     #[doc(hidden)]
     fn members(&self) -> Vec<Self::Package>;
 
@@ -336,6 +374,14 @@ impl PackagesSource for Metadata {
         &package.name
     }
 
+    fn package_version_of(package: &Self::Package) -> String {
+        package.version.to_string()
+    }
+
+    fn package_id_of(package: &Self::Package) -> String {
+        package.id.repr.clone()
+    }
+
     #[inline(always)]
     fn members(&self) -> Vec<Self::Package> {
         self.packages
@@ -372,9 +418,11 @@ mod tests {
 
     use crate::args::{PackagesFilter, PackagesFilterLong, PackagesSource, WithManifestPath};
 
-    #[derive(Clone)]
+    #[derive(Clone, Debug)]
     struct MockPackage {
         pub name: String,
+        pub version: String,
+        pub source: String,
         pub manifest_path: Utf8PathBuf,
     }
 
@@ -410,6 +458,14 @@ mod tests {
             package.name.as_str()
         }
 
+        fn package_version_of(package: &Self::Package) -> String {
+            package.version.clone()
+        }
+
+        fn package_id_of(package: &Self::Package) -> String {
+            format!("{} {} ({})", package.name, package.version, package.source)
+        }
+
         fn members(&self) -> Vec<Self::Package> {
             self.members.clone()
         }
@@ -420,9 +476,15 @@ mod tests {
     }
 
     fn mock_package(name: &str) -> MockPackage {
+        mock_package_from(name, "1.0.0", "path")
+    }
+
+    fn mock_package_from(name: &str, version: &str, source: &str) -> MockPackage {
         MockPackage {
             name: name.into(),
-            manifest_path: Utf8PathBuf::from(format!("package/{}", name)),
+            version: version.into(),
+            source: source.into(),
+            manifest_path: Utf8PathBuf::from(format!("{source}/{name}/{version}")),
         }
     }
 
@@ -579,4 +641,80 @@ mod tests {
         let package = filter.match_one(&mock).unwrap();
         assert_eq!(package.name, "second");
     }
+
+    #[test]
+    fn can_match_by_name_and_version() {
+        let mock = MockSource::new(vec![
+            mock_package_from("first", "1.0.0", "path"),
+            mock_package_from("second", "2.0.0", "path"),
+        ]);
+
+        let filter = PackagesFilter {
+            package: vec!["second@2.0.0".into()],
+            workspace: false,
+        };
+        let package = filter.match_one(&mock).unwrap();
+        assert_eq!(package.name, "second");
+
+        let filter = PackagesFilter {
+            package: vec!["second@1.0.0".into()],
+            workspace: false,
+        };
+        let err = filter.match_one(&mock).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "package `second@1.0.0` not found in workspace"
+        );
+    }
+
+    #[test]
+    fn can_match_by_id() {
+        let mock = MockSource::new(mock_packages(vec!["first", "second"]));
+        let filter = PackagesFilter {
+            package: vec!["second 1.0.0 (path)".into()],
+            workspace: false,
+        };
+        let package = filter.match_one(&mock).unwrap();
+        assert_eq!(package.name, "second");
+    }
+
+    #[test]
+    fn ids_disambiguate_duplicate_names() {
+        let mock = MockSource::new(vec![
+            mock_package_from("dup", "1.0.0", "git"),
+            mock_package_from("dup", "1.0.0", "registry"),
+        ]);
+
+        let filter = PackagesFilter {
+            package: vec!["dup".into()],
+            workspace: false,
+        };
+        assert!(filter.match_one(&mock).is_err());
+        assert_eq!(filter.match_many(&mock).unwrap().len(), 2);
+
+        let filter = PackagesFilter {
+            package: vec!["dup@1.0.0".into()],
+            workspace: false,
+        };
+        assert!(filter.match_one(&mock).is_err());
+
+        let filter = PackagesFilter {
+            package: vec!["dup 1.0.0 (registry)".into()],
+            workspace: false,
+        };
+        let package = filter.match_one(&mock).unwrap();
+        assert_eq!(package.source, "registry");
+    }
+
+    #[test]
+    fn rejects_invalid_version_spec() {
+        let mock = MockSource::new(mock_packages(vec!["first"]));
+        for spec in ["first@", "@1.0.0", "fir*@1.0.0"] {
+            let filter = PackagesFilter {
+                package: vec![spec.into()],
+                workspace: false,
+            };
+            assert!(filter.match_many(&mock).is_err(), "{spec}");
+        }
+    }
 }
@@ -0,0 +1,81 @@
+use serde::{Serialize, Serializer};
+use serde_json::Value;
+
+use crate::Message;
+
+type TextRenderer<'a> = Box<dyn FnOnce(&Value) -> String + 'a>;
+
+/// Print an arbitrary JSON value.
+///
+/// In JSON mode, the value is emitted as-is.
+/// In text mode, the value is rendered with a custom text renderer, if one was provided,
+/// or pretty-printed otherwise.
+pub struct JsonValue<'a> {
+    value: Value,
+    text: Option<TextRenderer<'a>>,
+}
+
+impl<'a> JsonValue<'a> {
+    /// Create a new message emitting the given value.
+    pub fn new(value: Value) -> Self {
+        Self { value, text: None }
+    }
+
+    /// Use the given closure to render this message in text mode.
+    pub fn with_text(self, text: impl FnOnce(&Value) -> String + 'a) -> Self {
+        Self {
+            text: Some(Box::new(text)),
+            ..self
+        }
+    }
+}
+
+impl Message for JsonValue<'_> {
+    fn text(self) -> String {
+        match self.text {
+            Some(text) => text(&self.value),
+            None => serde_json::to_string_pretty(&self.value)
+                .expect("JSON value must serialize without panics"),
+        }
+    }
+
+    fn structured<S: Serializer>(self, ser: S) -> Result<S::Ok, S::Error> {
+        self.value.serialize(ser)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::JsonValue;
+    use crate::Message;
+
+    fn structured(message: JsonValue<'_>) -> String {
+        let mut buf = Vec::new();
+        message
+            .structured(&mut serde_json::Serializer::new(&mut buf))
+            .unwrap();
+        String::from_utf8(buf).unwrap()
+    }
+
+    #[test]
+    fn text_with_renderer() {
+        let message = JsonValue::new(json!({"passed": 3, "failed": 1}))
+            .with_text(|value| format!("{} passed, {} failed", value["passed"], value["failed"]));
+        assert_eq!(message.text(), "3 passed, 1 failed");
+    }
+
+    #[test]
+    fn text_without_renderer() {
+        let message = JsonValue::new(json!({"passed": 3}));
+        assert_eq!(message.text(), "{\n  \"passed\": 3\n}");
+    }
+
+    #[test]
+    fn json_ignores_renderer() {
+        let message =
+            JsonValue::new(json!({"passed": 3, "failed": 1})).with_text(|_| "ignored".to_string());
+        assert_eq!(structured(message), r#"{"failed":1,"passed":3}"#);
+    }
+}
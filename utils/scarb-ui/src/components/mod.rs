@@ -1,13 +1,17 @@
 //! This module provides various ready to use message types and widgets for use with
 //! a [`Ui`][crate::Ui].
 
+pub use json_value::*;
 pub use machine::*;
+pub use select::*;
 pub use spinner::*;
 pub use status::*;
 pub use typed::*;
 pub use value::*;
 
+mod json_value;
 mod machine;
+mod select;
 mod spinner;
 mod status;
 mod typed;
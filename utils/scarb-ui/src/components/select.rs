@@ -0,0 +1,88 @@
+use anyhow::{anyhow, Error, Result};
+use dialoguer::theme::ColorfulTheme;
+
+/// Menu asking the user to pick one of the listed items.
+///
+/// In interactive text mode, the menu is displayed and the index of the chosen item is returned.
+/// Otherwise, selecting fails with an error listing all items, optionally followed by a help
+/// message telling the user how to make the choice explicitly, for example with a CLI flag.
+pub struct Select {
+    prompt: String,
+    items: Vec<String>,
+    help: Option<String>,
+}
+
+impl Select {
+    /// Create a new [`Select`] with the given prompt and items to choose from.
+    pub fn new(prompt: impl Into<String>, items: impl IntoIterator<Item = impl ToString>) -> Self {
+        Self {
+            prompt: prompt.into(),
+            items: items.into_iter().map(|item| item.to_string()).collect(),
+            help: None,
+        }
+    }
+
+    /// Attach a help message to the error reported when the menu cannot be displayed.
+    pub fn with_help(self, help: impl Into<String>) -> Self {
+        Self {
+            help: Some(help.into()),
+            ..self
+        }
+    }
+
+    pub(crate) fn interact(self) -> Result<usize> {
+        Ok(dialoguer::Select::with_theme(&ColorfulTheme::default())
+            .with_prompt(self.prompt)
+            .items(&self.items)
+            .default(0)
+            .interact()?)
+    }
+
+    pub(crate) fn into_error(self) -> Error {
+        let mut msg = format!("{}:\n`{}`", self.prompt, self.items.join("`, `"));
+        if let Some(help) = self.help {
+            msg.push_str(&format!("\nhelp: {help}"));
+        }
+        anyhow!(msg)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Select;
+    use crate::{Interactivity, OutputFormat, Ui, Verbosity};
+
+    #[test]
+    fn select_lists_items_when_not_interactive() {
+        let ui = Ui::new(Verbosity::Normal, OutputFormat::Text)
+            .with_interactivity(Interactivity::Disabled);
+        assert_eq!(
+            ui.select(
+                Select::new("please choose a target", ["a", "b"])
+                    .with_help("use the `--target` flag to choose a target")
+            )
+            .unwrap_err()
+            .to_string(),
+            "please choose a target:\n`a`, `b`\nhelp: use the `--target` flag to choose a target"
+        );
+
+        let ui = Ui::new(Verbosity::Normal, OutputFormat::Json)
+            .with_interactivity(Interactivity::Enabled);
+        assert_eq!(
+            ui.select(Select::new("please choose a target", ["a", "b"]))
+                .unwrap_err()
+                .to_string(),
+            "please choose a target:\n`a`, `b`"
+        );
+    }
+
+    #[test]
+    fn interactivity_is_configurable() {
+        let ui = |output_format, interactivity| {
+            Ui::new(Verbosity::Normal, output_format).with_interactivity(interactivity)
+        };
+        assert!(ui(OutputFormat::Text, Interactivity::Enabled).is_interactive());
+        assert!(!ui(OutputFormat::Text, Interactivity::Disabled).is_interactive());
+        assert!(!ui(OutputFormat::Json, Interactivity::Enabled).is_interactive());
+    }
+}
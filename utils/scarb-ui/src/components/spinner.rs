@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use std::time::Duration;
 
 use indicatif::{ProgressBar, ProgressDrawTarget, ProgressStyle, WeakProgressBar};
@@ -27,9 +28,23 @@ pub struct SpinnerHandle {
     pb: ProgressBar,
 }
 
+impl SpinnerHandle {
+    /// Change the message displayed next to the spinner.
+    pub fn set_message(&self, message: impl Into<Cow<'static, str>>) {
+        self.pb.set_message(message);
+    }
+
+    /// Stop the spinner, leaving the given message displayed in its place.
+    pub fn finish_with_message(self, message: impl Into<Cow<'static, str>>) {
+        self.pb.finish_with_message(message);
+    }
+}
+
 impl Drop for SpinnerHandle {
     fn drop(&mut self) {
-        self.pb.finish_and_clear()
+        if !self.pb.is_finished() {
+            self.pb.finish_and_clear()
+        }
     }
 }
 
@@ -50,3 +65,25 @@ impl Widget for Spinner {
         SpinnerHandle { pb }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Spinner;
+    use crate::{OutputFormat, Ui, Verbosity};
+
+    #[test]
+    fn spinner_is_displayed_only_in_text_mode() {
+        let ui = Ui::new(Verbosity::Normal, OutputFormat::Text);
+        let handle = ui.widget(Spinner::new("Resolving"));
+        assert!(handle.is_some());
+        let handle = handle.unwrap();
+        handle.set_message("Downloading");
+        handle.finish_with_message("Done");
+
+        let ui = Ui::new(Verbosity::Normal, OutputFormat::Json);
+        assert!(ui.widget(Spinner::new("Resolving")).is_none());
+
+        let ui = Ui::new(Verbosity::Quiet, OutputFormat::Text);
+        assert!(ui.widget(Spinner::new("Resolving")).is_none());
+    }
+}
@@ -1,6 +1,7 @@
 use console::{pad_str, Alignment, Style};
 use serde::{Serialize, Serializer};
 
+use crate::message::print_wrapped;
 use crate::Message;
 
 /// Indication of starting or finishing of a significant process in the application.
@@ -11,6 +12,9 @@ use crate::Message;
 /// Padding is hardcoded to **12** characters, therefore avoid using words longer than
 /// **11** characters.
 /// The `message` part is a free-form text describing the details of what's going on.
+///
+/// In JSON mode, status messages are emitted as `{"status": "compiling", "message": "..."}`
+/// objects, with the `status` part lowercased, so that tools can track progress of the process.
 #[derive(Serialize)]
 pub struct Status<'a> {
     status: &'a str,
@@ -49,6 +53,10 @@ impl Message for Status<'_> {
         )
     }
 
+    fn print_text_wrapped(self, width: usize) {
+        print_wrapped(self.text(), width)
+    }
+
     fn structured<S: Serializer>(self, ser: S) -> Result<S::Ok, S::Error> {
         let status = self.status.to_lowercase();
         Status {
@@ -59,3 +67,41 @@ impl Message for Status<'_> {
         .serialize(ser)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use console::strip_ansi_codes;
+
+    use super::Status;
+    use crate::Message;
+
+    fn structured(message: Status<'_>) -> String {
+        let mut buf = Vec::new();
+        message
+            .structured(&mut serde_json::Serializer::new(&mut buf))
+            .unwrap();
+        String::from_utf8(buf).unwrap()
+    }
+
+    #[test]
+    fn text_is_padded() {
+        let text = Status::new("Executing", "hello").text();
+        assert_eq!(strip_ansi_codes(&text), "   Executing hello");
+    }
+
+    #[test]
+    fn json_has_status_and_message() {
+        assert_eq!(
+            structured(Status::new("Executing", "hello")),
+            r#"{"status":"executing","message":"hello"}"#
+        );
+    }
+
+    #[test]
+    fn json_ignores_color() {
+        assert_eq!(
+            structured(Status::with_color("Warning", "yellow", "hello")),
+            r#"{"status":"warning","message":"hello"}"#
+        );
+    }
+}
@@ -15,6 +15,9 @@ pub struct TypedMessage<'a> {
     #[serde(skip_serializing_if = "Option::is_none")]
     code: Option<&'a str>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    severity: Option<&'a str>,
+
     #[serde(skip)]
     type_style: Option<&'a str>,
     #[serde(skip)]
@@ -30,6 +33,7 @@ impl<'a> TypedMessage<'a> {
             type_style: Some(type_style),
             skip_type_for_text: false,
             code: None,
+            severity: None,
         }
     }
 
@@ -48,6 +52,7 @@ impl<'a> TypedMessage<'a> {
             type_style: None,
             skip_type_for_text: true,
             code: None,
+            severity: None,
         }
     }
 
@@ -62,6 +67,16 @@ impl<'a> TypedMessage<'a> {
             ..self
         }
     }
+
+    /// Mark this message as a diagnostic of the given severity, like `warning` or `error`.
+    ///
+    /// The severity is only emitted as a `severity` field in structured mode.
+    pub fn with_severity(self, severity: &'a str) -> Self {
+        Self {
+            severity: Some(severity),
+            ..self
+        }
+    }
 }
 
 impl Message for TypedMessage<'_> {
@@ -0,0 +1,151 @@
+use std::error::Error as StdError;
+use std::fmt;
+use std::process::ExitCode;
+
+/// Category of a failure, determining the exit code of Scarb extensions.
+///
+/// Exit codes of all categories are stable, so that scripts and CI can tell failures apart.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub enum FailureKind {
+    /// Compilation error, or any other failure which has no more specific category.
+    ///
+    /// Exits with code `1`.
+    #[default]
+    Build,
+    /// Invalid usage, like malformed arguments, or selecting packages which do not exist.
+    ///
+    /// Exits with code `2`, the same as when parsing arguments with [`clap`] fails.
+    Usage,
+    /// The program panicked or failed while being run.
+    ///
+    /// Exits with code `3`.
+    Runtime,
+    /// The proof could not be verified.
+    ///
+    /// Exits with code `4`.
+    Verification,
+}
+
+impl FailureKind {
+    /// The numeric exit code of this failure category.
+    pub const fn code(self) -> u8 {
+        match self {
+            Self::Build => 1,
+            Self::Usage => 2,
+            Self::Runtime => 3,
+            Self::Verification => 4,
+        }
+    }
+
+    /// The process exit code of this failure category.
+    pub fn exit_code(self) -> ExitCode {
+        ExitCode::from(self.code())
+    }
+
+    /// Find the category of the given error.
+    ///
+    /// Looks for a [`Failure`] in the error chain, and falls back to [`FailureKind::Build`]
+    /// if there is none.
+    pub fn of(error: &anyhow::Error) -> Self {
+        error
+            .chain()
+            .find_map(|cause| cause.downcast_ref::<Failure>())
+            .map(Failure::kind)
+            .unwrap_or_default()
+    }
+}
+
+/// An error tagged with a [`FailureKind`].
+///
+/// The tag is transparent: both the message and the source of this error are those of the wrapped
+/// error, so tagging does not change how errors are printed.
+pub struct Failure {
+    kind: FailureKind,
+    error: anyhow::Error,
+}
+
+impl Failure {
+    /// Tag the given error with a failure category.
+    pub fn new(kind: FailureKind, error: impl Into<anyhow::Error>) -> Self {
+        Self {
+            kind,
+            error: error.into(),
+        }
+    }
+
+    /// The category of this failure.
+    pub fn kind(&self) -> FailureKind {
+        self.kind
+    }
+}
+
+impl fmt::Debug for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.error, f)
+    }
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let error: &(dyn StdError + 'static) = self.error.as_ref();
+        fmt::Display::fmt(error, f)
+    }
+}
+
+impl StdError for Failure {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        let error: &(dyn StdError + 'static) = self.error.as_ref();
+        error.source()
+    }
+}
+
+/// Extension trait for tagging errors of [`Result`]s with a [`FailureKind`].
+pub trait FailureExt<T> {
+    /// Tag the error, if any, with the given failure category.
+    fn failure_kind(self, kind: FailureKind) -> anyhow::Result<T>;
+}
+
+impl<T, E> FailureExt<T> for Result<T, E>
+where
+    E: Into<anyhow::Error>,
+{
+    fn failure_kind(self, kind: FailureKind) -> anyhow::Result<T> {
+        self.map_err(|error| Failure::new(kind, error).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::{anyhow, Context};
+
+    use super::{FailureExt, FailureKind};
+
+    #[test]
+    fn untagged_errors_are_build_failures() {
+        assert_eq!(FailureKind::of(&anyhow!("oops")), FailureKind::Build);
+        assert_eq!(FailureKind::Build.code(), 1);
+    }
+
+    #[test]
+    fn finds_kind_in_error_chain() {
+        let error = Err::<(), _>(anyhow!("inner").context("middle"))
+            .failure_kind(FailureKind::Runtime)
+            .context("outer")
+            .unwrap_err();
+        assert_eq!(FailureKind::of(&error), FailureKind::Runtime);
+        assert_eq!(FailureKind::of(&error).code(), 3);
+    }
+
+    #[test]
+    fn tagging_does_not_change_messages() {
+        let error = || anyhow!("inner").context("middle").context("outer");
+        let tagged = Err::<(), _>(error())
+            .failure_kind(FailureKind::Verification)
+            .unwrap_err();
+        assert_eq!(format!("{tagged:#}"), format!("{:#}", error()));
+        assert_eq!(
+            tagged.chain().map(ToString::to_string).collect::<Vec<_>>(),
+            ["outer", "middle", "inner"]
+        );
+    }
+}
@@ -27,20 +27,26 @@ pub use indicatif::{
     BinaryBytes, DecimalBytes, FormattedDuration, HumanBytes, HumanCount, HumanDuration,
     HumanFloatCount,
 };
-use std::fmt::Debug;
+use std::env;
+use std::fmt::{Debug, Display};
+use std::io::{self, IsTerminal};
 use std::sync::{Arc, RwLock};
 
+pub use failure::*;
 pub use message::*;
 pub use verbosity::*;
 pub use widget::*;
+pub use wrap::TextWrap;
 
-use crate::components::TypedMessage;
+use crate::components::{Select, TypedMessage};
 
 pub mod args;
 pub mod components;
+mod failure;
 mod message;
 mod verbosity;
 mod widget;
+mod wrap;
 
 /// The requested format of output (either textual or JSON).
 #[derive(Copy, Clone, Debug, Default, Eq, PartialEq, ValueEnum)]
@@ -52,6 +58,50 @@ pub enum OutputFormat {
     Json,
 }
 
+impl Display for OutputFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Text => write!(f, "text"),
+            Self::Json => write!(f, "json"),
+        }
+    }
+}
+
+impl OutputFormat {
+    /// Get the output format from the given environment variable.
+    ///
+    /// Valid values are `text` and `json`.
+    pub fn from_env_var(env_var_name: &str) -> anyhow::Result<Self> {
+        let env_var = env::var(env_var_name)?;
+        <Self as ValueEnum>::from_str(&env_var, false)
+            .map_err(|_| anyhow::anyhow!("invalid output format: {env_var}"))
+    }
+}
+
+/// The stream to which [`Ui`] prints warnings and errors in JSON output format.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum JsonDiagnosticsStream {
+    /// Print warnings and errors to standard output, along with all other messages.
+    #[default]
+    Stdout,
+    /// Print warnings and errors to standard error, with a `severity` field.
+    ///
+    /// This keeps standard output free of diagnostics, so that it only carries data messages.
+    Stderr,
+}
+
+/// Controls whether [`Ui`] may display interactive prompts, like [`Select`] menus.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum Interactivity {
+    /// Display prompts only if both standard input and output are terminals.
+    #[default]
+    Auto,
+    /// Always display prompts.
+    Enabled,
+    /// Never display prompts.
+    Disabled,
+}
+
 /// An abstraction around console output which stores preferences for output format (human vs JSON),
 /// colour, etc.
 ///
@@ -60,6 +110,9 @@ pub enum OutputFormat {
 pub struct Ui {
     verbosity: Verbosity,
     output_format: OutputFormat,
+    text_wrap: TextWrap,
+    json_diagnostics: JsonDiagnosticsStream,
+    interactivity: Interactivity,
     state: Arc<RwLock<State>>,
 }
 
@@ -68,6 +121,9 @@ impl Debug for Ui {
         f.debug_struct("Ui")
             .field("verbosity", &self.verbosity)
             .field("output_format", &self.output_format)
+            .field("text_wrap", &self.text_wrap)
+            .field("json_diagnostics", &self.json_diagnostics)
+            .field("interactivity", &self.interactivity)
             .finish()
     }
 }
@@ -87,10 +143,42 @@ impl Ui {
         Self {
             verbosity,
             output_format,
+            text_wrap: TextWrap::default(),
+            json_diagnostics: JsonDiagnosticsStream::default(),
+            interactivity: Interactivity::default(),
             state: Default::default(),
         }
     }
 
+    /// Configure wrapping of long lines in textual messages.
+    ///
+    /// Wrapping is disabled by default. It only applies to messages consisting of plain prose,
+    /// like [`Status`][crate::components::Status], and never to JSON output.
+    pub fn with_text_wrap(self, text_wrap: TextWrap) -> Self {
+        Self { text_wrap, ..self }
+    }
+
+    /// Configure the stream to which warnings and errors are printed in JSON output format.
+    ///
+    /// Diagnostics are printed to standard output by default, for compatibility with consumers
+    /// reading all messages from a single stream. It never applies to textual output.
+    pub fn with_json_diagnostics(self, json_diagnostics: JsonDiagnosticsStream) -> Self {
+        Self {
+            json_diagnostics,
+            ..self
+        }
+    }
+
+    /// Configure whether interactive prompts may be displayed.
+    ///
+    /// By default, prompts are displayed only if both standard input and output are terminals.
+    pub fn with_interactivity(self, interactivity: Interactivity) -> Self {
+        Self {
+            interactivity,
+            ..self
+        }
+    }
+
     /// Get the verbosity level of this [`Ui`] instance.
     pub fn verbosity(&self) -> Verbosity {
         self.verbosity
@@ -101,6 +189,26 @@ impl Ui {
         self.output_format
     }
 
+    /// Check whether this [`Ui`] instance may display interactive prompts.
+    ///
+    /// Prompts are never displayed if the output format is JSON.
+    pub fn is_interactive(&self) -> bool {
+        self.output_format == OutputFormat::Text
+            && match self.interactivity {
+                Interactivity::Auto => io::stdin().is_terminal() && io::stdout().is_terminal(),
+                Interactivity::Enabled => true,
+                Interactivity::Disabled => false,
+            }
+    }
+
+    /// Get the width at which textual messages are wrapped, or `None` if they are not wrapped.
+    pub fn text_width(&self) -> Option<usize> {
+        match self.output_format {
+            OutputFormat::Text => self.text_wrap.width(),
+            OutputFormat::Json => None,
+        }
+    }
+
     /// Print the message to standard output if not in quiet verbosity mode.
     pub fn print<T: Message>(&self, message: T) {
         if self.verbosity >= Verbosity::Normal {
@@ -138,26 +246,48 @@ impl Ui {
         }
     }
 
+    /// Ask the user to pick one of the items of a [`Select`] menu, and return its index.
+    ///
+    /// The menu is only displayed if this [`Ui`] is interactive, see [`Ui::is_interactive`].
+    /// Otherwise, an error listing all items is returned.
+    pub fn select(&self, select: Select) -> anyhow::Result<usize> {
+        if self.is_interactive() {
+            select.interact()
+        } else {
+            Err(select.into_error())
+        }
+    }
+
     /// Print a warning to the user.
     pub fn warn(&self, message: impl AsRef<str>) {
-        self.print(TypedMessage::styled("warn", "yellow", message.as_ref()))
+        self.print_diagnostic(
+            TypedMessage::styled("warn", "yellow", message.as_ref()),
+            "warning",
+        )
     }
 
     /// Print an error to the user.
     pub fn error(&self, message: impl AsRef<str>) {
-        self.print(TypedMessage::styled("error", "red", message.as_ref()))
+        self.print_diagnostic(
+            TypedMessage::styled("error", "red", message.as_ref()),
+            "error",
+        )
     }
 
     /// Print a warning to the user.
     pub fn warn_with_code(&self, code: impl AsRef<str>, message: impl AsRef<str>) {
-        self.print(
+        self.print_diagnostic(
             TypedMessage::styled("warn", "yellow", message.as_ref()).with_code(code.as_ref()),
+            "warning",
         )
     }
 
     /// Print an error to the user.
     pub fn error_with_code(&self, code: impl AsRef<str>, message: impl AsRef<str>) {
-        self.print(TypedMessage::styled("error", "red", message.as_ref()).with_code(code.as_ref()))
+        self.print_diagnostic(
+            TypedMessage::styled("error", "red", message.as_ref()).with_code(code.as_ref()),
+            "error",
+        )
     }
 
     /// Nicely format an [`anyhow::Error`] for display to the user, and print it with [`Ui::error`].
@@ -176,10 +306,24 @@ impl Ui {
         self.warn(format!("{error:?}").trim())
     }
 
+    fn print_diagnostic(&self, message: TypedMessage<'_>, severity: &str) {
+        if self.verbosity < Verbosity::Normal {
+            return;
+        }
+        match (self.output_format, self.json_diagnostics) {
+            (OutputFormat::Json, JsonDiagnosticsStream::Stderr) => {
+                // Spinners are never displayed in JSON mode, so there is nothing to suspend.
+                message.with_severity(severity).eprint_json()
+            }
+            _ => self.do_print(message),
+        }
+    }
+
     fn do_print<T: Message>(&self, message: T) {
-        let print = || match self.output_format {
-            OutputFormat::Text => message.print_text(),
-            OutputFormat::Json => message.print_json(),
+        let print = || match (self.output_format, self.text_width()) {
+            (OutputFormat::Text, Some(width)) => message.print_text_wrapped(width),
+            (OutputFormat::Text, None) => message.print_text(),
+            (OutputFormat::Json, _) => message.print_json(),
         };
         let handle = self
             .state
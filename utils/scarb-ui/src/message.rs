@@ -2,6 +2,7 @@ use serde::Serializer;
 
 #[cfg(doc)]
 use super::Ui;
+use crate::wrap::wrap_text;
 
 const JSON_SKIP_MESSAGE: &str = "UI_INTERNAL_SKIP";
 
@@ -34,6 +35,21 @@ pub trait Message {
         }
     }
 
+    /// Print the textual representation of this message when [`Ui`] wraps lines to fit in
+    /// `width` columns.
+    ///
+    /// Default implementation prints the message as-is, because wrapping would break
+    /// preformatted content, like code snippets in compiler diagnostics.
+    /// Messages consisting of plain prose, like [`Status`][crate::components::Status], override
+    /// this to wrap their text.
+    #[doc(hidden)]
+    fn print_text_wrapped(self, _width: usize)
+    where
+        Self: Sized,
+    {
+        self.print_text()
+    }
+
     /// Serialize this structured message to a serializer which is routed to [`Ui`] output stream.
     ///
     /// Default implementation does not serialize anything, making [`Ui`] skip printing
@@ -55,21 +71,39 @@ pub trait Message {
     where
         Self: Sized,
     {
-        let mut buf = Vec::with_capacity(128);
-        let mut serializer = serde_json::Serializer::new(&mut buf);
-        match self.structured(&mut serializer) {
-            Ok(_) => {
-                let string = unsafe {
-                    // UNSAFE: JSON is always UTF-8 encoded.
-                    String::from_utf8_unchecked(buf)
-                };
-                println!("{string}");
-            }
-            Err(err) => {
-                if err.to_string() != JSON_SKIP_MESSAGE {
-                    panic!("JSON serialization of UI message must not fail: {err}")
-                }
+        if let Some(string) = to_json_string(self) {
+            println!("{string}");
+        }
+    }
+
+    #[doc(hidden)]
+    fn eprint_json(self)
+    where
+        Self: Sized,
+    {
+        if let Some(string) = to_json_string(self) {
+            eprintln!("{string}");
+        }
+    }
+}
+
+/// Serialize a structured message to a JSON string, or return `None` if it should be skipped.
+fn to_json_string<T: Message>(message: T) -> Option<String> {
+    let mut buf = Vec::with_capacity(128);
+    let mut serializer = serde_json::Serializer::new(&mut buf);
+    match message.structured(&mut serializer) {
+        Ok(_) => {
+            let string = unsafe {
+                // UNSAFE: JSON is always UTF-8 encoded.
+                String::from_utf8_unchecked(buf)
+            };
+            Some(string)
+        }
+        Err(err) => {
+            if err.to_string() != JSON_SKIP_MESSAGE {
+                panic!("JSON serialization of UI message must not fail: {err}")
             }
+            None
         }
     }
 }
@@ -85,3 +119,10 @@ impl Message for String {
         self
     }
 }
+
+/// Print the textual representation of a message, wrapping its lines to fit in `width` columns.
+pub(crate) fn print_wrapped(text: String, width: usize) {
+    if !text.is_empty() {
+        println!("{}", wrap_text(&text, width));
+    }
+}
@@ -0,0 +1,184 @@
+use std::iter;
+
+use console::measure_text_width;
+
+/// Controls whether and how [`Ui`][crate::Ui] wraps long lines of textual messages.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum TextWrap {
+    /// Print messages as-is.
+    #[default]
+    Disabled,
+    /// Wrap lines at the width of the terminal.
+    ///
+    /// Falls back to [`TextWrap::DEFAULT_WIDTH`] if standard output is not a terminal,
+    /// so that piped output stays stable.
+    Terminal,
+    /// Wrap lines at the given width, regardless of the terminal size.
+    Fixed(usize),
+}
+
+impl TextWrap {
+    /// Width used by [`TextWrap::Terminal`] when standard output is not a terminal.
+    pub const DEFAULT_WIDTH: usize = 80;
+
+    /// Resolve the width at which lines should be wrapped, or `None` if wrapping is disabled.
+    pub fn width(self) -> Option<usize> {
+        match self {
+            Self::Disabled => None,
+            Self::Terminal => Some(
+                console::Term::stdout()
+                    .size_checked()
+                    .map(|(_rows, cols)| cols as usize)
+                    .unwrap_or(Self::DEFAULT_WIDTH),
+            ),
+            Self::Fixed(width) => Some(width),
+        }
+    }
+}
+
+/// Wrap each line of `text` at word boundaries so that it fits in `width` columns.
+///
+/// Continuation lines are indented the same way as the line they were split from.
+/// Whitespace between words is kept as-is, except where a line is broken.
+/// Words which do not fit in a line on their own are never broken.
+/// ANSI escape codes do not count towards the line width.
+pub(crate) fn wrap_text(text: &str, width: usize) -> String {
+    text.split('\n')
+        .map(|line| wrap_line(line, width))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn wrap_line(line: &str, width: usize) -> String {
+    if measure_text_width(line) <= width {
+        return line.to_string();
+    }
+
+    let content = line.trim_start_matches(' ');
+    let indent = &line[..line.len() - content.len()];
+    let indent_width = measure_text_width(indent);
+
+    let mut lines = Vec::new();
+    let mut current = indent.to_string();
+    let mut current_width = indent_width;
+    for (gap, word) in words(content) {
+        let gap_width = measure_text_width(gap);
+        let word_width = measure_text_width(word);
+        if current_width > indent_width && current_width + gap_width + word_width > width {
+            // Trailing whitespace is dropped rather than moved to an empty line of its own.
+            if word.is_empty() {
+                break;
+            }
+            lines.push(current);
+            current = indent.to_string();
+            current_width = indent_width;
+        } else {
+            current.push_str(gap);
+            current_width += gap_width;
+        }
+        current.push_str(word);
+        current_width += word_width;
+    }
+    lines.push(current);
+    lines.join("\n")
+}
+
+/// Split `text` into words, each paired with the run of spaces preceding it.
+///
+/// Trailing spaces are yielded as a final pair with an empty word.
+fn words(text: &str) -> impl Iterator<Item = (&str, &str)> {
+    let mut rest = text;
+    iter::from_fn(move || {
+        if rest.is_empty() {
+            return None;
+        }
+        let word_start = rest.len() - rest.trim_start_matches(' ').len();
+        let word_end = rest[word_start..]
+            .find(' ')
+            .map_or(rest.len(), |i| word_start + i);
+        let (gap, word) = (&rest[..word_start], &rest[word_start..word_end]);
+        rest = &rest[word_end..];
+        Some((gap, word))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+
+    use super::{wrap_text, TextWrap};
+    use crate::{OutputFormat, Ui, Verbosity};
+
+    #[test]
+    fn short_lines_are_untouched() {
+        let text = "error: something went wrong\n\nCaused by:\n    oops";
+        assert_eq!(wrap_text(text, 80), text);
+    }
+
+    #[test]
+    fn wraps_at_forced_width() {
+        let text = indoc! {"
+            error: failed to resolve dependencies of the package
+            Caused by:
+                cannot find package with the given name in the registry index
+        "};
+        assert_eq!(
+            wrap_text(text, 30),
+            indoc! {"
+                error: failed to resolve
+                dependencies of the package
+                Caused by:
+                    cannot find package with
+                    the given name in the
+                    registry index
+            "}
+        );
+    }
+
+    #[test]
+    fn does_not_break_long_words() {
+        assert_eq!(
+            wrap_text("  see /a/very/long/path/to/some/file.cairo", 10),
+            "  see\n  /a/very/long/path/to/some/file.cairo"
+        );
+    }
+
+    #[test]
+    fn preserves_whitespace_between_words() {
+        assert_eq!(
+            wrap_text("  key:   value  with   gaps and more words", 24),
+            "  key:   value  with\n  gaps and more words"
+        );
+    }
+
+    #[test]
+    fn drops_whitespace_at_line_breaks() {
+        assert_eq!(wrap_text("abc   def   ", 5), "abc\ndef");
+    }
+
+    #[test]
+    fn ignores_ansi_codes_when_measuring() {
+        let text = "\u{1b}[33mwarn\u{1b}[0m: abc def";
+        assert_eq!(wrap_text(text, 13), text);
+    }
+
+    #[test]
+    fn fixed_width() {
+        assert_eq!(TextWrap::Disabled.width(), None);
+        assert_eq!(TextWrap::Fixed(42).width(), Some(42));
+    }
+
+    #[test]
+    fn ui_text_width() {
+        let ui = Ui::new(Verbosity::Normal, OutputFormat::Text);
+        assert_eq!(ui.text_width(), None);
+        let ui = ui.with_text_wrap(TextWrap::Fixed(42));
+        assert_eq!(ui.text_width(), Some(42));
+    }
+
+    #[test]
+    fn no_wrapping_in_json_mode() {
+        let ui = Ui::new(Verbosity::Normal, OutputFormat::Json).with_text_wrap(TextWrap::Fixed(42));
+        assert_eq!(ui.text_width(), None);
+    }
+}
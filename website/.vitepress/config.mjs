@@ -52,6 +52,7 @@ const sidebar = {
       text: "Reference",
       items: [
         p("Compilation model", "/docs/reference/compilation-model"),
+        p("Configuration", "/docs/reference/config"),
         p("Conditional compilation", "/docs/reference/conditional-compilation"),
         p("Procedural Macros", "/docs/reference/procedural-macro"),
         p("Global directories", "/docs/reference/global-directories"),